@@ -1,10 +1,26 @@
 use std::time::Instant;
 
 use eframe::egui;
-use rcs_core::{Updatable, World, WorldConfig};
+use rcs_core::{ConflictRule, DeathCause, PolicyKind, Updatable, UpdateOrder, World, WorldConfig};
+use rcs_script::Scenario;
 
 use crate::world_view;
 
+/// File the best genome of a run is exported to, relative to the working
+/// directory.
+const GENOME_EXPORT_PATH: &str = "best_genome.txt";
+
+/// Scenario script suggested in the path field, relative to the working
+/// directory.
+const DEFAULT_SCENARIO_PATH: &str = "rcs_script/scenarios/oasis.rhai";
+
+/// Number of lines of script output shown below the scenario controls.
+const SCENARIO_OUTPUT_LINES: usize = 8;
+
+/// Number of cells from which a grid counts as large. Large grids get a hint
+/// that only the synchronous update order decides moves in parallel.
+const LARGE_GRID_CELLS: usize = 10_000;
+
 /// Top-level GUI state for the 2-D resource competition simulation.
 ///
 /// `SimulationApp` owns a [`World`] and its [`WorldConfig`], and is responsible
@@ -14,6 +30,8 @@ use crate::world_view;
 /// - exposing basic controls (reset, pause, single-step)
 /// - adjusting visualization parameters (cell size)
 /// - editing the world configuration before a reset
+/// - inspecting an agent selected by clicking on its cell
+/// - loading a scenario script and reloading it when the file changes
 ///
 /// The app is integrated into `eframe` by implementing [`eframe::App`].
 pub struct SimulationApp {
@@ -26,6 +44,19 @@ pub struct SimulationApp {
 
     step_interval: f32,
     last_step: Instant,
+
+    last_error: Option<String>,
+    debug_checks: bool,
+    selected_agent: Option<usize>,
+    show_pheromones: bool,
+    show_ownership: bool,
+    export_status: Option<String>,
+
+    scenario: Option<Scenario>,
+    scenario_path: String,
+    auto_reload: bool,
+    scenario_status: Option<String>,
+    scenario_output: Vec<String>,
 }
 
 impl SimulationApp {
@@ -48,8 +79,139 @@ impl SimulationApp {
             tick: 0,
             step_interval: 0.2,
             last_step: Instant::now(),
+            last_error: None,
+            debug_checks: false,
+            selected_agent: None,
+            show_pheromones: false,
+            show_ownership: false,
+            export_status: None,
+            scenario: None,
+            scenario_path: DEFAULT_SCENARIO_PATH.to_string(),
+            auto_reload: true,
+            scenario_status: None,
+            scenario_output: Vec::new(),
+        }
+    }
+
+    /// Rebuilds the world from the current configuration.
+    ///
+    /// An invalid configuration keeps the current world and reports the
+    /// problem in the top panel instead.
+    fn reset(&mut self) {
+        match World::try_from_config(self.config) {
+            Ok(world) => {
+                self.world = world;
+                self.world.set_debug_checks(self.debug_checks);
+                if let Some(scenario) = &self.scenario {
+                    scenario.install(&mut self.world);
+                }
+                self.tick = 0;
+                self.last_step = Instant::now();
+                self.last_error = None;
+                self.selected_agent = None;
+            }
+            Err(err) => self.last_error = Some(err.to_string()),
+        }
+    }
+
+    /// Advances the world by one tick and records any reported error.
+    ///
+    /// The error of the most recent failing tick is kept so it can be shown
+    /// in the top panel; a successful tick does not clear it.
+    fn step(&mut self) {
+        if let Err(err) = self.world.update() {
+            self.last_error = Some(format!("tick {}: {err}", self.tick));
+        }
+        self.tick += 1;
+    }
+
+    /// Loads the scenario at `scenario_path` and restarts the world with it.
+    ///
+    /// The scenario's `config()` overrides are applied to the world
+    /// configuration before the reset.
+    fn load_scenario(&mut self) {
+        let loaded = Scenario::load(&self.scenario_path).and_then(|scenario| {
+            let config = scenario.world_config(self.config)?;
+            Ok((scenario, config))
+        });
+        match loaded {
+            Ok((scenario, config)) => {
+                self.config = config;
+                self.scenario = Some(scenario);
+                self.scenario_status = Some(format!("Loaded {}", self.scenario_path));
+                self.scenario_output.clear();
+                self.reset();
+            }
+            Err(err) => self.scenario_status = Some(err.to_string()),
         }
     }
+
+    /// Removes the scenario and restarts the world without it.
+    fn unload_scenario(&mut self) {
+        self.scenario = None;
+        self.scenario_status = None;
+        self.scenario_output.clear();
+        self.reset();
+    }
+
+    /// Reloads the scenario if its file changed and collects script errors
+    /// and printed lines.
+    ///
+    /// The reloaded rules are installed into the running world right away;
+    /// `config()` changes only take effect on the next reset.
+    fn poll_scenario(&mut self) {
+        let Some(scenario) = &mut self.scenario else {
+            return;
+        };
+        if self.auto_reload {
+            match scenario.reload_if_changed() {
+                Ok(true) => {
+                    self.world.clear_hooks();
+                    if self.world.policy().name() == "script" {
+                        self.world.set_policy(self.config.policy.build());
+                    }
+                    scenario.install(&mut self.world);
+                    self.scenario_status = Some(format!("Reloaded at tick {}", self.tick));
+                }
+                Ok(false) => {}
+                Err(err) => self.scenario_status = Some(err.to_string()),
+            }
+        }
+        if let Some(err) = scenario.take_error() {
+            self.scenario_status = Some(err.to_string());
+        }
+        self.scenario_output.extend(scenario.take_output());
+        let excess = self
+            .scenario_output
+            .len()
+            .saturating_sub(SCENARIO_OUTPUT_LINES);
+        self.scenario_output.drain(..excess);
+    }
+
+    /// Writes the best genome of the current run to [`GENOME_EXPORT_PATH`].
+    ///
+    /// The outcome is shown below the export button.
+    fn export_best_genome(&mut self) {
+        let Some(genome) = self.world.best_genome() else {
+            return;
+        };
+        self.export_status = Some(
+            match std::fs::write(GENOME_EXPORT_PATH, genome.to_string()) {
+                Ok(()) => format!("Saved to {GENOME_EXPORT_PATH}"),
+                Err(err) => format!("Export failed: {err}"),
+            },
+        );
+    }
+
+    /// Selects the first living agent on a clicked cell.
+    ///
+    /// Clicking a cell without living agents clears the selection.
+    ///
+    /// ### Parameters
+    /// - `cid`: Id of the clicked cell.
+    fn select_agent_on(&mut self, cid: usize) {
+        self.selected_agent = self.world.agents_in_cell(cid).next().map(|a| a.id());
+    }
 }
 
 impl eframe::App for SimulationApp {
@@ -64,9 +226,7 @@ impl eframe::App for SimulationApp {
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
             ui.horizontal(|ui| {
                 if ui.button("Reset").clicked() {
-                    self.world = World::from_config(self.config);
-                    self.tick = 0;
-                    self.last_step = Instant::now();
+                    self.reset();
                 }
 
                 if ui
@@ -81,12 +241,16 @@ impl eframe::App for SimulationApp {
                 }
 
                 if ui.button("Step").clicked() {
-                    let _ = self.world.update();
-                    self.tick += 1;
+                    self.step();
                 }
 
                 ui.separator();
                 ui.label(format!("Tick: {}", self.tick));
+
+                if let Some(err) = &self.last_error {
+                    ui.separator();
+                    ui.colored_label(egui::Color32::LIGHT_RED, err);
+                }
             });
         });
 
@@ -97,6 +261,8 @@ impl eframe::App for SimulationApp {
                 ui.heading("View");
                 ui.label("Cell size:");
                 ui.add(egui::Slider::new(&mut self.cell_px, 5.0..=100.0).text("px"));
+                ui.checkbox(&mut self.show_pheromones, "Pheromone overlay");
+                ui.checkbox(&mut self.show_ownership, "Ownership overlay");
                 ui.separator();
 
                 ui.heading("Simulation Speed");
@@ -104,34 +270,254 @@ impl eframe::App for SimulationApp {
                 ui.add(egui::Slider::new(&mut self.step_interval, 0.01..=1.0).text("s"));
                 ui.separator();
 
+                ui.heading("Population");
+                ui.label(format!(
+                    "Living agents: {}",
+                    self.world.agents().iter().filter(|a| a.is_alive()).count()
+                ));
+                let infected = self
+                    .world
+                    .agents()
+                    .iter()
+                    .filter(|a| a.is_alive() && a.health_state().is_infected())
+                    .count();
+                ui.label(format!("Currently infected: {infected}"));
+                let stats = self.world.statistics();
+                ui.label(format!("Births: {}", stats.births));
+                ui.label(format!("Infections: {}", stats.infections));
+                ui.label(format!("Cells claimed: {}", stats.claims));
+                ui.label(format!("Fights: {}", stats.fights));
+                ui.label(format!("Food shared: {}", stats.shared));
+                ui.label(format!("Archived agents: {}", self.world.graveyard().len()));
+                for cause in DeathCause::ALL {
+                    ui.label(format!(
+                        "Deaths ({}): {}",
+                        cause.label(),
+                        stats.deaths(cause)
+                    ));
+                }
+                if let Some(age) = stats.mean_age_at_death() {
+                    ui.label(format!("Mean age at death: {age:.1}"));
+                }
+                group_statistics_ui(ui, &self.world);
+                if ui
+                    .add_enabled(
+                        self.world.best_genome().is_some(),
+                        egui::Button::new("Export best genome"),
+                    )
+                    .clicked()
+                {
+                    self.export_best_genome();
+                }
+                if let Some(status) = &self.export_status {
+                    ui.label(status);
+                }
+                ui.separator();
+
+                ui.heading("Scenario");
+                ui.text_edit_singleline(&mut self.scenario_path);
+                ui.horizontal(|ui| {
+                    if ui.button("Load").clicked() {
+                        self.load_scenario();
+                    }
+                    if ui
+                        .add_enabled(self.scenario.is_some(), egui::Button::new("Unload"))
+                        .clicked()
+                    {
+                        self.unload_scenario();
+                    }
+                });
+                ui.checkbox(&mut self.auto_reload, "Reload when the file changes");
+                if let Some(status) = &self.scenario_status {
+                    ui.label(status);
+                }
+                for line in &self.scenario_output {
+                    ui.monospace(line);
+                }
+                ui.separator();
+
+                ui.heading("Debug");
+                if ui
+                    .checkbox(&mut self.debug_checks, "Ledger & invariant checks")
+                    .changed()
+                {
+                    self.world.set_debug_checks(self.debug_checks);
+                }
+                if let Some(ledger) = self.world.last_ledger() {
+                    ui.label(format!("Regenerated: {}", ledger.regenerated));
+                    ui.label(format!("Consumed: {}", ledger.consumed));
+                    ui.label(format!("Corpses: {}", ledger.corpse_deposited));
+                    ui.label(format!("Wasted: {}", ledger.wasted));
+                    ui.label(format!(
+                        "Hooks: +{} / -{}",
+                        ledger.hook_added, ledger.hook_removed
+                    ));
+                }
+                ui.separator();
+
+                agent_inspector_ui(ui, &self.world, self.selected_agent);
+                ui.separator();
+
                 world_config_ui(ui, &mut self.config);
             });
 
+        self.poll_scenario();
         if !self.paused {
             let now = Instant::now();
             let dt = now.duration_since(self.last_step).as_secs_f32();
 
             if dt >= self.step_interval {
-                let _ = self.world.update();
-                self.tick += 1;
+                self.step();
                 self.last_step = now;
             }
         }
 
         egui::CentralPanel::default().show(ctx, |ui| {
-            world_view::draw_world(ui, &self.world, &self.config, self.cell_px);
+            let clicked = world_view::draw_world(
+                ui,
+                &self.world,
+                &self.config,
+                self.cell_px,
+                self.selected_agent,
+                self.show_pheromones,
+                self.show_ownership,
+            );
+            if let Some(cid) = clicked {
+                self.select_agent_on(cid);
+            }
         });
         ctx.request_repaint();
     }
 }
 
+/// Renders one row per group comparing survival, births and sharing.
+///
+/// Nothing is shown while the group layer is disabled.
+///
+/// ### Parameters
+/// - `ui`: egui UI to draw into.
+/// - `world`: The world whose groups are summarized.
+fn group_statistics_ui(ui: &mut egui::Ui, world: &World) {
+    let stats = world.group_statistics();
+    if stats.is_empty() {
+        return;
+    }
+    let groups = world.groups();
+    egui::Grid::new("group_stats_grid")
+        .num_columns(6)
+        .striped(true)
+        .show(ui, |ui| {
+            for header in ["group", "kind", "living", "dead", "births", "shared"] {
+                ui.label(header);
+            }
+            ui.end_row();
+            for group in stats {
+                ui.label(group.group.to_string());
+                ui.label(if groups.is_altruistic(group.group) {
+                    "altruistic"
+                } else {
+                    "selfish"
+                });
+                ui.label(group.living.to_string());
+                ui.label(group.dead.to_string());
+                ui.label(group.births.to_string());
+                ui.label(group.shared.to_string());
+                ui.end_row();
+            }
+        });
+}
+
+/// Renders details about the selected agent, including its memory.
+///
+/// ### Parameters
+/// - `ui`: egui UI to draw into.
+/// - `world`: The world the agent lives in.
+/// - `selected`: Id of the selected agent, if any.
+fn agent_inspector_ui(ui: &mut egui::Ui, world: &World, selected: Option<usize>) {
+    ui.heading("Agent");
+    let Some(id) = selected else {
+        ui.label("Click an agent to inspect it.");
+        return;
+    };
+    let Some(agent) = world.agent(id) else {
+        match world.grave(id) {
+            Some(record) => ui.label(format!(
+                "Agent {id} died of {} at tick {} and was archived.",
+                record.cause.map_or("unknown causes", |c| c.label()),
+                record.death_tick.map_or("?".to_string(), |t| t.to_string()),
+            )),
+            None => ui.label("Click an agent to inspect it."),
+        };
+        return;
+    };
+
+    ui.label(format!("Id: {}", agent.id()));
+    ui.label(format!("Cell: {}", agent.cid()));
+    ui.label(format!("HP: {}", agent.health_point()));
+    ui.label(format!("Need per tick: {}", agent.need()));
+    ui.label(format!("Energy: {}", agent.energy()));
+    match agent.lifespan() {
+        Some(lifespan) => ui.label(format!("Age: {} / {lifespan}", agent.age())),
+        None => ui.label(format!("Age: {}", agent.age())),
+    };
+    ui.label(format!("Offspring: {}", agent.offspring()));
+    ui.label(format!("Health state: {}", agent.health_state().label()));
+    ui.label(format!("Aggression: {:.2}", agent.aggression()));
+    if let Some(group) = agent.group() {
+        ui.label(format!("Group: {group} (gave {} energy)", agent.donated()));
+    }
+    ui.label(format!(
+        "Territory: {} cells",
+        world.territories().territory_size(agent.id())
+    ));
+    if !agent.is_alive() {
+        let cause = agent.death_cause().map_or("unknown", |c| c.label());
+        ui.colored_label(egui::Color32::LIGHT_RED, format!("Dead ({cause})"));
+    }
+
+    let Some(memory) = agent.memory() else {
+        ui.label("No memory.");
+        return;
+    };
+    ui.label(format!(
+        "Memory ({}/{}):",
+        memory.entries().count(),
+        memory.capacity()
+    ));
+    egui::Grid::new("agent_memory_grid")
+        .num_columns(3)
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label("cell");
+            ui.label("resource");
+            ui.label("tick");
+            ui.end_row();
+            for entry in memory.entries().rev() {
+                ui.label(entry.cid.to_string());
+                ui.label(entry.resource.to_string());
+                ui.label(entry.tick.to_string());
+                ui.end_row();
+            }
+        });
+}
+
 /// Renders the interactive controls for editing a [`WorldConfig`].
 ///
 /// This helper is used in the right-hand side panel to adjust:
 /// - world dimensions (`width × height`)
-/// - cell initial resource range and regeneration rate range
+/// - cell initial resource, regeneration rate and terrain cost ranges
 /// - agent count and consumption-rate ranges
 /// - fixed initial agent health point
+/// - random seed and agent update order, with a hint on grids past
+///   [`LARGE_GRID_CELLS`] that only the synchronous order runs in parallel
+/// - optional cell capacity, the compaction interval for dead agents and
+///   the rule for contested cells
+/// - movement policy, perception settings and agent memory size
+/// - pheromone deposit, evaporation and diffusion
+/// - the evolutionary mode (reproduction, energy and mutation)
+/// - agent lifespans and age-dependent consumption
+/// - the epidemic layer
+/// - territory claims, fights over scarce cells and group food sharing
 ///
 /// The values edited here only take effect after the user presses **Reset** button.
 ///
@@ -169,6 +555,11 @@ fn world_config_ui(ui: &mut egui::Ui, cfg: &mut WorldConfig) {
             ui.add(egui::DragValue::new(&mut cfg.max_regen_rate).range(0..=10));
             ui.end_row();
 
+            ui.label("Cell: terrain cost");
+            ui.add(egui::DragValue::new(&mut cfg.min_terrain_cost).range(1..=cfg.max_terrain_cost));
+            ui.add(egui::DragValue::new(&mut cfg.max_terrain_cost).range(1..=10));
+            ui.end_row();
+
             ui.label("Agents: initial count");
             ui.add(egui::DragValue::new(&mut cfg.min_agents).range(1..=cfg.max_agents));
             ui.add(egui::DragValue::new(&mut cfg.max_agents).range(1..=2000));
@@ -188,4 +579,260 @@ fn world_config_ui(ui: &mut egui::Ui, cfg: &mut WorldConfig) {
     ui.label("Agent HP (initial, fixed):");
     ui.add(egui::DragValue::new(&mut cfg.agent_hp).range(1..=1000));
     ui.separator();
+
+    ui.heading("Scheduling");
+    let mut fixed_seed = cfg.seed.is_some();
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut fixed_seed, "Fixed seed");
+        let mut seed = cfg.seed.unwrap_or(0);
+        ui.add_enabled(fixed_seed, egui::DragValue::new(&mut seed));
+        cfg.seed = fixed_seed.then_some(seed);
+    });
+    egui::ComboBox::from_label("Agent update order")
+        .selected_text(cfg.update_order.label())
+        .show_ui(ui, |ui| {
+            for order in UpdateOrder::ALL {
+                ui.selectable_value(&mut cfg.update_order, order, order.label());
+            }
+        });
+    let is_large = cfg.width * cfg.height >= LARGE_GRID_CELLS;
+    if cfg!(feature = "parallel") && is_large && cfg.update_order != UpdateOrder::Synchronous {
+        ui.label("Only synchronous move decisions run in parallel.");
+    }
+
+    let mut capped = cfg.cell_capacity.is_some();
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut capped, "Cell capacity");
+        let mut capacity = cfg.cell_capacity.unwrap_or(1);
+        ui.add_enabled(capped, egui::DragValue::new(&mut capacity).range(1..=100));
+        cfg.cell_capacity = capped.then_some(capacity);
+    });
+    let mut compacting = cfg.compaction_interval.is_some();
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut compacting, "Compact dead agents every");
+        let mut interval = cfg.compaction_interval.unwrap_or(100);
+        ui.add_enabled(
+            compacting,
+            egui::DragValue::new(&mut interval).range(1..=10_000),
+        );
+        ui.label("ticks");
+        cfg.compaction_interval = compacting.then_some(interval);
+    });
+    egui::ComboBox::from_label("Contested cells")
+        .selected_text(cfg.conflict_rule.label())
+        .show_ui(ui, |ui| {
+            for rule in ConflictRule::ALL {
+                ui.selectable_value(&mut cfg.conflict_rule, rule, rule.label());
+            }
+        });
+    ui.separator();
+
+    ui.heading("Behaviour");
+    egui::ComboBox::from_label("Movement policy")
+        .selected_text(cfg.policy.label())
+        .show_ui(ui, |ui| {
+            for kind in PolicyKind::ALL {
+                ui.selectable_value(&mut cfg.policy, kind, kind.label());
+            }
+        });
+    ui.horizontal(|ui| {
+        ui.label("Perception radius:");
+        ui.add(egui::DragValue::new(&mut cfg.perception.radius).range(1..=20));
+    });
+    ui.horizontal(|ui| {
+        ui.label("Upkeep per extra radius:");
+        ui.add(egui::DragValue::new(&mut cfg.perception.cost_per_radius).range(0..=10));
+    });
+    ui.checkbox(&mut cfg.perception.see_agents, "Agents see other agents");
+    ui.horizontal(|ui| {
+        ui.label("Memory size (cells, 0 = off):");
+        ui.add(egui::DragValue::new(&mut cfg.agent_memory).range(0..=64));
+    });
+    ui.separator();
+
+    ui.heading("Pheromones");
+    ui.checkbox(&mut cfg.pheromone.enabled, "Fed agents leave trails");
+    ui.add_enabled_ui(cfg.pheromone.enabled, |ui| {
+        ui.horizontal(|ui| {
+            ui.label("Deposit when fed:");
+            ui.add(
+                egui::DragValue::new(&mut cfg.pheromone.deposit)
+                    .range(0.0..=10.0)
+                    .speed(0.1),
+            );
+        });
+        ui.add(egui::Slider::new(&mut cfg.pheromone.evaporation, 0.0..=1.0).text("evaporation"));
+        ui.add(egui::Slider::new(&mut cfg.pheromone.diffusion, 0.0..=1.0).text("diffusion"));
+    });
+    ui.separator();
+
+    ui.heading("Evolution");
+    ui.checkbox(&mut cfg.evolution.enabled, "Reproduction & mutation");
+    ui.add_enabled_ui(cfg.evolution.enabled, |ui| {
+        egui::Grid::new("evolution_grid")
+            .num_columns(2)
+            .spacing([10.0, 4.0])
+            .show(ui, |ui| {
+                ui.label("Hidden units");
+                ui.add(egui::DragValue::new(&mut cfg.evolution.hidden).range(1..=32));
+                ui.end_row();
+                ui.label("Appetite (extra food)");
+                ui.add(egui::DragValue::new(&mut cfg.evolution.appetite).range(0..=20));
+                ui.end_row();
+                ui.label("Birth cost (energy)");
+                ui.add(egui::DragValue::new(&mut cfg.evolution.birth_cost).range(1..=200));
+                ui.end_row();
+                ui.label("Child HP");
+                ui.add(egui::DragValue::new(&mut cfg.evolution.child_hp).range(1..=1000));
+                ui.end_row();
+                ui.label("Mutation rate");
+                ui.add(egui::Slider::new(
+                    &mut cfg.evolution.mutation_rate,
+                    0.0..=1.0,
+                ));
+                ui.end_row();
+                ui.label("Mutation size");
+                ui.add(egui::Slider::new(
+                    &mut cfg.evolution.mutation_size,
+                    0.0..=2.0,
+                ));
+                ui.end_row();
+                ui.label("Max agents");
+                ui.add(egui::DragValue::new(&mut cfg.evolution.max_agents).range(1..=5000));
+                ui.end_row();
+            });
+    });
+    ui.label("Use the \"neural\" movement policy to let genomes steer.");
+    ui.separator();
+
+    ui.heading("Aging");
+    ui.checkbox(&mut cfg.aging.enabled, "Maximum lifespan");
+    ui.add_enabled_ui(cfg.aging.enabled, |ui| {
+        ui.horizontal(|ui| {
+            ui.label("Lifespan:");
+            ui.add(
+                egui::DragValue::new(&mut cfg.aging.min_lifespan).range(1..=cfg.aging.max_lifespan),
+            );
+            ui.label("to");
+            ui.add(egui::DragValue::new(&mut cfg.aging.max_lifespan).range(1..=100_000));
+        });
+        ui.horizontal(|ui| {
+            ui.label("Extra need per quarter of life:");
+            ui.add(egui::DragValue::new(&mut cfg.aging.senescence).range(0..=10));
+        });
+    });
+    ui.separator();
+
+    ui.heading("Disease");
+    ui.checkbox(&mut cfg.disease.enabled, "Epidemic");
+    ui.add_enabled_ui(cfg.disease.enabled, |ui| {
+        egui::Grid::new("disease_grid")
+            .num_columns(2)
+            .spacing([10.0, 4.0])
+            .show(ui, |ui| {
+                ui.label("Initially infected");
+                ui.add(egui::DragValue::new(&mut cfg.disease.initial_infected).range(0..=2000));
+                ui.end_row();
+                ui.label("Transmission (same cell)");
+                ui.add(egui::Slider::new(&mut cfg.disease.transmission, 0.0..=1.0));
+                ui.end_row();
+                ui.label("Transmission (adjacent)");
+                ui.add(egui::Slider::new(
+                    &mut cfg.disease.adjacent_transmission,
+                    0.0..=1.0,
+                ));
+                ui.end_row();
+                ui.label("Duration (ticks)");
+                ui.add(egui::DragValue::new(&mut cfg.disease.duration).range(1..=1000));
+                ui.end_row();
+                ui.label("Extra need while sick");
+                ui.add(egui::DragValue::new(&mut cfg.disease.extra_need).range(0..=10));
+                ui.end_row();
+                ui.label("HP drain per tick");
+                ui.add(egui::DragValue::new(&mut cfg.disease.hp_drain).range(0..=10));
+                ui.end_row();
+            });
+        ui.checkbox(
+            &mut cfg.disease.lasting_immunity,
+            "Recovered agents stay immune",
+        );
+    });
+    ui.separator();
+
+    ui.heading("Territory");
+    ui.checkbox(&mut cfg.territory.enabled, "Agents claim cells");
+    ui.add_enabled_ui(cfg.territory.enabled, |ui| {
+        egui::Grid::new("territory_grid")
+            .num_columns(2)
+            .spacing([10.0, 4.0])
+            .show(ui, |ui| {
+                ui.label("Ticks to claim");
+                ui.add(egui::DragValue::new(&mut cfg.territory.claim_after).range(1..=100));
+                ui.end_row();
+                ui.label("Abandoned after (0 = never)");
+                ui.add(egui::DragValue::new(&mut cfg.territory.abandon_after).range(0..=1000));
+                ui.end_row();
+                ui.label("Intrusion cost");
+                ui.add(egui::DragValue::new(&mut cfg.territory.intrusion_cost).range(0..=10));
+                ui.end_row();
+            });
+        ui.checkbox(&mut cfg.territory.owner_priority, "Owners are fed first");
+    });
+    ui.separator();
+
+    ui.heading("Combat");
+    ui.checkbox(&mut cfg.combat.enabled, "Fight over scarce cells");
+    ui.add_enabled_ui(cfg.combat.enabled, |ui| {
+        egui::Grid::new("combat_grid")
+            .num_columns(2)
+            .spacing([10.0, 4.0])
+            .show(ui, |ui| {
+                ui.label("HP cost per fight");
+                ui.add(egui::DragValue::new(&mut cfg.combat.fight_cost).range(0..=10));
+                ui.end_row();
+                ui.label("Min aggression");
+                ui.add(egui::Slider::new(
+                    &mut cfg.combat.min_aggression,
+                    0.0..=cfg.combat.max_aggression,
+                ));
+                ui.end_row();
+                ui.label("Max aggression");
+                ui.add(egui::Slider::new(
+                    &mut cfg.combat.max_aggression,
+                    cfg.combat.min_aggression..=1.0,
+                ));
+                ui.end_row();
+            });
+    });
+    ui.separator();
+
+    ui.heading("Groups");
+    ui.checkbox(&mut cfg.groups.enabled, "Agents belong to groups");
+    ui.add_enabled_ui(cfg.groups.enabled, |ui| {
+        egui::Grid::new("groups_grid")
+            .num_columns(2)
+            .spacing([10.0, 4.0])
+            .show(ui, |ui| {
+                ui.label("Groups");
+                ui.add(egui::DragValue::new(&mut cfg.groups.groups).range(1..=16));
+                ui.end_row();
+                ui.label("Altruistic groups");
+                ui.add(
+                    egui::DragValue::new(&mut cfg.groups.altruistic_groups)
+                        .range(0..=cfg.groups.groups),
+                );
+                ui.end_row();
+                ui.label("Energy reserve");
+                ui.add(egui::DragValue::new(&mut cfg.groups.reserve).range(0..=100));
+                ui.end_row();
+                ui.label("Max gift");
+                ui.add(egui::DragValue::new(&mut cfg.groups.max_gift).range(0..=20));
+                ui.end_row();
+            });
+        ui.checkbox(
+            &mut cfg.groups.share_adjacent,
+            "Share with neighboring cells",
+        );
+    });
+    ui.separator();
 }
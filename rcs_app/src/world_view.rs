@@ -6,20 +6,40 @@ use rcs_core::{World, WorldConfig};
 /// Each cell is rendered as a colored rectangle, where the color encodes
 /// the current resource amount relative to [`WorldConfig::max_resource`].
 /// Living agents are rendered as circles centered in their current cell,
-/// with a color that fades as their health decreases.
+/// with a color that fades as their health decreases; infected agents get
+/// a green ring. When `show_pheromones` is set, the pheromone field is
+/// drawn on top of the cells in magenta, scaled to the current maximum.
+/// When `show_ownership` is set, owned cells are tinted with a color
+/// derived from the owner's id. The selected agent is outlined, together
+/// with the cells it remembers.
 ///
 /// ### Parameters
 /// - `ui`: Target [`egui::Ui`] to draw into.
 /// - `world`: The current world state (cells + agents).
 /// - `cfg`: The configuration used to interpret resource / HP ranges for colors.
 /// - `cell_px`: Size of each grid cell in screen pixels.
-pub fn draw_world(ui: &mut egui::Ui, world: &World, cfg: &WorldConfig, cell_px: f32) {
+/// - `selected`: Id of the agent to highlight, if any.
+/// - `show_pheromones`: Whether to draw the pheromone overlay.
+/// - `show_ownership`: Whether to draw the territory overlay.
+///
+/// ### Returns
+/// - `Some(cell_id)` if the user clicked on a cell this frame.
+/// - `None` otherwise.
+pub fn draw_world(
+    ui: &mut egui::Ui,
+    world: &World,
+    cfg: &WorldConfig,
+    cell_px: f32,
+    selected: Option<usize>,
+    show_pheromones: bool,
+    show_ownership: bool,
+) -> Option<usize> {
     let (width, height) = world.size();
     let world_width_px = width as f32 * cell_px;
     let world_height_px = height as f32 * cell_px;
-    let (rect, _response) = ui.allocate_exact_size(
+    let (rect, response) = ui.allocate_exact_size(
         egui::vec2(world_width_px, world_height_px),
-        egui::Sense::hover(),
+        egui::Sense::click(),
     );
     let painter = ui.painter_at(rect);
 
@@ -53,6 +73,53 @@ pub fn draw_world(ui: &mut egui::Ui, world: &World, cfg: &WorldConfig, cell_px:
         }
     }
 
+    let pheromones = world.pheromones();
+    let max_pheromone = pheromones.max();
+    if show_pheromones && max_pheromone > 0.0 {
+        for (cid, &level) in pheromones.values().iter().enumerate() {
+            let t = (level / max_pheromone).clamp(0.0, 1.0);
+            if t <= 0.0 {
+                continue;
+            }
+            let (x, y) = (cid % width, cid / width);
+            let min = rect.min + egui::vec2(x as f32 * cell_px, y as f32 * cell_px);
+            painter.rect_filled(
+                egui::Rect::from_min_size(min, egui::vec2(cell_px, cell_px)),
+                0.0,
+                egui::Color32::from_rgba_unmultiplied(220, 40, 220, (t * 180.0) as u8),
+            );
+        }
+    }
+
+    if show_ownership {
+        for (cid, owner) in world.territories().owners().enumerate() {
+            let Some(owner) = owner else {
+                continue;
+            };
+            let (x, y) = (cid % width, cid / width);
+            let min = rect.min + egui::vec2(x as f32 * cell_px, y as f32 * cell_px);
+            painter.rect_filled(
+                egui::Rect::from_min_size(min, egui::vec2(cell_px, cell_px)),
+                0.0,
+                owner_color(owner),
+            );
+        }
+    }
+
+    let selected = selected.and_then(|id| world.agent(id));
+    if let Some(memory) = selected.and_then(|a| a.memory()) {
+        for entry in memory.entries() {
+            let (x, y) = (entry.cid % width, entry.cid / width);
+            let min = rect.min + egui::vec2(x as f32 * cell_px, y as f32 * cell_px);
+            painter.rect_stroke(
+                egui::Rect::from_min_size(min, egui::vec2(cell_px, cell_px)),
+                0.0,
+                egui::Stroke::new(2.0, egui::Color32::LIGHT_BLUE),
+                egui::StrokeKind::Inside,
+            );
+        }
+    }
+
     for agent in world.agents() {
         if !agent.is_alive() {
             continue;
@@ -68,5 +135,44 @@ pub fn draw_world(ui: &mut egui::Ui, world: &World, cfg: &WorldConfig, cell_px:
         let t = (hp / max_hp_f).clamp(0.0, 1.0);
         let color = egui::Color32::from_rgb(255, (255.0 * t) as u8, (255.0 * t) as u8);
         painter.circle_filled(center, cell_px * 0.35, color);
+        if agent.health_state().is_infected() {
+            painter.circle_stroke(
+                center,
+                cell_px * 0.35,
+                egui::Stroke::new(2.0, egui::Color32::GREEN),
+            );
+        }
+        if selected.is_some_and(|s| s.id() == agent.id()) {
+            painter.circle_stroke(
+                center,
+                cell_px * 0.45,
+                egui::Stroke::new(2.0, egui::Color32::YELLOW),
+            );
+        }
     }
+
+    let pos = response
+        .interact_pointer_pos()
+        .filter(|_| response.clicked())?;
+    let offset = pos - rect.min;
+    let x = ((offset.x / cell_px) as usize).min(width - 1);
+    let y = ((offset.y / cell_px) as usize).min(height - 1);
+    Some(y * width + x)
+}
+
+/// Returns a translucent color identifying a cell owner.
+///
+/// The id is hashed as an integer (Fibonacci hashing), so agents with
+/// neighboring slots get clearly different colors and the generation bits
+/// of reused ids still change the hue.
+///
+/// ### Parameters
+/// - `owner`: Id of the owning agent.
+///
+/// ### Returns
+/// A semi-transparent [`egui::Color32`].
+fn owner_color(owner: usize) -> egui::Color32 {
+    let hash = (owner as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    let hue = (hash >> 40) as f32 / (1u64 << 24) as f32;
+    egui::ecolor::Hsva::new(hue, 0.8, 0.9, 0.45).into()
 }
@@ -0,0 +1,117 @@
+//! Benchmarks for the core stepping loop.
+//!
+//! Run with `cargo bench -p rcs_core` (add `--features parallel` to measure
+//! the rayon path). Worlds are built outside the timed section, so every
+//! measurement covers a single phase or tick of a freshly generated world.
+
+use criterion::{BatchSize, BenchmarkId, Criterion, criterion_group, criterion_main};
+use rcs_core::{SoaWorld, Updatable, UpdateOrder, World, WorldConfig};
+use std::hint::black_box;
+
+/// Returns a seeded configuration with a square grid and a fixed population.
+///
+/// ### Parameters
+/// - `side`: Width and height of the grid.
+/// - `agents`: Number of agents to spawn.
+///
+/// ### Returns
+/// A [`WorldConfig`] with default ranges otherwise.
+fn config(side: usize, agents: usize) -> WorldConfig {
+    WorldConfig {
+        width: side,
+        height: side,
+        min_agents: agents,
+        max_agents: agents,
+        seed: Some(42),
+        ..WorldConfig::default()
+    }
+}
+
+fn cell_regeneration(c: &mut Criterion) {
+    let mut group = c.benchmark_group("cell_regeneration");
+    for side in [50, 200, 500] {
+        let mut world = World::from_config(config(side, 1));
+        group.bench_function(BenchmarkId::from_parameter(side * side), |b| {
+            b.iter(|| black_box(world.regenerate_resources()).ok())
+        });
+    }
+    group.finish();
+}
+
+fn allocation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("allocation");
+    // 10 000 cells with on average 0.1, 1 and 10 agents per cell.
+    for agents in [1_000, 10_000, 100_000] {
+        group.bench_function(BenchmarkId::new("agents_on_100x100", agents), |b| {
+            b.iter_batched(
+                || World::from_config(config(100, agents)),
+                |mut world| black_box(world.allocate_resources()).ok(),
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn agent_stepping(c: &mut Criterion) {
+    let mut group = c.benchmark_group("agent_stepping");
+    for update_order in [UpdateOrder::Sequential, UpdateOrder::Synchronous] {
+        for agents in [2_000, 20_000] {
+            let cfg = WorldConfig {
+                update_order,
+                ..config(200, agents)
+            };
+            group.bench_function(BenchmarkId::new(update_order.label(), agents), |b| {
+                b.iter_batched(
+                    || {
+                        let mut world = World::from_config(cfg);
+                        world.allocate_resources().ok();
+                        world
+                    },
+                    |mut world| black_box(world.step_all_agents()).ok(),
+                    BatchSize::LargeInput,
+                )
+            });
+        }
+    }
+    group.finish();
+}
+
+fn full_tick(c: &mut Criterion) {
+    let mut group = c.benchmark_group("full_tick");
+    for (side, agents) in [(50, 500), (200, 2_000), (500, 20_000)] {
+        let id = format!("{side}x{side}/{agents}");
+        group.bench_function(BenchmarkId::new("world", &id), |b| {
+            b.iter_batched(
+                || World::from_config(config(side, agents)),
+                |mut world| black_box(world.update()).ok(),
+                BatchSize::LargeInput,
+            )
+        });
+        group.bench_function(BenchmarkId::new("soa", &id), |b| {
+            b.iter_batched(
+                || SoaWorld::from_config(config(side, agents)).unwrap(),
+                |mut world| black_box(world.update()).ok(),
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.sample_size(10);
+    group.bench_function(BenchmarkId::new("soa", "1000x1000/1000000"), |b| {
+        b.iter_batched(
+            || SoaWorld::from_config(config(1_000, 1_000_000)).unwrap(),
+            |mut world| black_box(world.update()).ok(),
+            BatchSize::PerIteration,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    cell_regeneration,
+    allocation,
+    agent_stepping,
+    full_tick
+);
+criterion_main!(benches);
@@ -1,4 +1,8 @@
+use crate::disease::HealthState;
 use crate::errors::SimulationError;
+use crate::evolution::Genome;
+use crate::lifecycle::DeathCause;
+use crate::memory::AgentMemory;
 use crate::traits::Updatable;
 
 /// An agent that moves between cells and consumes resources to stay alive.
@@ -7,16 +11,39 @@ use crate::traits::Updatable;
 /// - a unique `id`
 /// - the id of the cell it currently occupies (`cid`)
 /// - its per-step consumption rate
+/// - an extra per-step upkeep (e.g. the cost of a wider perception)
+/// - an appetite for resource beyond its need, stored as energy
 /// - the amount of resource currently allocated to it
-/// - its remaining health points
-/// - whether it is still alive
+/// - its remaining health points and stored energy
+/// - whether it is still alive (and if not, why it died), its age, its
+///   optional maximum lifespan and its number of offspring
+/// - its epidemic state and its aggression in contested cells
+/// - an optional group and the energy it has given to group members
+/// - an optional bounded memory of recently visited cells
+/// - an optional neural-network genome controlling its moves
 pub struct Agent {
     id: usize,
     cid: usize,
     consumption_rate: u32,
+    upkeep: u32,
+    appetite: u32,
     allocated_resource: u32,
     health_point: u32,
+    energy: u32,
     alive: bool,
+    death_cause: Option<DeathCause>,
+    death_tick: Option<u64>,
+    age: u64,
+    lifespan: Option<u64>,
+    senescence: u32,
+    offspring: u32,
+    health_state: HealthState,
+    infection_need: u32,
+    aggression: f32,
+    group: Option<usize>,
+    donated: u64,
+    memory: Option<AgentMemory>,
+    genome: Option<Genome>,
 }
 
 impl Agent {
@@ -30,6 +57,11 @@ impl Agent {
     /// - `health_point`: Initial health points of the agent.
     /// - `alive`: Initial alive status.
     ///
+    /// The agent starts without extra upkeep, appetite, energy, lifespan,
+    /// memory or genome; see [`Agent::set_upkeep`], [`Agent::set_appetite`],
+    /// [`Agent::set_lifespan`], [`Agent::enable_memory`] and
+    /// [`Agent::set_genome`].
+    ///
     /// ### Returns
     /// A new [`Agent`] instance.
     pub fn new(
@@ -44,39 +76,74 @@ impl Agent {
             id,
             cid,
             consumption_rate,
+            upkeep: 0,
+            appetite: 0,
             allocated_resource,
             health_point,
+            energy: 0,
             alive,
+            death_cause: None,
+            death_tick: None,
+            age: 0,
+            lifespan: None,
+            senescence: 0,
+            offspring: 0,
+            health_state: HealthState::Susceptible,
+            infection_need: 0,
+            aggression: 0.0,
+            group: None,
+            donated: 0,
+            memory: None,
+            genome: None,
         }
     }
 
     /// Performs one step of metabolism for the agent.
     ///
-    /// If `allocated_resource` is smaller than `consumption_rate`, the agent
-    /// loses one health point. In all cases, `allocated_resource` is reset
-    /// to zero. When `health_point` reaches zero, `alive` is set to `false`.
+    /// If `allocated_resource` is smaller than [`Agent::need`], the agent
+    /// loses one health point; anything above the need is stored as energy.
+    /// In all cases, `allocated_resource` is reset to zero and the agent
+    /// ages by one step. When `health_point` reaches zero, the agent dies
+    /// of starvation; when it reaches its lifespan, it dies of old age.
     ///
     /// This method is internal; external callers should use [`Agent::update`].
     fn metabolize(&mut self) {
-        if self.allocated_resource < self.consumption_rate {
+        let need = self.need();
+        if self.allocated_resource < need {
             self.health_point = self.health_point.saturating_sub(1);
+        } else {
+            self.energy = self.energy.saturating_add(self.allocated_resource - need);
         }
         self.allocated_resource = 0;
+        self.age += 1;
         if self.health_point == 0 {
-            self.alive = false;
+            self.die(DeathCause::Starvation);
+        } else if self.lifespan.is_some_and(|lifespan| self.age >= lifespan) {
+            self.die(DeathCause::OldAge);
         }
     }
 
+    /// Marks the agent as dead.
+    ///
+    /// Only the first cause is recorded.
+    ///
+    /// ### Parameters
+    /// - `cause`: Why the agent died.
+    fn die(&mut self, cause: DeathCause) {
+        self.alive = false;
+        self.death_cause.get_or_insert(cause);
+    }
+
     /// Applies the movement cost to the agent.
     ///
-    /// Movement always costs one health point (saturating at zero). If the
-    /// health reaches zero, the agent is marked as dead.
+    /// Movement costs `cost` health points (saturating at zero). If the
+    /// health reaches zero, the agent dies of exhaustion.
     ///
     /// This method is internal; external callers should use [`Agent::move_to`].
-    fn movement_cost(&mut self) {
-        self.health_point = self.health_point.saturating_sub(1);
+    fn movement_cost(&mut self, cost: u32) {
+        self.health_point = self.health_point.saturating_sub(cost);
         if self.health_point == 0 {
-            self.alive = false;
+            self.die(DeathCause::Exhaustion);
         }
     }
 
@@ -92,19 +159,36 @@ impl Agent {
     /// - `Ok(())` if the agent is alive and the move succeeds.
     /// - `Err(SimulationError::NotAlive)` if the agent is dead.
     pub fn move_to(&mut self, new_id: usize) -> Result<(), SimulationError> {
+        self.move_to_with_cost(new_id, 1)
+    }
+
+    /// Moves the agent to a new cell, paying a given movement cost.
+    ///
+    /// This is used for cells whose terrain is harder to enter than the
+    /// default cost of one health point (see [`crate::Cell::terrain_cost`]).
+    ///
+    /// ### Parameters
+    /// - `new_id`: The id of the cell to move to.
+    /// - `cost`: Health points lost by the move.
+    ///
+    /// ### Returns
+    /// - `Ok(())` if the agent is alive and the move succeeds.
+    /// - `Err(SimulationError::NotAlive)` if the agent is dead.
+    pub fn move_to_with_cost(&mut self, new_id: usize, cost: u32) -> Result<(), SimulationError> {
         if !self.alive {
             return Err(SimulationError::NotAlive);
         }
         self.cid = new_id;
-        self.movement_cost();
+        self.movement_cost(cost);
         Ok(())
     }
 
     /// Retrieves resource for the agent from a cell's available amount.
     ///
-    /// The agent takes up to its `consumption_rate` from the given `resource`
-    /// pool. The amount actually taken is stored in `allocated_resource`, and
-    /// the remaining resource (if any) is returned.
+    /// The agent takes up to its [`Agent::need`] plus its appetite from
+    /// the given `resource` pool. The amount actually taken is stored in
+    /// `allocated_resource`, and the remaining resource (if any) is
+    /// returned.
     ///
     /// ### Parameters
     /// - `resource`: Total amount of resource offered to the agent.
@@ -112,7 +196,7 @@ impl Agent {
     /// ### Returns
     /// The leftover resource that was not taken by the agent.
     pub fn retrieve_resource(&mut self, resource: u32) -> u32 {
-        let take = resource.min(self.consumption_rate);
+        let take = resource.min(self.need().saturating_add(self.appetite));
         self.allocated_resource = take;
         resource - take
     }
@@ -152,6 +236,14 @@ impl Agent {
         self.id
     }
 
+    /// Gives the agent back the id the world knows it by.
+    ///
+    /// ### Parameters
+    /// - `id`: The agent's stable id.
+    pub(crate) fn set_id(&mut self, id: usize) {
+        self.id = id;
+    }
+
     /// Returns the id of the cell the agent currently occupies.
     ///
     /// ### Returns
@@ -171,12 +263,387 @@ impl Agent {
     /// Returns whether the agent is hungry in this step.
     ///
     /// An agent is considered hungry if its `allocated_resource` is
-    /// strictly less than its `consumption_rate`.
+    /// strictly less than its [`Agent::need`].
     ///
     /// ### Returns
     /// `true` if the agent is hungry, `false` otherwise.
     pub fn is_hungry(&self) -> bool {
-        self.allocated_resource < self.consumption_rate
+        self.allocated_resource < self.need()
+    }
+
+    /// Returns the base consumption rate of the agent.
+    ///
+    /// ### Returns
+    /// The resource needed per step, without upkeep.
+    pub fn consumption_rate(&self) -> u32 {
+        self.consumption_rate
+    }
+
+    /// Returns the total resource the agent needs per step.
+    ///
+    /// ### Returns
+    /// `consumption_rate + upkeep`, plus the senescence surcharge for each
+    /// full quarter of its lifespan the agent has lived, plus the extra
+    /// need of an ongoing infection.
+    pub fn need(&self) -> u32 {
+        let quarters = match self.lifespan {
+            Some(lifespan) if lifespan > 0 => (self.age.saturating_mul(4) / lifespan).min(3),
+            _ => 0,
+        };
+        self.consumption_rate
+            .saturating_add(self.upkeep)
+            .saturating_add(self.senescence.saturating_mul(quarters as u32))
+            .saturating_add(self.infection_need)
+    }
+
+    /// Returns the extra resource the agent needs per step, e.g. for perception.
+    ///
+    /// ### Returns
+    /// The upkeep set by [`Agent::set_upkeep`].
+    pub fn upkeep(&self) -> u32 {
+        self.upkeep
+    }
+
+    /// Sets the extra resource the agent needs per step on top of its
+    /// consumption rate.
+    ///
+    /// ### Parameters
+    /// - `upkeep`: Additional resource needed per step.
+    pub fn set_upkeep(&mut self, upkeep: u32) {
+        self.upkeep = upkeep;
+    }
+
+    /// Returns how much resource the agent takes beyond its need.
+    ///
+    /// ### Returns
+    /// The extra amount stored as energy when available.
+    pub fn appetite(&self) -> u32 {
+        self.appetite
+    }
+
+    /// Sets how much resource the agent takes beyond its need.
+    ///
+    /// ### Parameters
+    /// - `appetite`: Extra resource per step that is stored as energy.
+    pub fn set_appetite(&mut self, appetite: u32) {
+        self.appetite = appetite;
+    }
+
+    /// Returns the energy stored from surplus food.
+    ///
+    /// ### Returns
+    /// The current energy reserve.
+    pub fn energy(&self) -> u32 {
+        self.energy
+    }
+
+    /// Pays the energy cost of a birth.
+    ///
+    /// ### Parameters
+    /// - `cost`: Energy a child costs.
+    ///
+    /// ### Returns
+    /// - `true` if the agent is alive and had enough energy; the cost is
+    ///   deducted and the child is counted.
+    /// - `false` otherwise; nothing changes.
+    pub fn pay_birth(&mut self, cost: u32) -> bool {
+        if !self.alive || self.energy < cost {
+            return false;
+        }
+        self.energy -= cost;
+        self.offspring += 1;
+        true
+    }
+
+    /// Gives stored energy away, keeping a reserve.
+    ///
+    /// ### Parameters
+    /// - `amount`: Most energy to give.
+    /// - `reserve`: Energy the agent keeps for itself.
+    ///
+    /// ### Returns
+    /// The energy actually given; `0` for dead agents.
+    pub fn give_energy(&mut self, amount: u32, reserve: u32) -> u32 {
+        if !self.alive {
+            return 0;
+        }
+        let gift = amount.min(self.energy.saturating_sub(reserve));
+        self.energy -= gift;
+        self.donated += u64::from(gift);
+        gift
+    }
+
+    /// Adds resource received from another agent to this step's allocation.
+    ///
+    /// ### Parameters
+    /// - `amount`: Resource received.
+    pub fn receive(&mut self, amount: u32) {
+        self.allocated_resource = self.allocated_resource.saturating_add(amount);
+    }
+
+    /// Returns the total energy this agent has given away.
+    ///
+    /// ### Returns
+    /// The sum of all gifts made with [`Agent::give_energy`].
+    pub fn donated(&self) -> u64 {
+        self.donated
+    }
+
+    /// Returns the group the agent belongs to.
+    ///
+    /// ### Returns
+    /// - `Some(group)` if the agent is a group member.
+    /// - `None` otherwise.
+    pub fn group(&self) -> Option<usize> {
+        self.group
+    }
+
+    /// Sets the group the agent belongs to.
+    ///
+    /// ### Parameters
+    /// - `group`: Index of the group, or `None` to leave all groups.
+    pub fn set_group(&mut self, group: Option<usize>) {
+        self.group = group;
+    }
+
+    /// Returns the number of steps the agent has lived through.
+    ///
+    /// ### Returns
+    /// The number of completed metabolism steps.
+    pub fn age(&self) -> u64 {
+        self.age
+    }
+
+    /// Returns the age at which the agent dies of old age.
+    ///
+    /// ### Returns
+    /// - `Some(lifespan)` if the agent ages.
+    /// - `None` if it never dies of old age.
+    pub fn lifespan(&self) -> Option<u64> {
+        self.lifespan
+    }
+
+    /// Sets the age at which the agent dies of old age.
+    ///
+    /// ### Parameters
+    /// - `lifespan`: Maximum age, or `None` for no limit.
+    pub fn set_lifespan(&mut self, lifespan: Option<u64>) {
+        self.lifespan = lifespan;
+    }
+
+    /// Returns the extra need per quarter of lifespan lived.
+    ///
+    /// ### Returns
+    /// The senescence surcharge; see [`Agent::need`].
+    pub fn senescence(&self) -> u32 {
+        self.senescence
+    }
+
+    /// Sets the extra need per quarter of lifespan lived.
+    ///
+    /// Has no effect on agents without a lifespan.
+    ///
+    /// ### Parameters
+    /// - `senescence`: Additional resource per step per quarter.
+    pub fn set_senescence(&mut self, senescence: u32) {
+        self.senescence = senescence;
+    }
+
+    /// Returns why the agent died.
+    ///
+    /// ### Returns
+    /// - `Some(DeathCause)` once the agent has died during the simulation.
+    /// - `None` while it is alive, or if it was created dead.
+    pub fn death_cause(&self) -> Option<DeathCause> {
+        self.death_cause
+    }
+
+    /// Returns the tick on which the agent died.
+    ///
+    /// ### Returns
+    /// - `Some(tick)` once the world has handled the agent's death.
+    /// - `None` while it is alive, or if it was created dead.
+    pub fn death_tick(&self) -> Option<u64> {
+        self.death_tick
+    }
+
+    /// Records the tick on which the agent died.
+    ///
+    /// ### Parameters
+    /// - `tick`: The world tick during which the agent died.
+    pub fn set_death_tick(&mut self, tick: u64) {
+        self.death_tick = Some(tick);
+    }
+
+    /// Returns the agent's epidemic state.
+    ///
+    /// ### Returns
+    /// The current [`HealthState`].
+    pub fn health_state(&self) -> HealthState {
+        self.health_state
+    }
+
+    /// Returns how likely the agent is to fight over a contested cell.
+    ///
+    /// ### Returns
+    /// A probability in `[0, 1]`; see [`crate::CombatConfig`].
+    pub fn aggression(&self) -> f32 {
+        self.aggression
+    }
+
+    /// Sets how likely the agent is to fight over a contested cell.
+    ///
+    /// ### Parameters
+    /// - `aggression`: Probability of fighting, clamped to `[0, 1]`.
+    pub fn set_aggression(&mut self, aggression: f32) {
+        self.aggression = aggression.clamp(0.0, 1.0);
+    }
+
+    /// Applies damage taken in a fight.
+    ///
+    /// Health is reduced by `damage` (saturating at zero); at zero the
+    /// agent dies in combat. Dead agents are unaffected.
+    ///
+    /// ### Parameters
+    /// - `damage`: Health points lost.
+    pub fn wound(&mut self, damage: u32) {
+        if !self.alive {
+            return;
+        }
+        self.health_point = self.health_point.saturating_sub(damage);
+        if self.health_point == 0 {
+            self.die(DeathCause::Combat);
+        }
+    }
+
+    /// Infects a susceptible, living agent.
+    ///
+    /// ### Parameters
+    /// - `duration`: Ticks until the agent recovers.
+    /// - `extra_need`: Additional resource needed per step while infected.
+    ///
+    /// ### Returns
+    /// - `true` if the agent was susceptible and is now infected.
+    /// - `false` otherwise; nothing changes.
+    pub fn infect(&mut self, duration: u32, extra_need: u32) -> bool {
+        if !self.alive || self.health_state != HealthState::Susceptible || duration == 0 {
+            return false;
+        }
+        self.health_state = HealthState::Infected {
+            remaining: duration,
+        };
+        self.infection_need = extra_need;
+        true
+    }
+
+    /// Advances an ongoing infection by one tick.
+    ///
+    /// An infected agent loses `hp_drain` health points (dying of disease
+    /// at zero) and gets one tick closer to recovery. On recovery the
+    /// extra need goes away and the agent becomes recovered or, without
+    /// lasting immunity, susceptible again. Other agents are unaffected.
+    ///
+    /// ### Parameters
+    /// - `hp_drain`: Health lost per tick of infection.
+    /// - `lasting_immunity`: Whether recovered agents stay immune.
+    pub fn progress_disease(&mut self, hp_drain: u32, lasting_immunity: bool) {
+        let HealthState::Infected { remaining } = self.health_state else {
+            return;
+        };
+        if !self.alive {
+            return;
+        }
+        self.health_point = self.health_point.saturating_sub(hp_drain);
+        if self.health_point == 0 {
+            self.die(DeathCause::Disease);
+        }
+        self.health_state = if remaining > 1 {
+            HealthState::Infected {
+                remaining: remaining - 1,
+            }
+        } else {
+            self.infection_need = 0;
+            if lasting_immunity {
+                HealthState::Recovered
+            } else {
+                HealthState::Susceptible
+            }
+        };
+    }
+
+    /// Returns the number of children the agent has had.
+    ///
+    /// ### Returns
+    /// The number of successful [`Agent::pay_birth`] calls.
+    pub fn offspring(&self) -> u32 {
+        self.offspring
+    }
+
+    /// Returns the genome controlling the agent's moves.
+    ///
+    /// ### Returns
+    /// - `Some(&Genome)` for evolving agents.
+    /// - `None` otherwise.
+    pub fn genome(&self) -> Option<&Genome> {
+        self.genome.as_ref()
+    }
+
+    /// Consumes the agent and returns its genome without copying it.
+    ///
+    /// ### Returns
+    /// The genome, as [`Agent::genome`] would have returned it.
+    pub(crate) fn into_genome(self) -> Option<Genome> {
+        self.genome
+    }
+
+    /// Replaces the agent's genome.
+    ///
+    /// ### Parameters
+    /// - `genome`: The new genome, or `None` to remove it.
+    pub fn set_genome(&mut self, genome: Option<Genome>) {
+        self.genome = genome;
+    }
+
+    /// Gives the agent a bounded memory of visited cells.
+    ///
+    /// Any previous memory is discarded.
+    ///
+    /// ### Parameters
+    /// - `capacity`: Maximum number of cells remembered; `0` disables memory.
+    pub fn enable_memory(&mut self, capacity: usize) {
+        self.memory = (capacity > 0).then(|| AgentMemory::new(capacity));
+    }
+
+    /// Returns the agent's memory of visited cells.
+    ///
+    /// ### Returns
+    /// - `Some(&AgentMemory)` if memory is enabled.
+    /// - `None` otherwise.
+    pub fn memory(&self) -> Option<&AgentMemory> {
+        self.memory.as_ref()
+    }
+
+    /// Records an observation of a cell in the agent's memory.
+    ///
+    /// Does nothing if memory is disabled.
+    ///
+    /// ### Parameters
+    /// - `cid`: Id of the observed cell.
+    /// - `resource`: Resource seen on the cell.
+    /// - `tick`: Tick of the observation.
+    pub fn remember(&mut self, cid: usize, resource: u32, tick: u64) {
+        if let Some(memory) = &mut self.memory {
+            memory.record(cid, resource, tick);
+        }
+    }
+
+    /// Returns the resource allocated to the agent for the current step.
+    ///
+    /// ### Returns
+    /// The amount taken by the last [`Agent::retrieve_resource`], or `0`
+    /// after metabolism.
+    pub fn allocated_resource(&self) -> u32 {
+        self.allocated_resource
     }
 
     /// Returns the current health points of the agent.
@@ -238,6 +705,101 @@ mod tests {
         assert!(a.is_hungry());
     }
 
+    #[test]
+    fn upkeep_raises_the_need() {
+        let mut a = Agent::new(0, 0, 3, 0, 5, true);
+        a.set_upkeep(2);
+        assert_eq!(a.consumption_rate(), 3);
+        assert_eq!(a.need(), 5);
+
+        let leftover = a.retrieve_resource(4);
+        assert_eq!(leftover, 0);
+        assert!(a.is_hungry());
+        a.update().unwrap();
+        assert_eq!(a.health_point(), 4);
+    }
+
+    #[test]
+    fn appetite_turns_surplus_into_energy() {
+        let mut a = Agent::new(0, 0, 2, 0, 5, true);
+        a.set_appetite(3);
+        assert_eq!(a.retrieve_resource(10), 5);
+        a.update().unwrap();
+        assert_eq!(a.energy(), 3);
+        assert_eq!(a.age(), 1);
+
+        assert!(!a.pay_birth(4));
+        assert!(a.pay_birth(3));
+        assert_eq!((a.energy(), a.offspring()), (0, 1));
+    }
+
+    #[test]
+    fn agents_die_of_old_age_and_need_more_when_old() {
+        let mut a = Agent::new(0, 0, 2, 0, 50, true);
+        a.set_lifespan(Some(4));
+        a.set_senescence(3);
+        assert_eq!(a.need(), 2);
+
+        for expected_need in [5, 8, 11] {
+            a.retrieve_resource(100);
+            a.update().unwrap();
+            assert_eq!(a.need(), expected_need);
+        }
+        a.retrieve_resource(100);
+        a.update().unwrap();
+        assert!(!a.is_alive());
+        assert_eq!(a.death_cause(), Some(DeathCause::OldAge));
+    }
+
+    #[test]
+    fn death_cause_distinguishes_starvation_and_exhaustion() {
+        let mut starving = Agent::new(0, 0, 2, 0, 1, true);
+        starving.update().unwrap();
+        assert_eq!(starving.death_cause(), Some(DeathCause::Starvation));
+
+        let mut tired = Agent::new(1, 0, 2, 0, 1, true);
+        tired.move_to(1).unwrap();
+        assert_eq!(tired.death_cause(), Some(DeathCause::Exhaustion));
+    }
+
+    #[test]
+    fn infection_raises_need_until_recovery() {
+        let mut a = Agent::new(0, 0, 2, 0, 5, true);
+        assert!(a.infect(2, 3));
+        assert!(!a.infect(2, 3));
+        assert_eq!(a.need(), 5);
+
+        a.progress_disease(1, true);
+        assert_eq!(a.health_state(), HealthState::Infected { remaining: 1 });
+        a.progress_disease(1, true);
+        assert_eq!(a.health_state(), HealthState::Recovered);
+        assert_eq!((a.need(), a.health_point()), (2, 3));
+        assert!(!a.infect(2, 3));
+    }
+
+    #[test]
+    fn disease_can_kill() {
+        let mut a = Agent::new(0, 0, 2, 0, 2, true);
+        a.infect(5, 0);
+        a.progress_disease(2, false);
+        assert!(!a.is_alive());
+        assert_eq!(a.death_cause(), Some(DeathCause::Disease));
+    }
+
+    #[test]
+    fn memory_is_optional() {
+        let mut a = Agent::new(0, 0, 3, 0, 5, true);
+        a.remember(1, 4, 0);
+        assert!(a.memory().is_none());
+
+        a.enable_memory(2);
+        a.remember(1, 4, 0);
+        assert_eq!(a.memory().unwrap().recall(1).unwrap().resource, 4);
+
+        a.enable_memory(0);
+        assert!(a.memory().is_none());
+    }
+
     #[test]
     fn metabolize_does_not_reduce_health_when_fed() {
         let mut a = Agent::new(0, 0, 3, 3, 5, true);
@@ -280,6 +842,18 @@ mod tests {
         assert!(a.is_alive());
     }
 
+    #[test]
+    fn move_to_with_cost_applies_terrain_cost() {
+        let mut a = Agent::new(0, 1, 3, 0, 5, true);
+        a.move_to_with_cost(2, 3).unwrap();
+        assert_eq!(a.cid(), 2);
+        assert_eq!(a.health_point(), 2);
+
+        a.move_to_with_cost(3, 4).unwrap();
+        assert_eq!(a.health_point(), 0);
+        assert!(!a.is_alive());
+    }
+
     #[test]
     fn move_to_fails_if_agent_is_dead() {
         let mut a = Agent::new(0, 1, 3, 0, 0, false);
@@ -306,4 +880,32 @@ mod tests {
         let empty: Vec<(usize, u32)> = Vec::new();
         assert_eq!(a.decide_move(&empty), None);
     }
+
+    #[test]
+    fn wounds_can_kill_in_combat() {
+        let mut a = Agent::new(0, 0, 1, 0, 3, true);
+        a.set_aggression(1.5);
+        assert_eq!(a.aggression(), 1.0);
+        a.wound(2);
+        assert_eq!(a.health_point(), 1);
+        assert!(a.is_alive());
+        a.wound(2);
+        assert!(!a.is_alive());
+        assert_eq!(a.death_cause(), Some(DeathCause::Combat));
+    }
+
+    #[test]
+    fn gifts_keep_a_reserve() {
+        let mut donor = Agent::new(0, 0, 1, 3, 5, true);
+        donor.update().unwrap();
+        assert_eq!(donor.energy(), 2);
+        assert_eq!(donor.give_energy(5, 1), 1);
+        assert_eq!(donor.give_energy(5, 1), 0);
+        assert_eq!(donor.donated(), 1);
+
+        let mut recipient = Agent::new(1, 0, 2, 1, 5, true);
+        recipient.receive(1);
+        recipient.update().unwrap();
+        assert_eq!(recipient.health_point(), 5);
+    }
 }
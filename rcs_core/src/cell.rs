@@ -9,12 +9,14 @@ use crate::traits::Updatable;
 /// - the maximum amount of resource it can hold
 /// - its current regeneration rate per update step
 /// - the maximum regeneration rate it can reach
+/// - the terrain cost (health points) an agent pays to enter it
 pub struct Cell {
     id: usize,
     cur_resource: u32,
     max_resource: u32,
     regen_rate: u32,
     max_regen_rate: u32,
+    terrain_cost: u32,
 }
 
 impl Cell {
@@ -27,6 +29,8 @@ impl Cell {
     /// - `regen_rate`: Initial regeneration rate per update step.
     /// - `max_regen_rate`: Upper bound on the regeneration rate.
     ///
+    /// The terrain cost starts at `1`; see [`Cell::set_terrain_cost`].
+    ///
     /// ### Returns
     /// A new [`Cell`] instance with the given configuration.
     pub fn new(
@@ -42,6 +46,7 @@ impl Cell {
             max_resource,
             regen_rate,
             max_regen_rate,
+            terrain_cost: 1,
         }
     }
 
@@ -106,6 +111,23 @@ impl Cell {
         self.regen_rate = (self.regen_rate.saturating_add(regen_rate)).min(self.max_regen_rate);
     }
 
+    /// Sets the health points an agent pays to enter this cell.
+    ///
+    /// ### Parameters
+    /// - `cost`: Movement cost; values below `1` are raised to `1`, so
+    ///   moving is never free.
+    pub fn set_terrain_cost(&mut self, cost: u32) {
+        self.terrain_cost = cost.max(1);
+    }
+
+    /// Returns the health points an agent pays to enter this cell.
+    ///
+    /// ### Returns
+    /// The terrain cost, at least `1`.
+    pub fn terrain_cost(&self) -> u32 {
+        self.terrain_cost
+    }
+
     /// Returns the id of the cell.
     ///
     /// ### Returns
@@ -121,6 +143,30 @@ impl Cell {
     pub fn cur_resource(&self) -> u32 {
         self.cur_resource
     }
+
+    /// Returns the maximum amount of resource the cell can hold.
+    ///
+    /// ### Returns
+    /// The resource capacity of the cell.
+    pub fn max_resource(&self) -> u32 {
+        self.max_resource
+    }
+
+    /// Returns the current regeneration rate of the cell.
+    ///
+    /// ### Returns
+    /// The amount of resource added per update step (before capping).
+    pub fn regen_rate(&self) -> u32 {
+        self.regen_rate
+    }
+
+    /// Returns the upper bound on the regeneration rate.
+    ///
+    /// ### Returns
+    /// The maximum regeneration rate of the cell.
+    pub fn max_regen_rate(&self) -> u32 {
+        self.max_regen_rate
+    }
 }
 
 impl Updatable for Cell {
@@ -148,6 +194,9 @@ mod tests {
         let cell = Cell::new(1, 10, 100, 2, 5);
         assert_eq!(cell.id(), 1);
         assert_eq!(cell.cur_resource(), 10);
+        assert_eq!(cell.max_resource(), 100);
+        assert_eq!(cell.regen_rate(), 2);
+        assert_eq!(cell.max_regen_rate(), 5);
     }
 
     #[test]
@@ -201,6 +250,16 @@ mod tests {
         assert_eq!(cell.cur_resource(), 5);
     }
 
+    #[test]
+    fn terrain_cost_defaults_to_one_and_is_never_free() {
+        let mut cell = Cell::new(0, 0, 100, 1, 5);
+        assert_eq!(cell.terrain_cost(), 1);
+        cell.set_terrain_cost(4);
+        assert_eq!(cell.terrain_cost(), 4);
+        cell.set_terrain_cost(0);
+        assert_eq!(cell.terrain_cost(), 1);
+    }
+
     #[test]
     fn update_regenerates_resource_but_not_over_max() {
         let mut cell = Cell::new(0, 99, 100, 5, 5);
@@ -0,0 +1,86 @@
+/// Settings of the optional contest mode.
+///
+/// When `enabled`, a cell is contested if two or more living agents stand
+/// on it and its resource does not cover their combined need. Each agent
+/// on a contested cell decides to fight with probability equal to its
+/// aggression (see [`crate::Agent::aggression`]), which is drawn uniformly
+/// from `[min_aggression, max_aggression]` and inherited by children.
+///
+/// This is a hawk–dove game:
+/// - without fighters, the resource is split as usual
+/// - a single fighter takes the whole cell unopposed
+/// - several fighters duel one after another; the current holder beats a
+///   challenger with probability `hp / (hp + challenger_hp)`, both pay
+///   `fight_cost` health points, and the last one standing takes the cell
+///
+/// Agents that do not fight on a contested cell with fighters get nothing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CombatConfig {
+    pub enabled: bool,
+    pub fight_cost: u32,
+    pub min_aggression: f32,
+    pub max_aggression: f32,
+}
+
+impl CombatConfig {
+    /// Returns whether the settings are usable.
+    ///
+    /// ### Returns
+    /// `true` if `0 <= min_aggression <= max_aggression <= 1`.
+    pub fn is_valid(&self) -> bool {
+        0.0 <= self.min_aggression
+            && self.min_aggression <= self.max_aggression
+            && self.max_aggression <= 1.0
+    }
+
+    /// Returns the probability that the current holder of a cell wins a duel.
+    ///
+    /// ### Parameters
+    /// - `holder_hp`: Health points of the current holder.
+    /// - `challenger_hp`: Health points of the challenger.
+    ///
+    /// ### Returns
+    /// `holder_hp / (holder_hp + challenger_hp)`, or `0.5` if both are zero.
+    pub fn win_chance(holder_hp: u32, challenger_hp: u32) -> f64 {
+        let total = u64::from(holder_hp) + u64::from(challenger_hp);
+        if total == 0 {
+            return 0.5;
+        }
+        holder_hp as f64 / total as f64
+    }
+}
+
+impl Default for CombatConfig {
+    /// Returns a disabled configuration with aggression spread over `[0, 1]`.
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            fight_cost: 1,
+            min_aggression: 0.0,
+            max_aggression: 1.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn win_chance_is_weighted_by_health() {
+        assert_eq!(CombatConfig::win_chance(3, 1), 0.75);
+        assert_eq!(CombatConfig::win_chance(0, 4), 0.0);
+        assert_eq!(CombatConfig::win_chance(0, 0), 0.5);
+    }
+
+    #[test]
+    fn config_validation() {
+        assert!(CombatConfig::default().is_valid());
+        let bad = CombatConfig {
+            min_aggression: 0.8,
+            max_aggression: 0.2,
+            ..CombatConfig::default()
+        };
+        assert!(!bad.is_valid());
+    }
+}
@@ -0,0 +1,128 @@
+/// Where an agent stands in the course of an epidemic.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HealthState {
+    /// Never infected, or lost its immunity.
+    #[default]
+    Susceptible,
+    /// Carries the disease for `remaining` more ticks.
+    Infected { remaining: u32 },
+    /// Recovered and immune.
+    Recovered,
+}
+
+impl HealthState {
+    /// Returns a short human-readable name for this state.
+    ///
+    /// ### Returns
+    /// `"susceptible"`, `"infected"` or `"recovered"`.
+    pub fn label(&self) -> &'static str {
+        match self {
+            HealthState::Susceptible => "susceptible",
+            HealthState::Infected { .. } => "infected",
+            HealthState::Recovered => "recovered",
+        }
+    }
+
+    /// Returns whether the agent currently carries the disease.
+    ///
+    /// ### Returns
+    /// `true` for [`HealthState::Infected`].
+    pub fn is_infected(&self) -> bool {
+        matches!(self, HealthState::Infected { .. })
+    }
+}
+
+/// Settings of the optional epidemic layer.
+///
+/// When `enabled`, `initial_infected` random agents start infected. Each
+/// tick, a susceptible agent catches the disease from every infected agent
+/// on its own cell with probability `transmission`, and from every
+/// infected agent on a four-neighbor cell with probability
+/// `adjacent_transmission`. An infection lasts `duration` ticks, during
+/// which the agent needs `extra_need` more resource and loses `hp_drain`
+/// health per tick. Afterwards the agent is immune if `lasting_immunity`
+/// is set and susceptible again otherwise.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DiseaseConfig {
+    pub enabled: bool,
+    pub initial_infected: usize,
+    pub transmission: f32,
+    pub adjacent_transmission: f32,
+    pub duration: u32,
+    pub extra_need: u32,
+    pub hp_drain: u32,
+    pub lasting_immunity: bool,
+}
+
+impl DiseaseConfig {
+    /// Returns whether the settings are usable.
+    ///
+    /// ### Returns
+    /// `true` if both transmission probabilities lie in `[0, 1]` and
+    /// infections last at least one tick.
+    pub fn is_valid(&self) -> bool {
+        (0.0..=1.0).contains(&self.transmission)
+            && (0.0..=1.0).contains(&self.adjacent_transmission)
+            && self.duration > 0
+    }
+
+    /// Returns the probability that a susceptible agent gets infected.
+    ///
+    /// ### Parameters
+    /// - `same_cell`: Infected agents sharing the agent's cell.
+    /// - `adjacent`: Infected agents on the four neighboring cells.
+    ///
+    /// ### Returns
+    /// `1 - (1 - transmission)^same_cell * (1 - adjacent_transmission)^adjacent`.
+    pub fn infection_chance(&self, same_cell: u32, adjacent: u32) -> f64 {
+        let escape_same = (1.0 - f64::from(self.transmission)).powi(same_cell as i32);
+        let escape_adjacent = (1.0 - f64::from(self.adjacent_transmission)).powi(adjacent as i32);
+        (1.0 - escape_same * escape_adjacent).clamp(0.0, 1.0)
+    }
+}
+
+impl Default for DiseaseConfig {
+    /// Returns a disabled, mildly contagious disease with lasting immunity.
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            initial_infected: 3,
+            transmission: 0.3,
+            adjacent_transmission: 0.05,
+            duration: 10,
+            extra_need: 1,
+            hp_drain: 0,
+            lasting_immunity: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn infection_chance_combines_contacts() {
+        let cfg = DiseaseConfig {
+            transmission: 0.5,
+            adjacent_transmission: 0.1,
+            ..DiseaseConfig::default()
+        };
+        assert_eq!(cfg.infection_chance(0, 0), 0.0);
+        assert!((cfg.infection_chance(1, 0) - 0.5).abs() < 1e-9);
+        assert!((cfg.infection_chance(2, 0) - 0.75).abs() < 1e-9);
+        assert!((cfg.infection_chance(1, 1) - (1.0 - 0.5 * 0.9)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn config_validation_and_labels() {
+        assert!(DiseaseConfig::default().is_valid());
+        let bad = DiseaseConfig {
+            duration: 0,
+            ..DiseaseConfig::default()
+        };
+        assert!(!bad.is_valid());
+        assert_eq!(HealthState::Infected { remaining: 2 }.label(), "infected");
+        assert!(!HealthState::Recovered.is_infected());
+    }
+}
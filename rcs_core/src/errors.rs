@@ -1,9 +1,194 @@
+use std::fmt;
+
+/// A simulation entity that an error refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Entity {
+    /// The world as a whole (e.g. global resource conservation).
+    World,
+    /// A cell, identified by its id.
+    Cell(usize),
+    /// An agent, identified by its id.
+    Agent(usize),
+}
+
+impl fmt::Display for Entity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Entity::World => write!(f, "world"),
+            Entity::Cell(cid) => write!(f, "cell {cid}"),
+            Entity::Agent(id) => write!(f, "agent {id}"),
+        }
+    }
+}
+
 /// Errors that can occur during the simulation.
 ///
-/// This enum is used by components such as `Agent` and `Cell`
+/// This enum is used by components such as `Agent`, `Cell` and `World`
 /// to report exceptional situations, instead of panicking.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SimulationError {
+    /// The operation requires a living agent.
     NotAlive,
+    /// A cell did not hold enough resource for an exact consumption.
     NotEnoughResources { available: u32 },
+    /// A cell id does not refer to a cell of the world.
+    InvalidCellId { cid: usize, num_cells: usize },
+    /// An agent tried to move to a cell outside the grid or not adjacent
+    /// to its current cell.
+    OutOfBoundsMove {
+        agent: usize,
+        from: usize,
+        to: usize,
+    },
+    /// An internal consistency rule of the simulation was broken.
+    InvariantViolation {
+        tick: u64,
+        entity: Entity,
+        reason: String,
+    },
+    /// A world configuration is inconsistent.
+    InvalidConfig(String),
+    /// A serialised genome could not be parsed.
+    InvalidGenome(String),
+    /// Several errors were collected during a single world update.
+    Multiple(Vec<SimulationError>),
+}
+
+impl SimulationError {
+    /// Combines a list of collected errors into a single result.
+    ///
+    /// Nested [`SimulationError::Multiple`] values are flattened, so the
+    /// returned list never contains another `Multiple`.
+    ///
+    /// ### Parameters
+    /// - `errors`: Errors gathered while running one operation.
+    ///
+    /// ### Returns
+    /// - `Ok(())` if `errors` is empty.
+    /// - `Err(error)` with the only error if there is exactly one.
+    /// - `Err(SimulationError::Multiple(..))` otherwise.
+    pub fn collect(errors: Vec<SimulationError>) -> Result<(), SimulationError> {
+        let mut flat = Vec::with_capacity(errors.len());
+        let mut pending = errors;
+        pending.reverse();
+        while let Some(err) = pending.pop() {
+            match err {
+                SimulationError::Multiple(inner) => pending.extend(inner.into_iter().rev()),
+                other => flat.push(other),
+            }
+        }
+        match flat.len() {
+            0 => Ok(()),
+            1 => Err(flat.remove(0)),
+            _ => Err(SimulationError::Multiple(flat)),
+        }
+    }
+}
+
+impl fmt::Display for SimulationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SimulationError::NotAlive => write!(f, "agent is not alive"),
+            SimulationError::NotEnoughResources { available } => {
+                write!(f, "not enough resources (available: {available})")
+            }
+            SimulationError::InvalidCellId { cid, num_cells } => {
+                write!(f, "invalid cell id {cid} (world has {num_cells} cells)")
+            }
+            SimulationError::OutOfBoundsMove { agent, from, to } => {
+                write!(f, "agent {agent} cannot move from cell {from} to cell {to}")
+            }
+            SimulationError::InvariantViolation {
+                tick,
+                entity,
+                reason,
+            } => {
+                write!(f, "invariant violated at tick {tick} by {entity}: {reason}")
+            }
+            SimulationError::InvalidConfig(reason) => {
+                write!(f, "invalid world configuration: {reason}")
+            }
+            SimulationError::InvalidGenome(reason) => write!(f, "invalid genome: {reason}"),
+            SimulationError::Multiple(errors) => {
+                write!(f, "{} errors:", errors.len())?;
+                for err in errors {
+                    write!(f, " [{err}]")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for SimulationError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_includes_details() {
+        let err = SimulationError::InvalidCellId {
+            cid: 12,
+            num_cells: 9,
+        };
+        assert_eq!(err.to_string(), "invalid cell id 12 (world has 9 cells)");
+
+        let err = SimulationError::InvalidConfig("width must be > 0".into());
+        assert_eq!(
+            err.to_string(),
+            "invalid world configuration: width must be > 0"
+        );
+
+        let err = SimulationError::InvalidGenome("missing header".into());
+        assert_eq!(err.to_string(), "invalid genome: missing header");
+    }
+
+    #[test]
+    fn invariant_violation_names_tick_and_entity() {
+        let err = SimulationError::InvariantViolation {
+            tick: 4,
+            entity: Entity::Agent(2),
+            reason: "moved while dead".into(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "invariant violated at tick 4 by agent 2: moved while dead"
+        );
+    }
+
+    #[test]
+    fn collect_returns_ok_single_or_multiple() {
+        assert_eq!(SimulationError::collect(Vec::new()), Ok(()));
+
+        let single = SimulationError::collect(vec![SimulationError::NotAlive]);
+        assert_eq!(single, Err(SimulationError::NotAlive));
+
+        let many = SimulationError::collect(vec![
+            SimulationError::NotAlive,
+            SimulationError::NotEnoughResources { available: 1 },
+        ])
+        .unwrap_err();
+        assert!(matches!(many, SimulationError::Multiple(ref v) if v.len() == 2));
+        assert!(many.to_string().starts_with("2 errors:"));
+    }
+
+    #[test]
+    fn collect_flattens_nested_multiple() {
+        let nested = SimulationError::Multiple(vec![
+            SimulationError::NotAlive,
+            SimulationError::Multiple(vec![SimulationError::NotAlive]),
+        ]);
+        let err = SimulationError::collect(vec![nested, SimulationError::NotAlive]).unwrap_err();
+        assert_eq!(
+            err,
+            SimulationError::Multiple(vec![SimulationError::NotAlive; 3])
+        );
+    }
+
+    #[test]
+    fn implements_std_error() {
+        fn takes_error(_: &dyn std::error::Error) {}
+        takes_error(&SimulationError::NotAlive);
+    }
 }
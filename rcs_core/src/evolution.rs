@@ -0,0 +1,405 @@
+use std::fmt;
+use std::str::FromStr;
+
+use rand::Rng;
+
+use crate::errors::SimulationError;
+use crate::policy::{GreedyPolicy, MovementPolicy};
+use crate::rl::{Action, Observation};
+use crate::{Agent, World};
+
+/// Settings of the evolutionary mode.
+///
+/// When `enabled`, agents store the food they take beyond their need as
+/// energy (up to `appetite` extra per tick). An agent holding at least
+/// `birth_cost` energy pays it to spawn a child with `child_hp` health on
+/// its cell or a free neighbor. The child inherits the parent's genome,
+/// with each weight perturbed by up to `±mutation_size` with probability
+/// `mutation_rate`. Births stop while `max_agents` agents are alive.
+///
+/// Selection is implicit: agents whose controllers fail to find food
+/// starve before they can reproduce.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EvolutionConfig {
+    pub enabled: bool,
+    pub hidden: usize,
+    pub appetite: u32,
+    pub birth_cost: u32,
+    pub child_hp: u32,
+    pub mutation_rate: f32,
+    pub mutation_size: f32,
+    pub max_agents: usize,
+}
+
+impl EvolutionConfig {
+    /// Returns whether the settings are usable.
+    ///
+    /// ### Returns
+    /// `true` if the network has hidden units, births cost energy and give
+    /// health, and the mutation parameters are finite and in range.
+    pub fn is_valid(&self) -> bool {
+        self.hidden > 0
+            && self.birth_cost > 0
+            && self.child_hp > 0
+            && (0.0..=1.0).contains(&self.mutation_rate)
+            && self.mutation_size.is_finite()
+            && self.mutation_size >= 0.0
+    }
+}
+
+impl Default for EvolutionConfig {
+    /// Returns a disabled configuration with moderate mutation.
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            hidden: 6,
+            appetite: 2,
+            birth_cost: 10,
+            child_hp: 3,
+            mutation_rate: 0.1,
+            mutation_size: 0.5,
+            max_agents: 500,
+        }
+    }
+}
+
+/// A small feed-forward network mapping an agent's view to an [`Action`].
+///
+/// The network has [`Genome::INPUTS`] inputs (see [`Genome::inputs`]), one
+/// hidden `tanh` layer and one output per entry of [`Action::ALL`]; the
+/// action with the largest output wins. `weights` holds, in order, the
+/// hidden layer (each row: input weights, then bias) and the output layer
+/// (each row: hidden weights, then bias).
+///
+/// Genomes are serialised as plain text via [`fmt::Display`] and parsed
+/// back with [`FromStr`]:
+///
+/// ```text
+/// rcs-genome 1
+/// inputs 12 hidden 6 outputs 5
+/// <weights separated by spaces>
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Genome {
+    hidden: usize,
+    weights: Vec<f32>,
+}
+
+impl Genome {
+    /// Number of network inputs: a 3×3 resource patch plus health, energy
+    /// and hunger.
+    pub const INPUTS: usize = 12;
+    /// Number of network outputs, one per [`Action`].
+    pub const OUTPUTS: usize = 5;
+
+    /// Returns the number of weights of a network with `hidden` units.
+    fn weight_count(hidden: usize) -> usize {
+        (Self::INPUTS + 1) * hidden + (hidden + 1) * Self::OUTPUTS
+    }
+
+    /// Creates a genome from explicit weights.
+    ///
+    /// ### Parameters
+    /// - `hidden`: Number of hidden units.
+    /// - `weights`: All weights in the layout described on [`Genome`].
+    ///
+    /// ### Returns
+    /// - `Ok(Genome)` if the number of weights matches and all are finite.
+    /// - `Err(SimulationError::InvalidGenome(..))` otherwise.
+    pub fn new(hidden: usize, weights: Vec<f32>) -> Result<Self, SimulationError> {
+        let expected = Self::weight_count(hidden);
+        if hidden == 0 || weights.len() != expected {
+            return Err(SimulationError::InvalidGenome(format!(
+                "expected {expected} weights for {hidden} hidden units, got {}",
+                weights.len()
+            )));
+        }
+        if weights.iter().any(|w| !w.is_finite()) {
+            return Err(SimulationError::InvalidGenome(
+                "weights must be finite".into(),
+            ));
+        }
+        Ok(Self { hidden, weights })
+    }
+
+    /// Creates a genome with weights drawn uniformly from `[-1, 1]`.
+    ///
+    /// ### Parameters
+    /// - `hidden`: Number of hidden units; at least `1` is used.
+    /// - `rng`: Random number generator.
+    ///
+    /// ### Returns
+    /// A new random [`Genome`].
+    pub fn random(hidden: usize, rng: &mut impl Rng) -> Self {
+        let hidden = hidden.max(1);
+        let weights = (0..Self::weight_count(hidden))
+            .map(|_| rng.gen_range(-1.0..=1.0))
+            .collect();
+        Self { hidden, weights }
+    }
+
+    /// Returns the number of hidden units.
+    ///
+    /// ### Returns
+    /// The width of the hidden layer.
+    pub fn hidden(&self) -> usize {
+        self.hidden
+    }
+
+    /// Returns all weights.
+    ///
+    /// ### Returns
+    /// A slice in the layout described on [`Genome`].
+    pub fn weights(&self) -> &[f32] {
+        &self.weights
+    }
+
+    /// Returns a mutated copy of this genome.
+    ///
+    /// ### Parameters
+    /// - `rate`: Probability that a weight is perturbed.
+    /// - `size`: Maximum absolute perturbation.
+    /// - `rng`: Random number generator.
+    ///
+    /// ### Returns
+    /// The child's [`Genome`].
+    pub fn mutated(&self, rate: f32, size: f32, rng: &mut impl Rng) -> Self {
+        let rate = f64::from(rate.clamp(0.0, 1.0));
+        let weights = self
+            .weights
+            .iter()
+            .map(|&w| {
+                if size > 0.0 && rng.gen_bool(rate) {
+                    w + rng.gen_range(-size..=size)
+                } else {
+                    w
+                }
+            })
+            .collect();
+        Self {
+            hidden: self.hidden,
+            weights,
+        }
+    }
+
+    /// Encodes an observation as network inputs.
+    ///
+    /// The patch cells become their resource relative to the agent's need,
+    /// capped at `1.0`, or `-1.0` outside the grid. Health and energy are
+    /// scaled by `1/10` and capped at `1.0`; hunger is `1.0` or `0.0`.
+    ///
+    /// ### Parameters
+    /// - `obs`: An observation with radius `1`.
+    ///
+    /// ### Returns
+    /// The [`Genome::INPUTS`] input values.
+    pub fn inputs(obs: &Observation) -> [f32; Self::INPUTS] {
+        let need = obs.need.max(1) as f32;
+        let mut inputs = [0.0; Self::INPUTS];
+        let mut i = 0;
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                inputs[i] = obs.at(dx, dy).map_or(-1.0, |r| (r as f32 / need).min(1.0));
+                i += 1;
+            }
+        }
+        inputs[9] = (obs.health_point as f32 / 10.0).min(1.0);
+        inputs[10] = (obs.energy as f32 / 10.0).min(1.0);
+        inputs[11] = if obs.hungry { 1.0 } else { 0.0 };
+        inputs
+    }
+
+    /// Runs the network on an observation.
+    ///
+    /// ### Parameters
+    /// - `obs`: An observation with radius `1`.
+    ///
+    /// ### Returns
+    /// The [`Action`] with the largest output; the earlier one on ties.
+    pub fn decide(&self, obs: &Observation) -> Action {
+        let inputs = Self::inputs(obs);
+        let (hidden_weights, output_weights) =
+            self.weights.split_at((Self::INPUTS + 1) * self.hidden);
+
+        let hidden: Vec<f32> = hidden_weights
+            .chunks_exact(Self::INPUTS + 1)
+            .map(|row| {
+                let sum: f32 = row.iter().zip(&inputs).map(|(w, x)| w * x).sum();
+                (sum + row[Self::INPUTS]).tanh()
+            })
+            .collect();
+
+        let mut best = (0, f32::NEG_INFINITY);
+        for (i, row) in output_weights.chunks_exact(self.hidden + 1).enumerate() {
+            let sum: f32 = row.iter().zip(&hidden).map(|(w, h)| w * h).sum();
+            let out = sum + row[self.hidden];
+            if out > best.1 {
+                best = (i, out);
+            }
+        }
+        Action::ALL[best.0]
+    }
+}
+
+impl fmt::Display for Genome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "rcs-genome 1")?;
+        writeln!(
+            f,
+            "inputs {} hidden {} outputs {}",
+            Self::INPUTS,
+            self.hidden,
+            Self::OUTPUTS
+        )?;
+        let weights: Vec<String> = self.weights.iter().map(|w| w.to_string()).collect();
+        writeln!(f, "{}", weights.join(" "))
+    }
+}
+
+impl FromStr for Genome {
+    type Err = SimulationError;
+
+    /// Parses the text format written by [`fmt::Display`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| SimulationError::InvalidGenome(reason.to_string());
+        let mut lines = s.lines().map(str::trim).filter(|l| !l.is_empty());
+
+        if lines.next() != Some("rcs-genome 1") {
+            return Err(invalid("missing 'rcs-genome 1' header"));
+        }
+        let shape: Vec<&str> = lines
+            .next()
+            .ok_or_else(|| invalid("missing shape line"))?
+            .split_whitespace()
+            .collect();
+        let hidden = match shape.as_slice() {
+            ["inputs", inputs, "hidden", hidden, "outputs", outputs]
+                if inputs.parse() == Ok(Self::INPUTS) && outputs.parse() == Ok(Self::OUTPUTS) =>
+            {
+                hidden
+                    .parse()
+                    .map_err(|_| invalid("hidden size is not a number"))?
+            }
+            _ => return Err(invalid("unsupported network shape")),
+        };
+        let weights = lines
+            .flat_map(str::split_whitespace)
+            .map(|w| w.parse().map_err(|_| invalid("weight is not a number")))
+            .collect::<Result<Vec<f32>, _>>()?;
+        Genome::new(hidden, weights)
+    }
+}
+
+/// Moves agents as their [`Genome`] dictates.
+///
+/// Each living agent's network is fed a radius-1 [`World::observe`] view.
+/// Agents without a genome fall back to the [`GreedyPolicy`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NeuralPolicy;
+
+impl MovementPolicy for NeuralPolicy {
+    fn name(&self) -> &'static str {
+        "neural"
+    }
+
+    fn decide(&self, world: &World, agent: &Agent) -> Option<usize> {
+        match agent.genome() {
+            Some(genome) => genome
+                .decide(&world.observe(agent, 1))
+                .target(agent.cid(), world.size()),
+            None => GreedyPolicy.decide(world, agent),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cell, Updatable};
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    /// A genome whose output for `action` is a constant `1.0`.
+    fn constant_genome(action: Action) -> Genome {
+        let hidden = 1;
+        let mut weights = vec![0.0; Genome::weight_count(hidden)];
+        let output_start = Genome::INPUTS + 1;
+        weights[output_start + action.index() * (hidden + 1) + hidden] = 1.0;
+        Genome::new(hidden, weights).unwrap()
+    }
+
+    #[test]
+    fn text_format_round_trips() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let genome = Genome::random(4, &mut rng);
+        let text = genome.to_string();
+        assert!(text.starts_with("rcs-genome 1\ninputs 12 hidden 4 outputs 5\n"));
+        assert_eq!(text.parse::<Genome>().unwrap(), genome);
+    }
+
+    #[test]
+    fn parse_rejects_malformed_text() {
+        assert!(matches!(
+            "hello".parse::<Genome>(),
+            Err(SimulationError::InvalidGenome(_))
+        ));
+        let short = "rcs-genome 1\ninputs 12 hidden 1 outputs 5\n0.5 0.5\n";
+        assert!(short.parse::<Genome>().is_err());
+        let shape = "rcs-genome 1\ninputs 3 hidden 1 outputs 5\n";
+        assert!(shape.parse::<Genome>().is_err());
+    }
+
+    #[test]
+    fn mutation_changes_only_some_weights() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let parent = Genome::random(3, &mut rng);
+        assert_eq!(parent.mutated(0.0, 1.0, &mut rng), parent);
+
+        let child = parent.mutated(1.0, 0.5, &mut rng);
+        assert_ne!(child, parent);
+        for (a, b) in parent.weights().iter().zip(child.weights()) {
+            assert!((a - b).abs() <= 0.5);
+        }
+    }
+
+    #[test]
+    fn neural_policy_follows_the_genome() {
+        let cells = (0..3).map(|id| Cell::new(id, 0, 100, 0, 5)).collect();
+        let world = World::new((3, 1), cells, Vec::new());
+
+        let mut agent = Agent::new(0, 1, 1, 0, 5, true);
+        agent.set_genome(Some(constant_genome(Action::Left)));
+        assert_eq!(NeuralPolicy.decide(&world, &agent), Some(0));
+
+        agent.set_genome(Some(constant_genome(Action::Up)));
+        assert_eq!(NeuralPolicy.decide(&world, &agent), None);
+    }
+
+    #[test]
+    fn fed_agents_reproduce_and_pass_on_their_genome() {
+        let cells = vec![Cell::new(0, 100, 100, 10, 10)];
+        let mut parent = Agent::new(0, 0, 1, 0, 5, true);
+        parent.set_genome(Some(constant_genome(Action::Stay)));
+        let mut world = World::new((1, 1), cells, vec![parent]);
+        world.set_seed(3);
+        world.set_policy(Box::new(NeuralPolicy));
+        world.set_evolution(EvolutionConfig {
+            enabled: true,
+            appetite: 4,
+            birth_cost: 8,
+            mutation_rate: 1.0,
+            max_agents: 3,
+            ..EvolutionConfig::default()
+        });
+        for _ in 0..10 {
+            world.update().unwrap();
+        }
+
+        let agents = world.agents();
+        assert_eq!(agents.len(), 3);
+        assert_eq!(agents[0].offspring(), 2);
+        assert_eq!(agents[1].health_point(), 3);
+        assert_ne!(agents[1].genome(), agents[0].genome());
+        assert_eq!(world.best_genome(), agents[0].genome());
+    }
+}
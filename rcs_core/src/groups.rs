@@ -0,0 +1,107 @@
+/// Settings of the optional group layer.
+///
+/// When `enabled`, every agent is assigned to one of `groups` groups at
+/// random, and children join their parent's group. Groups with an index
+/// below `altruistic_groups` share food: after resources are allocated, a
+/// member that got less than it needs receives energy from fellow members
+/// on its own cell (and on the four neighboring cells if `share_adjacent`
+/// is set). Each donor keeps at least `reserve` energy and gives at most
+/// `max_gift` per recipient and tick. The remaining groups are selfish.
+///
+/// Only agents with an appetite store energy (see
+/// [`crate::Agent::set_appetite`]), so sharing is most useful together
+/// with the evolutionary mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GroupConfig {
+    pub enabled: bool,
+    pub groups: usize,
+    pub altruistic_groups: usize,
+    pub reserve: u32,
+    pub max_gift: u32,
+    pub share_adjacent: bool,
+}
+
+impl GroupConfig {
+    /// Returns whether the settings are usable.
+    ///
+    /// ### Returns
+    /// `true` if there is at least one group and no more altruistic groups
+    /// than groups.
+    pub fn is_valid(&self) -> bool {
+        self.groups > 0 && self.altruistic_groups <= self.groups
+    }
+
+    /// Returns whether the members of a group share food.
+    ///
+    /// ### Parameters
+    /// - `group`: Index of the group.
+    ///
+    /// ### Returns
+    /// `true` if `group < altruistic_groups`.
+    pub fn is_altruistic(&self, group: usize) -> bool {
+        group < self.altruistic_groups
+    }
+}
+
+impl Default for GroupConfig {
+    /// Returns a disabled configuration with one altruistic and one
+    /// selfish group.
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            groups: 2,
+            altruistic_groups: 1,
+            reserve: 5,
+            max_gift: 2,
+            share_adjacent: true,
+        }
+    }
+}
+
+/// Summary of one group, see [`crate::World::group_statistics`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GroupStatistics {
+    pub group: usize,
+    pub living: usize,
+    pub dead: usize,
+    pub births: u64,
+    pub energy: u64,
+    pub shared: u64,
+}
+
+impl GroupStatistics {
+    /// Returns the average energy of the living members.
+    ///
+    /// ### Returns
+    /// - `Some(energy)` if the group has living members.
+    /// - `None` otherwise.
+    pub fn mean_energy(&self) -> Option<f64> {
+        (self.living > 0).then(|| self.energy as f64 / self.living as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn altruism_and_validation() {
+        let cfg = GroupConfig::default();
+        assert!(cfg.is_valid());
+        assert!(cfg.is_altruistic(0));
+        assert!(!cfg.is_altruistic(1));
+        let bad = GroupConfig {
+            altruistic_groups: 3,
+            ..cfg
+        };
+        assert!(!bad.is_valid());
+
+        let stats = GroupStatistics {
+            living: 4,
+            energy: 10,
+            ..GroupStatistics::default()
+        };
+        assert_eq!(stats.mean_energy(), Some(2.5));
+        assert_eq!(GroupStatistics::default().mean_energy(), None);
+    }
+}
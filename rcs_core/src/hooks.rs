@@ -0,0 +1,186 @@
+use crate::{Agent, World};
+
+/// Extends [`World::update`] with custom rules.
+///
+/// Hooks are registered with [`World::add_hook`] and called in
+/// registration order at fixed points of every tick:
+///
+/// 1. [`WorldHook::before_tick`], before anything else happens
+/// 2. [`WorldHook::after_regen`], once the cells have regenerated
+/// 3. [`WorldHook::after_allocation`], once resources are allocated
+/// 4. [`WorldHook::on_agent_moved`] / [`WorldHook::on_agent_died`] while
+///    the agents are stepped (deaths from fights and disease are reported
+///    too)
+/// 5. [`WorldHook::after_tick`], after all phases and before the tick
+///    counter advances
+///
+/// Phase hooks get mutable access to the world and may change cells,
+/// settings and agents (through [`World::agent_mut`]) with its public API;
+/// per-agent callbacks only read it. With debug checks enabled, resource
+/// a hook adds or removes in the middle of a tick is recorded in the
+/// [`crate::ResourceLedger`] as `hook_added` / `hook_removed`.
+///
+/// Hooks must not run the phases of a tick themselves:
+/// [`World::allocate_resources`] and [`World::step_all_agents`] would run
+/// twice, and [`World::compact`] does nothing between
+/// [`WorldHook::after_regen`] and [`WorldHook::after_tick`]. Calling
+/// [`World::clear_hooks`] from a callback does not remove the running
+/// hooks.
+///
+/// All methods do nothing by default. Like [`crate::MovementPolicy`],
+/// hooks must be `Send + Sync` so that worlds can be moved across threads.
+pub trait WorldHook: Send + Sync {
+    /// Called at the start of a tick.
+    ///
+    /// ### Parameters
+    /// - `world`: The world about to be updated.
+    fn before_tick(&mut self, _world: &mut World) {}
+
+    /// Called after the cells have regenerated.
+    ///
+    /// ### Parameters
+    /// - `world`: The world being updated.
+    fn after_regen(&mut self, _world: &mut World) {}
+
+    /// Called after resources have been allocated to the agents.
+    ///
+    /// ### Parameters
+    /// - `world`: The world being updated.
+    fn after_allocation(&mut self, _world: &mut World) {}
+
+    /// Called when an agent has moved to a new cell.
+    ///
+    /// ### Parameters
+    /// - `world`: The world being updated.
+    /// - `agent`: The agent, already standing on its new cell.
+    /// - `from`: The cell it left.
+    fn on_agent_moved(&mut self, _world: &World, _agent: &Agent, _from: usize) {}
+
+    /// Called when an agent has died.
+    ///
+    /// ### Parameters
+    /// - `world`: The world being updated.
+    /// - `agent`: The dead agent (see [`Agent::death_cause`]).
+    fn on_agent_died(&mut self, _world: &World, _agent: &Agent) {}
+
+    /// Called at the end of a tick.
+    ///
+    /// ### Parameters
+    /// - `world`: The updated world; [`World::tick`] has not advanced yet.
+    fn after_tick(&mut self, _world: &mut World) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cell, Updatable};
+    use std::sync::{Arc, Mutex};
+
+    /// Logs every callback and refills cell 0 after allocation.
+    struct Recorder(Arc<Mutex<Vec<String>>>);
+
+    impl WorldHook for Recorder {
+        fn before_tick(&mut self, world: &mut World) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("before {}", world.tick()));
+        }
+
+        fn after_regen(&mut self, _world: &mut World) {
+            self.0.lock().unwrap().push("regen".to_string());
+        }
+
+        fn after_allocation(&mut self, world: &mut World) {
+            self.0.lock().unwrap().push("allocation".to_string());
+            world.cell_mut(0).add_resource(7);
+        }
+
+        fn on_agent_moved(&mut self, _world: &World, agent: &Agent, from: usize) {
+            let log = format!("moved {} {from}->{}", agent.id(), agent.cid());
+            self.0.lock().unwrap().push(log);
+        }
+
+        fn on_agent_died(&mut self, _world: &World, agent: &Agent) {
+            self.0.lock().unwrap().push(format!("died {}", agent.id()));
+        }
+
+        fn after_tick(&mut self, world: &mut World) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("after {}", world.tick()));
+        }
+    }
+
+    #[test]
+    fn hooks_see_every_phase_and_can_change_cells() {
+        let cells = vec![Cell::new(0, 0, 100, 0, 10), Cell::new(1, 5, 100, 0, 10)];
+        let agents = vec![
+            Agent::new(0, 0, 1, 0, 5, true),
+            Agent::new(1, 0, 1, 0, 1, true),
+        ];
+        let mut world = World::new((2, 1), cells, agents);
+        let log = Arc::new(Mutex::new(Vec::new()));
+        world.add_hook(Box::new(Recorder(log.clone())));
+
+        world.update().unwrap();
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            [
+                "before 0",
+                "regen",
+                "allocation",
+                "moved 0 0->1",
+                "moved 1 0->1",
+                "died 1",
+                "after 0",
+            ]
+        );
+        // The refill happened after allocation, so nobody ate it.
+        assert_eq!(world.cell(0).cur_resource(), 7);
+        assert_eq!(world.hook_count(), 1);
+    }
+
+    /// Kills agent 1 after allocation and tries to compact at both ends.
+    struct Meddler(Arc<Mutex<Vec<usize>>>);
+
+    impl WorldHook for Meddler {
+        fn after_allocation(&mut self, world: &mut World) {
+            self.0.lock().unwrap().push(world.compact());
+            if let Some(mut agent) = world.agent_mut(1) {
+                agent.wound(10);
+            }
+        }
+
+        fn after_tick(&mut self, world: &mut World) {
+            self.0.lock().unwrap().push(world.compact());
+        }
+    }
+
+    #[test]
+    fn hooks_kill_agents_but_compact_only_between_phases() {
+        let cells = (0..3).map(|id| Cell::new(id, 5, 100, 0, 10)).collect();
+        let agents = vec![
+            Agent::new(0, 0, 1, 0, 5, true),
+            Agent::new(1, 1, 1, 0, 5, true),
+            Agent::new(2, 2, 1, 0, 5, false),
+        ];
+        let mut world = World::new((3, 1), cells, agents);
+        world.set_debug_checks(true);
+        let compacted = Arc::new(Mutex::new(Vec::new()));
+        world.add_hook(Box::new(Meddler(compacted.clone())));
+
+        world.update().unwrap();
+
+        assert_eq!(*compacted.lock().unwrap(), [0, 2]);
+        assert!(
+            world
+                .events()
+                .iter()
+                .any(|e| matches!(e, crate::WorldEvent::Death { agent: 1, .. }))
+        );
+        assert_eq!(world.agents().len(), 1);
+    }
+}
@@ -0,0 +1,245 @@
+use crate::errors::SimulationError;
+
+/// Number of low bits of an agent id that hold its slot.
+const SLOT_BITS: u32 = 32;
+
+/// Most unused slots an explicit id may skip past the end of the id map, so
+/// that one stray id cannot make the map allocate billions of slots.
+pub const MAX_SLOT_GAP: usize = 1 << 16;
+
+// Agent ids pack a 32-bit generation above a 32-bit slot into a `usize`,
+// which only fits on 64-bit targets.
+const _: () = assert!(
+    usize::BITS >= 64,
+    "agent ids need a 64-bit usize to hold their generation"
+);
+
+/// Splits an agent id into its slot and generation.
+///
+/// An id is `generation << 32 | slot`, so the agents of a fresh world,
+/// which all have generation `0`, keep ids equal to their slots.
+///
+/// ### Parameters
+/// - `id`: An agent id (see [`crate::Agent::id`]).
+///
+/// ### Returns
+/// `(slot, generation)`.
+pub fn split_id(id: usize) -> (usize, u32) {
+    let id = id as u64;
+    (
+        (id & u64::from(u32::MAX)) as usize,
+        (id >> SLOT_BITS) as u32,
+    )
+}
+
+/// Builds an agent id from a slot and a generation.
+///
+/// ### Parameters
+/// - `slot`: Slot in the id map.
+/// - `generation`: How many times the slot has been reused.
+///
+/// ### Returns
+/// The id `generation << 32 | slot`. Ids need a 64-bit `usize`; the crate
+/// does not build on narrower targets.
+pub fn make_id(slot: usize, generation: u32) -> usize {
+    ((u64::from(generation) << SLOT_BITS) | slot as u64) as usize
+}
+
+/// One slot of the id map.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Slot {
+    generation: u32,
+    index: Option<usize>,
+    /// Whether the slot is waiting in the free list.
+    free: bool,
+}
+
+/// A generational map from stable agent ids to positions in
+/// [`crate::World::agents`].
+///
+/// Compacting the world moves agents to new positions; their ids stay the
+/// same. Slots freed by compaction are reused with a new generation, so an
+/// id is never handed out twice and a stale id (e.g. a GUI selection of an
+/// archived agent) simply resolves to nothing.
+///
+/// The free list may hold slots that were taken by an explicit id since;
+/// they are skipped when it is popped.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AgentIds {
+    slots: Vec<Slot>,
+    free: Vec<usize>,
+}
+
+impl AgentIds {
+    /// Returns the current position of an agent.
+    ///
+    /// ### Parameters
+    /// - `id`: Stable id of the agent.
+    ///
+    /// ### Returns
+    /// - `Some(index)` into [`crate::World::agents`] if the id is live.
+    /// - `None` if it was never issued or the agent has been archived.
+    pub fn index_of(&self, id: usize) -> Option<usize> {
+        let (slot, generation) = split_id(id);
+        self.slots
+            .get(slot)
+            .filter(|s| s.generation == generation)
+            .and_then(|s| s.index)
+    }
+
+    /// Returns the number of agents currently mapped.
+    ///
+    /// ### Returns
+    /// The number of live ids.
+    pub fn len(&self) -> usize {
+        self.slots.iter().filter(|s| s.index.is_some()).count()
+    }
+
+    /// Returns whether no agent is mapped.
+    ///
+    /// ### Returns
+    /// `true` if [`AgentIds::len`] is zero.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Registers an agent that already has an id.
+    ///
+    /// ### Parameters
+    /// - `id`: The agent's id.
+    /// - `index`: Its position in the world's agent list.
+    ///
+    /// ### Returns
+    /// - `Ok(())` on success.
+    /// - `Err(SimulationError::InvalidConfig)` if the id's slot lies more
+    ///   than [`MAX_SLOT_GAP`] slots past the end of the map; nothing is
+    ///   registered then.
+    pub(crate) fn insert(&mut self, id: usize, index: usize) -> Result<(), SimulationError> {
+        let (slot, generation) = split_id(id);
+        if slot.saturating_sub(self.slots.len()) > MAX_SLOT_GAP {
+            return Err(SimulationError::InvalidConfig(format!(
+                "agent id {id} skips more than {MAX_SLOT_GAP} unused slots"
+            )));
+        }
+        if slot >= self.slots.len() {
+            let gap = self.slots.len()..slot;
+            self.free.extend(gap.clone());
+            self.slots.extend(gap.map(|_| Slot {
+                free: true,
+                ..Slot::default()
+            }));
+            self.slots.push(Slot::default());
+        }
+        self.slots[slot] = Slot {
+            generation,
+            index: Some(index),
+            free: false,
+        };
+        Ok(())
+    }
+
+    /// Issues a fresh id for a new agent.
+    ///
+    /// ### Parameters
+    /// - `index`: The new agent's position in the world's agent list.
+    ///
+    /// ### Returns
+    /// An id that has never been issued before.
+    pub(crate) fn allocate(&mut self, index: usize) -> usize {
+        let slot = loop {
+            match self.free.pop() {
+                Some(slot) if self.slots[slot].free => break slot,
+                Some(_) => continue,
+                None => {
+                    self.slots.push(Slot::default());
+                    break self.slots.len() - 1;
+                }
+            }
+        };
+        self.slots[slot].index = Some(index);
+        self.slots[slot].free = false;
+        make_id(slot, self.slots[slot].generation)
+    }
+
+    /// Points a live id at a new position.
+    ///
+    /// ### Parameters
+    /// - `id`: Stable id of the agent.
+    /// - `index`: Its new position in the world's agent list.
+    pub(crate) fn relocate(&mut self, id: usize, index: usize) {
+        let (slot, generation) = split_id(id);
+        if let Some(s) = self
+            .slots
+            .get_mut(slot)
+            .filter(|s| s.generation == generation)
+        {
+            s.index = Some(index);
+        }
+    }
+
+    /// Retires an id; its slot is reused with the next generation.
+    ///
+    /// ### Parameters
+    /// - `id`: Stable id of the archived agent.
+    pub(crate) fn release(&mut self, id: usize) {
+        let (slot, generation) = split_id(id);
+        if let Some(s) = self
+            .slots
+            .get_mut(slot)
+            .filter(|s| s.generation == generation && s.index.is_some())
+        {
+            s.index = None;
+            s.generation = s.generation.wrapping_add(1);
+            s.free = true;
+            self.free.push(slot);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn released_slots_are_reused_with_a_new_generation() {
+        let mut ids = AgentIds::default();
+        ids.insert(0, 0).unwrap();
+        ids.insert(1, 1).unwrap();
+        assert_eq!(ids.allocate(2), 2);
+
+        ids.release(1);
+        assert_eq!(ids.index_of(1), None);
+        ids.relocate(2, 1);
+        let reused = ids.allocate(2);
+        assert_eq!(split_id(reused), (1, 1));
+        assert_eq!(ids.index_of(reused), Some(2));
+        assert_eq!(ids.index_of(2), Some(1));
+        assert_eq!(ids.len(), 3);
+    }
+
+    #[test]
+    fn gaps_left_by_explicit_ids_are_free() {
+        let mut ids = AgentIds::default();
+        ids.insert(2, 0).unwrap();
+        assert_eq!(ids.index_of(2), Some(0));
+        assert_eq!(ids.index_of(0), None);
+        let fresh = ids.allocate(1);
+        assert!(fresh < 2);
+        assert_eq!(ids.index_of(fresh), Some(1));
+    }
+
+    #[test]
+    fn far_away_ids_are_refused_and_taken_free_slots_skipped() {
+        let mut ids = AgentIds::default();
+        assert!(ids.insert(MAX_SLOT_GAP + 1, 0).is_err());
+        assert!(ids.insert(make_id(usize::MAX >> 32, 0), 0).is_err());
+        assert!(ids.is_empty());
+
+        ids.insert(3, 0).unwrap();
+        ids.insert(1, 1).unwrap();
+        let mut fresh = vec![ids.allocate(2), ids.allocate(3), ids.allocate(4)];
+        fresh.sort_unstable();
+        assert_eq!(fresh, [0, 2, 4]);
+        assert_eq!(ids.index_of(1), Some(1));
+    }
+}
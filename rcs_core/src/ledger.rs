@@ -0,0 +1,280 @@
+use crate::errors::{Entity, SimulationError};
+use crate::{Agent, Cell};
+
+/// Resource bookkeeping for a single world update.
+///
+/// A ledger is only recorded while debug checks are enabled on the world
+/// (see [`crate::World::set_debug_checks`]). All amounts are totals over
+/// every cell of the world for one tick:
+/// - `regenerated`: resource actually added by cell regeneration
+/// - `consumed`: resource removed from cells by agents
+/// - `wasted`: regeneration or corpse resource lost to the `max_resource` cap
+/// - `corpse_deposited`: resource actually added by dead agents
+/// - `hook_added` / `hook_removed`: resource added to or taken from cells
+///   by [`crate::WorldHook`]s in the middle of the tick
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResourceLedger {
+    pub tick: u64,
+    pub total_before: u64,
+    pub total_after: u64,
+    pub regenerated: u64,
+    pub consumed: u64,
+    pub wasted: u64,
+    pub corpse_deposited: u64,
+    pub hook_added: u64,
+    pub hook_removed: u64,
+}
+
+impl ResourceLedger {
+    /// Starts a new ledger for a tick.
+    ///
+    /// ### Parameters
+    /// - `tick`: The tick being recorded.
+    /// - `cells`: The cells at the start of the tick.
+    ///
+    /// ### Returns
+    /// A ledger with `total_before` set and all flows at zero.
+    pub fn open(tick: u64, cells: &[Cell]) -> Self {
+        Self {
+            tick,
+            total_before: total_resource(cells),
+            ..Self::default()
+        }
+    }
+
+    /// Records a deposit into a cell, splitting it into kept and wasted parts.
+    ///
+    /// ### Parameters
+    /// - `offered`: The amount that was offered to the cell.
+    /// - `before`: The cell's resource before the deposit.
+    /// - `after`: The cell's resource after the deposit.
+    ///
+    /// ### Returns
+    /// The amount actually kept by the cell. The rest is added to `wasted`.
+    pub fn record_deposit(&mut self, offered: u32, before: u32, after: u32) -> u64 {
+        let kept = after.saturating_sub(before);
+        self.wasted += u64::from(offered.saturating_sub(kept));
+        u64::from(kept)
+    }
+
+    /// Returns the resource total predicted by the recorded flows.
+    ///
+    /// ### Returns
+    /// `total_before + regenerated + corpse_deposited + hook_added -
+    /// consumed - hook_removed`.
+    pub fn expected_total(&self) -> u64 {
+        (self.total_before + self.regenerated + self.corpse_deposited + self.hook_added)
+            .saturating_sub(self.consumed + self.hook_removed)
+    }
+
+    /// Records the change a hook made to the cells that no other flow
+    /// explains.
+    ///
+    /// ### Parameters
+    /// - `change`: Change of the resource total during the hook, minus the
+    ///   flows recorded meanwhile (e.g. corpses of agents the hook killed).
+    pub fn record_hook(&mut self, change: i64) {
+        if change >= 0 {
+            self.hook_added += change.unsigned_abs();
+        } else {
+            self.hook_removed += change.unsigned_abs();
+        }
+    }
+
+    /// Returns whether the recorded flows explain the final total.
+    ///
+    /// ### Returns
+    /// `true` if [`ResourceLedger::expected_total`] equals `total_after`.
+    pub fn is_balanced(&self) -> bool {
+        self.expected_total() == self.total_after
+    }
+}
+
+/// Sums the current resource of all cells.
+///
+/// ### Parameters
+/// - `cells`: The cells to sum over.
+///
+/// ### Returns
+/// The total resource stored in `cells`.
+pub fn total_resource(cells: &[Cell]) -> u64 {
+    cells.iter().map(|c| u64::from(c.cur_resource())).sum()
+}
+
+/// Records where the dead agents are at the start of a tick.
+///
+/// ### Parameters
+/// - `agents`: All agents of the world.
+///
+/// ### Returns
+/// `(index, cell_id)` pairs for every agent that is already dead.
+pub fn dead_positions(agents: &[Agent]) -> Vec<(usize, usize)> {
+    agents
+        .iter()
+        .enumerate()
+        .filter(|(_, a)| !a.is_alive())
+        .map(|(i, a)| (i, a.cid()))
+        .collect()
+}
+
+/// Checks the world invariants at the end of a tick.
+///
+/// The checked invariants are:
+/// - every cell holds at most `max_resource` and regenerates at most
+///   `max_regen_rate`
+/// - every agent stands on a cell inside the grid
+/// - agents that were dead at the start of the tick did not move
+/// - the ledger balances (resource is conserved)
+///
+/// ### Parameters
+/// - `ledger`: The closed ledger of the tick.
+/// - `cells`: The cells at the end of the tick.
+/// - `agents`: The agents at the end of the tick.
+/// - `dead_before`: Output of [`dead_positions`] taken at the start of the tick.
+///
+/// ### Returns
+/// One [`SimulationError::InvariantViolation`] per broken invariant.
+pub fn check_invariants(
+    ledger: &ResourceLedger,
+    cells: &[Cell],
+    agents: &[Agent],
+    dead_before: &[(usize, usize)],
+) -> Vec<SimulationError> {
+    let tick = ledger.tick;
+    let violation = |entity: Entity, reason: String| SimulationError::InvariantViolation {
+        tick,
+        entity,
+        reason,
+    };
+    let mut errors = Vec::new();
+
+    for cell in cells {
+        if cell.cur_resource() > cell.max_resource() {
+            errors.push(violation(
+                Entity::Cell(cell.id()),
+                format!(
+                    "resource {} exceeds max_resource {}",
+                    cell.cur_resource(),
+                    cell.max_resource()
+                ),
+            ));
+        }
+        if cell.regen_rate() > cell.max_regen_rate() {
+            errors.push(violation(
+                Entity::Cell(cell.id()),
+                format!(
+                    "regen_rate {} exceeds max_regen_rate {}",
+                    cell.regen_rate(),
+                    cell.max_regen_rate()
+                ),
+            ));
+        }
+    }
+
+    for agent in agents {
+        if agent.cid() >= cells.len() {
+            errors.push(violation(
+                Entity::Agent(agent.id()),
+                format!("stands on cell {} outside the grid", agent.cid()),
+            ));
+        }
+    }
+
+    for &(i, cid) in dead_before {
+        if let Some(agent) = agents.get(i)
+            && agent.cid() != cid
+        {
+            errors.push(violation(
+                Entity::Agent(agent.id()),
+                format!("moved from cell {cid} to cell {} while dead", agent.cid()),
+            ));
+        }
+    }
+
+    if !ledger.is_balanced() {
+        errors.push(violation(
+            Entity::World,
+            format!(
+                "resource not conserved: expected {} but cells hold {}",
+                ledger.expected_total(),
+                ledger.total_after
+            ),
+        ));
+    }
+
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_deposit_splits_kept_and_wasted() {
+        let mut ledger = ResourceLedger::default();
+        let kept = ledger.record_deposit(5, 97, 100);
+        assert_eq!(kept, 3);
+        assert_eq!(ledger.wasted, 2);
+    }
+
+    #[test]
+    fn ledger_balances_when_flows_explain_total() {
+        let cells = vec![Cell::new(0, 10, 100, 0, 5), Cell::new(1, 5, 100, 0, 5)];
+        let mut ledger = ResourceLedger::open(0, &cells);
+        assert_eq!(ledger.total_before, 15);
+
+        ledger.regenerated = 4;
+        ledger.corpse_deposited = 5;
+        ledger.consumed = 6;
+        ledger.total_after = 18;
+        assert!(ledger.is_balanced());
+
+        ledger.total_after = 17;
+        assert!(!ledger.is_balanced());
+    }
+
+    #[test]
+    fn check_invariants_reports_out_of_bounds_and_moved_dead_agents() {
+        let cells = vec![Cell::new(0, 0, 10, 0, 1)];
+        let agents = vec![
+            Agent::new(0, 3, 1, 0, 1, true),
+            Agent::new(1, 0, 1, 0, 0, false),
+        ];
+        let ledger = ResourceLedger::open(7, &cells);
+
+        let errors = check_invariants(&ledger, &cells, &agents, &[(1, 2)]);
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(
+            errors[0],
+            SimulationError::InvariantViolation {
+                tick: 7,
+                entity: Entity::Agent(0),
+                ..
+            }
+        ));
+        assert!(matches!(
+            errors[1],
+            SimulationError::InvariantViolation {
+                entity: Entity::Agent(1),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn check_invariants_reports_unbalanced_ledger() {
+        let cells = vec![Cell::new(0, 10, 10, 0, 1)];
+        let mut ledger = ResourceLedger::open(0, &cells);
+        ledger.total_after = 3;
+
+        let errors = check_invariants(&ledger, &cells, &[], &[]);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            errors[0],
+            SimulationError::InvariantViolation {
+                entity: Entity::World,
+                ..
+            }
+        ));
+    }
+}
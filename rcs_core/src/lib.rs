@@ -4,19 +4,98 @@
 //!
 //! - [`Agent`]: mobile entities that consume resources and may die.
 //! - [`Cell`]: resource storage and regeneration at each grid position.
+//! - [`CombatConfig`]: an optional contest mode in which aggressive agents
+//!   fight over scarce cells.
+//! - [`DiseaseConfig`]: an optional epidemic layer with susceptible,
+//!   infected and recovered agents ([`HealthState`]).
+//! - [`Entity`]: the cell or agent an error refers to.
+//! - [`GroupConfig`]: optional groups whose altruistic members share food,
+//!   summarized by [`GroupStatistics`].
+//! - [`Genome`]: an evolvable neural controller, used by the [`NeuralPolicy`]
+//!   when agents reproduce under an [`EvolutionConfig`].
+//! - [`AgentMemory`]: an agent's bounded memory of visited cells, used by
+//!   the [`MemoryPolicy`].
+//! - [`AgentIds`]: a generational map that keeps agent ids stable when the
+//!   world compacts dead agents away.
+//! - [`lifecycle`]: aging ([`AgingConfig`]), [`DeathCause`]s, per-tick
+//!   [`WorldEvent`]s, running [`Statistics`] and the [`GraveRecord`]s of
+//!   compacted agents.
+//! - [`MovementPolicy`]: decides where agents move ([`GreedyPolicy`] by default,
+//!   [`VisionPolicy`] and others selectable via [`PolicyKind`]).
+//! - [`pathfinding`]: A* / Dijkstra over the grid and the [`ForagingPolicy`].
+//! - [`Perception`]: what an agent can see within its perception radius.
+//! - [`PheromoneField`]: an optional per-cell trail that agents deposit
+//!   into when they find food ([`PheromoneConfig`]), followed by the
+//!   [`PheromonePolicy`].
+//! - [`rl`]: an observation/action stepping API ([`World::step_with_actions`])
+//!   and the tabular [`QLearningPolicy`].
+//! - [`ResourceLedger`]: per-tick resource bookkeeping for debug checks.
+//! - [`SoaWorld`]: a headless struct-of-arrays engine for the base
+//!   simulation, for runs with millions of agents.
+//! - [`SpatialIndex`]: which agents stand on which cell, behind the
+//!   [`World::agents_in_cell`], [`World::agents_in_rect`] and
+//!   [`World::agents_within`] queries.
 //! - [`SimulationError`]: error type used by update and movement operations.
+//! - [`UpdateOrder`] / [`ConflictRule`]: how agents are scheduled within a
+//!   tick and who wins a contested cell.
+//! - [`TerritoryMap`]: which agent owns which cell; owners are fed first
+//!   and intruders pay extra to enter ([`TerritoryConfig`]).
 //! - [`Updatable`]: a common trait for types that advance one simulation step.
 //! - [`World`]: the grid of cells and agents, plus the step logic.
 //! - [`WorldConfig`]: configuration for constructing a randomized world.
+//! - [`WorldHook`]: callbacks that let other crates add rules to
+//!   [`World::update`] without forking it.
+//!
+//! The optional `parallel` feature runs cell regeneration, allocation
+//! planning and synchronous move decisions on the rayon thread pool. Results
+//! are identical to the sequential path for the same seed. Move decisions
+//! under the sequential and shuffled update orders depend on the moves made
+//! before them in the same tick, so they always run one agent at a time.
 
 pub mod agent;
 pub mod cell;
+pub mod combat;
+pub mod disease;
 pub mod errors;
+pub mod evolution;
+pub mod groups;
+pub mod hooks;
+pub mod ids;
+pub mod ledger;
+pub mod lifecycle;
+pub mod memory;
+pub mod pathfinding;
+pub mod perception;
+pub mod pheromone;
+pub mod policy;
+pub mod rl;
+pub mod schedule;
+pub mod soa;
+pub mod spatial;
+pub mod territory;
 pub mod traits;
 pub mod world;
 
 pub use agent::Agent;
 pub use cell::Cell;
-pub use errors::SimulationError;
+pub use combat::CombatConfig;
+pub use disease::{DiseaseConfig, HealthState};
+pub use errors::{Entity, SimulationError};
+pub use evolution::{EvolutionConfig, Genome, NeuralPolicy};
+pub use groups::{GroupConfig, GroupStatistics};
+pub use hooks::WorldHook;
+pub use ids::AgentIds;
+pub use ledger::ResourceLedger;
+pub use lifecycle::{AgingConfig, DeathCause, GraveRecord, Statistics, WorldEvent};
+pub use memory::{AgentMemory, MemoryEntry, MemoryPolicy};
+pub use pathfinding::{ForagingPolicy, Path};
+pub use perception::{Perception, PerceptionConfig, VisionPolicy};
+pub use pheromone::{PheromoneConfig, PheromoneField, PheromonePolicy};
+pub use policy::{GreedyPolicy, MoveOutcome, MovementPolicy, PolicyKind};
+pub use rl::{Action, Observation, QLearningPolicy, QTable, RewardConfig, StepResult};
+pub use schedule::{ConflictRule, UpdateOrder};
+pub use soa::SoaWorld;
+pub use spatial::SpatialIndex;
+pub use territory::{TerritoryConfig, TerritoryMap};
 pub use traits::Updatable;
-pub use world::{World, WorldConfig};
+pub use world::{AgentMut, World, WorldConfig};
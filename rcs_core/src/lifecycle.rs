@@ -0,0 +1,262 @@
+use crate::Agent;
+use crate::evolution::Genome;
+
+/// Why an agent died.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DeathCause {
+    /// Health ran out while underfed.
+    Starvation,
+    /// Health ran out paying a movement cost.
+    Exhaustion,
+    /// The agent reached its maximum lifespan.
+    OldAge,
+    /// Health ran out while fighting an infection.
+    Disease,
+    /// Health ran out in a fight over a contested cell.
+    Combat,
+}
+
+impl DeathCause {
+    /// All death causes, e.g. for iterating over [`Statistics`].
+    pub const ALL: [DeathCause; 5] = [
+        DeathCause::Starvation,
+        DeathCause::Exhaustion,
+        DeathCause::OldAge,
+        DeathCause::Disease,
+        DeathCause::Combat,
+    ];
+
+    /// Returns a short human-readable name for this cause.
+    ///
+    /// ### Returns
+    /// A static label such as `"old age"`.
+    pub fn label(&self) -> &'static str {
+        match self {
+            DeathCause::Starvation => "starvation",
+            DeathCause::Exhaustion => "exhaustion",
+            DeathCause::OldAge => "old age",
+            DeathCause::Disease => "disease",
+            DeathCause::Combat => "combat",
+        }
+    }
+
+    /// Returns the position of this cause in [`DeathCause::ALL`].
+    fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+/// Aging settings shared by all agents of a world.
+///
+/// When `enabled`, each agent draws a maximum lifespan uniformly from
+/// `[min_lifespan, max_lifespan]` and dies of old age when it reaches it.
+/// Children inherit their parent's lifespan, so long-lived lineages stay
+/// long-lived. For each full quarter of its lifespan an agent has lived,
+/// it needs `senescence` more resource per step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AgingConfig {
+    pub enabled: bool,
+    pub min_lifespan: u64,
+    pub max_lifespan: u64,
+    pub senescence: u32,
+}
+
+impl AgingConfig {
+    /// Returns whether the settings are usable.
+    ///
+    /// ### Returns
+    /// `true` if `0 < min_lifespan <= max_lifespan`.
+    pub fn is_valid(&self) -> bool {
+        self.min_lifespan > 0 && self.min_lifespan <= self.max_lifespan
+    }
+}
+
+impl Default for AgingConfig {
+    /// Returns a disabled configuration with lifespans of 100 to 200 ticks.
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_lifespan: 100,
+            max_lifespan: 200,
+            senescence: 1,
+        }
+    }
+}
+
+/// Something notable that happened during a tick.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorldEvent {
+    /// An agent died on a cell.
+    Death {
+        tick: u64,
+        agent: usize,
+        cid: usize,
+        age: u64,
+        cause: DeathCause,
+    },
+    /// A susceptible agent caught the disease on a cell.
+    Infection { tick: u64, agent: usize, cid: usize },
+    /// A parent gave birth to a child on a cell.
+    Birth {
+        tick: u64,
+        parent: usize,
+        child: usize,
+        cid: usize,
+    },
+    /// Two agents fought over a contested cell.
+    Fight {
+        tick: u64,
+        cid: usize,
+        winner: usize,
+        loser: usize,
+    },
+    /// An agent gave energy to a hungry member of its group.
+    Share {
+        tick: u64,
+        donor: usize,
+        recipient: usize,
+        amount: u32,
+    },
+    /// An agent took ownership of a cell.
+    Claim { tick: u64, agent: usize, cid: usize },
+}
+
+/// Running totals over the whole lifetime of a world.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Statistics {
+    pub births: u64,
+    pub infections: u64,
+    pub claims: u64,
+    pub fights: u64,
+    pub shared: u64,
+    deaths: [u64; DeathCause::ALL.len()],
+    total_age_at_death: u64,
+}
+
+impl Statistics {
+    /// Adds an event to the totals.
+    ///
+    /// ### Parameters
+    /// - `event`: The event to count.
+    pub fn record(&mut self, event: &WorldEvent) {
+        match *event {
+            WorldEvent::Death { age, cause, .. } => {
+                self.deaths[cause.index()] += 1;
+                self.total_age_at_death += age;
+            }
+            WorldEvent::Infection { .. } => self.infections += 1,
+            WorldEvent::Birth { .. } => self.births += 1,
+            WorldEvent::Claim { .. } => self.claims += 1,
+            WorldEvent::Fight { .. } => self.fights += 1,
+            WorldEvent::Share { amount, .. } => self.shared += u64::from(amount),
+        }
+    }
+
+    /// Returns the number of deaths with a given cause.
+    ///
+    /// ### Parameters
+    /// - `cause`: The [`DeathCause`] to count.
+    ///
+    /// ### Returns
+    /// The number of agents that died of `cause`.
+    pub fn deaths(&self, cause: DeathCause) -> u64 {
+        self.deaths[cause.index()]
+    }
+
+    /// Returns the number of deaths of any cause.
+    ///
+    /// ### Returns
+    /// The sum over all causes.
+    pub fn total_deaths(&self) -> u64 {
+        self.deaths.iter().sum()
+    }
+
+    /// Returns the average age of the agents that died.
+    ///
+    /// ### Returns
+    /// - `Some(age)` if at least one agent died.
+    /// - `None` otherwise.
+    pub fn mean_age_at_death(&self) -> Option<f64> {
+        let deaths = self.total_deaths();
+        (deaths > 0).then(|| self.total_age_at_death as f64 / deaths as f64)
+    }
+}
+
+/// What remains of an agent after [`crate::World::compact`] removed it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraveRecord {
+    pub id: usize,
+    pub cid: usize,
+    pub age: u64,
+    pub offspring: u32,
+    pub donated: u64,
+    pub group: Option<usize>,
+    pub cause: Option<DeathCause>,
+    pub death_tick: Option<u64>,
+    pub genome: Option<Genome>,
+}
+
+impl From<Agent> for GraveRecord {
+    /// Archives a dead agent, keeping its genome.
+    fn from(agent: Agent) -> Self {
+        Self {
+            id: agent.id(),
+            cid: agent.cid(),
+            age: agent.age(),
+            offspring: agent.offspring(),
+            donated: agent.donated(),
+            group: agent.group(),
+            cause: agent.death_cause(),
+            death_tick: agent.death_tick(),
+            genome: agent.into_genome(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statistics_count_events_by_cause() {
+        let mut stats = Statistics::default();
+        let death = |age, cause| WorldEvent::Death {
+            tick: 0,
+            agent: 0,
+            cid: 0,
+            age,
+            cause,
+        };
+        stats.record(&death(10, DeathCause::OldAge));
+        stats.record(&death(4, DeathCause::Starvation));
+        stats.record(&WorldEvent::Infection {
+            tick: 0,
+            agent: 1,
+            cid: 0,
+        });
+        stats.record(&WorldEvent::Birth {
+            tick: 0,
+            parent: 0,
+            child: 1,
+            cid: 0,
+        });
+
+        assert_eq!(stats.deaths(DeathCause::OldAge), 1);
+        assert_eq!(stats.deaths(DeathCause::Exhaustion), 0);
+        assert_eq!(stats.total_deaths(), 2);
+        assert_eq!(stats.births, 1);
+        assert_eq!(stats.infections, 1);
+        assert_eq!(stats.mean_age_at_death(), Some(7.0));
+        assert_eq!(Statistics::default().mean_age_at_death(), None);
+    }
+
+    #[test]
+    fn aging_config_validation() {
+        assert!(AgingConfig::default().is_valid());
+        let bad = AgingConfig {
+            min_lifespan: 0,
+            ..AgingConfig::default()
+        };
+        assert!(!bad.is_valid());
+    }
+}
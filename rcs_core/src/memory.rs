@@ -0,0 +1,263 @@
+use std::collections::VecDeque;
+
+use crate::policy::MovementPolicy;
+use crate::{Agent, World};
+
+/// One remembered observation of a cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryEntry {
+    pub cid: usize,
+    pub resource: u32,
+    pub tick: u64,
+}
+
+/// A bounded memory of recently visited cells and the resource seen there.
+///
+/// Each cell appears at most once; observing a cell again replaces the old
+/// entry and makes it the most recent one. When the memory is full, the
+/// oldest entry is forgotten.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgentMemory {
+    capacity: usize,
+    entries: VecDeque<MemoryEntry>,
+}
+
+impl AgentMemory {
+    /// Creates an empty memory.
+    ///
+    /// ### Parameters
+    /// - `capacity`: Maximum number of cells remembered.
+    ///
+    /// ### Returns
+    /// A new, empty [`AgentMemory`].
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the maximum number of cells remembered.
+    ///
+    /// ### Returns
+    /// The capacity given to [`AgentMemory::new`].
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Records an observation of a cell.
+    ///
+    /// ### Parameters
+    /// - `cid`: Id of the observed cell.
+    /// - `resource`: Resource seen on the cell.
+    /// - `tick`: Tick of the observation.
+    pub fn record(&mut self, cid: usize, resource: u32, tick: u64) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.entries.retain(|e| e.cid != cid);
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(MemoryEntry {
+            cid,
+            resource,
+            tick,
+        });
+    }
+
+    /// Returns all remembered observations, oldest first.
+    ///
+    /// ### Returns
+    /// An iterator over the stored [`MemoryEntry`] values.
+    pub fn entries(&self) -> impl DoubleEndedIterator<Item = &MemoryEntry> {
+        self.entries.iter()
+    }
+
+    /// Returns the latest observation of a cell.
+    ///
+    /// ### Parameters
+    /// - `cid`: Id of the cell to look up.
+    ///
+    /// ### Returns
+    /// - `Some(&MemoryEntry)` if the cell is remembered.
+    /// - `None` otherwise.
+    pub fn recall(&self, cid: usize) -> Option<&MemoryEntry> {
+        self.entries.iter().find(|e| e.cid == cid)
+    }
+
+    /// Returns how recently a cell was observed.
+    ///
+    /// ### Parameters
+    /// - `cid`: Id of the cell to look up.
+    ///
+    /// ### Returns
+    /// - `Some(rank)` where a higher rank means a more recent observation.
+    /// - `None` if the cell is not remembered.
+    pub fn recency(&self, cid: usize) -> Option<usize> {
+        self.entries.iter().position(|e| e.cid == cid)
+    }
+
+    /// Returns whether a cell is among the `n` most recently visited ones.
+    ///
+    /// ### Parameters
+    /// - `cid`: Id of the cell to look up.
+    /// - `n`: How many of the most recent entries to consider.
+    ///
+    /// ### Returns
+    /// `true` if the cell was visited recently.
+    pub fn visited_recently(&self, cid: usize, n: usize) -> bool {
+        self.entries.iter().rev().take(n).any(|e| e.cid == cid)
+    }
+
+    /// Returns the richest remembered cell.
+    ///
+    /// Ties are broken in favour of the more recent observation.
+    ///
+    /// ### Parameters
+    /// - `exclude`: A cell to ignore, usually the current one.
+    ///
+    /// ### Returns
+    /// - `Some(&MemoryEntry)` for the best known patch with resource.
+    /// - `None` if no other remembered cell had resource.
+    pub fn best_known(&self, exclude: usize) -> Option<&MemoryEntry> {
+        self.entries
+            .iter()
+            .filter(|e| e.cid != exclude && e.resource > 0)
+            .max_by_key(|e| (e.resource, e.tick))
+    }
+}
+
+/// Uses the agent's memory to avoid backtracking and to return to known
+/// good patches.
+///
+/// A hungry agent:
+/// 1. moves to the richest neighbor it has not visited among its last
+///    `avoid_recent` cells
+/// 2. otherwise heads toward the richest remembered cell
+/// 3. otherwise explores the neighbor it visited least recently
+///
+/// Agents without memory (see [`Agent::enable_memory`]) treat every cell as
+/// unvisited. Fed agents stay.
+#[derive(Debug, Clone, Copy)]
+pub struct MemoryPolicy {
+    pub avoid_recent: usize,
+}
+
+impl Default for MemoryPolicy {
+    /// Returns a memory policy that avoids the last 3 visited cells.
+    fn default() -> Self {
+        Self { avoid_recent: 3 }
+    }
+}
+
+impl MovementPolicy for MemoryPolicy {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+
+    fn decide(&self, world: &World, agent: &Agent) -> Option<usize> {
+        if !agent.is_hungry() {
+            return None;
+        }
+        let cid = agent.cid();
+        let recent = |nid: usize| {
+            agent
+                .memory()
+                .is_some_and(|m| m.visited_recently(nid, self.avoid_recent))
+        };
+
+        let fresh: Vec<(usize, u32)> = world
+            .neighbor_cells_info(cid)
+            .into_iter()
+            .filter(|&(nid, _)| !recent(nid))
+            .collect();
+
+        if let Some(target) = agent.decide_move(&fresh) {
+            return Some(target);
+        }
+        if let Some(patch) = agent.memory().and_then(|m| m.best_known(cid)) {
+            return world.step_toward(cid, patch.cid);
+        }
+        world
+            .neighbor_cells_info(cid)
+            .into_iter()
+            .map(|(nid, _)| nid)
+            .min_by_key(|&nid| agent.memory().and_then(|m| m.recency(nid)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cell, Updatable};
+
+    #[test]
+    fn record_is_bounded_and_deduplicated() {
+        let mut memory = AgentMemory::new(3);
+        memory.record(1, 5, 0);
+        memory.record(2, 0, 1);
+        memory.record(1, 2, 2);
+        memory.record(3, 7, 3);
+        memory.record(4, 1, 4);
+
+        let cids: Vec<usize> = memory.entries().map(|e| e.cid).collect();
+        assert_eq!(cids, vec![1, 3, 4]);
+        assert_eq!(memory.recall(1).unwrap().resource, 2);
+        assert!(memory.recall(2).is_none());
+        assert!(memory.visited_recently(4, 1));
+        assert!(!memory.visited_recently(1, 2));
+        assert_eq!(memory.recency(4), Some(2));
+        assert_eq!(memory.recency(9), None);
+    }
+
+    #[test]
+    fn best_known_ignores_excluded_and_empty_cells() {
+        let mut memory = AgentMemory::new(4);
+        memory.record(1, 9, 0);
+        memory.record(2, 0, 1);
+        memory.record(3, 4, 2);
+        assert_eq!(memory.best_known(0).unwrap().cid, 1);
+        assert_eq!(memory.best_known(1).unwrap().cid, 3);
+
+        let empty = AgentMemory::new(0);
+        assert!(empty.best_known(0).is_none());
+    }
+
+    #[test]
+    fn memory_policy_sweeps_an_empty_strip_toward_unvisited_cells() {
+        // 4×1 strip; every cell is empty, so the greedy rule would bounce
+        // between two cells. From the dead end at cell 0 the agent has to
+        // step back to 1, but then it heads on to 2 instead of returning.
+        let cells = (0..4).map(|id| Cell::new(id, 0, 100, 0, 5)).collect();
+        let mut agent = Agent::new(0, 1, 1, 0, 50, true);
+        agent.enable_memory(4);
+        let mut world = World::new((4, 1), cells, vec![agent]);
+        world.set_policy(Box::new(MemoryPolicy::default()));
+
+        let mut path = vec![1];
+        for _ in 0..3 {
+            world.update().unwrap();
+            path.push(world.agents()[0].cid());
+        }
+        assert_eq!(path, vec![1, 0, 1, 2]);
+        // From cell 2 the only unvisited neighbor left is 3.
+        world.update().unwrap();
+        assert_eq!(world.agents()[0].cid(), 3);
+    }
+
+    #[test]
+    fn memory_policy_returns_to_known_good_patch() {
+        let cells = (0..5).map(|id| Cell::new(id, 0, 100, 0, 5)).collect();
+        let world = World::new((5, 1), cells, Vec::new());
+
+        let mut agent = Agent::new(0, 3, 1, 0, 50, true);
+        agent.enable_memory(5);
+        agent.remember(0, 20, 0);
+        agent.remember(2, 0, 1);
+        agent.remember(4, 0, 2);
+        agent.remember(3, 0, 3);
+
+        assert_eq!(MemoryPolicy::default().decide(&world, &agent), Some(2));
+    }
+}
@@ -0,0 +1,390 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::sync::Mutex;
+
+use crate::perception;
+use crate::policy::{MoveOutcome, MovementPolicy};
+use crate::{Agent, World};
+
+/// A route through the grid.
+///
+/// `cells` lists the cells to enter in order, excluding the start cell and
+/// ending with the goal. `cost` is the total terrain cost of entering them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Path {
+    pub cells: Vec<usize>,
+    pub cost: u32,
+}
+
+/// Returns whether a path may pass through a cell.
+///
+/// Cells that are full (see [`World::is_cell_full`]) are impassable.
+fn passable(world: &World, cid: usize) -> bool {
+    !world.is_cell_full(cid)
+}
+
+/// Walks the predecessor map back from `goal` to build a [`Path`].
+fn reconstruct(prev: &HashMap<usize, usize>, start: usize, goal: usize, cost: u32) -> Path {
+    let mut cells = vec![goal];
+    let mut cur = goal;
+    while let Some(&p) = prev.get(&cur) {
+        if p == start {
+            break;
+        }
+        cells.push(p);
+        cur = p;
+    }
+    cells.reverse();
+    Path { cells, cost }
+}
+
+/// Finds the cheapest path between two cells with A*.
+///
+/// Entering a cell costs its [`crate::Cell::terrain_cost`]; full cells are
+/// avoided. Since every terrain cost is at least `1`, the Manhattan
+/// distance is an admissible heuristic.
+///
+/// ### Parameters
+/// - `world`: The world whose grid is searched.
+/// - `from`: Id of the start cell.
+/// - `to`: Id of the goal cell.
+/// - `max_expansions`: Upper bound on the number of cells expanded, i.e.
+///   the planning budget. The search gives up when it is exhausted.
+///
+/// ### Returns
+/// - `Some(Path)` with the cheapest route; empty if `from == to`.
+/// - `None` if the goal is unreachable or the budget ran out.
+pub fn shortest_path(world: &World, from: usize, to: usize, max_expansions: usize) -> Option<Path> {
+    let num_cells = world.cells().len();
+    if from >= num_cells || to >= num_cells {
+        return None;
+    }
+    if from == to {
+        return Some(Path::default());
+    }
+    if !passable(world, to) {
+        return None;
+    }
+
+    let width = world.size().0;
+    let mut open = BinaryHeap::new();
+    let mut best: HashMap<usize, u32> = HashMap::new();
+    let mut prev: HashMap<usize, usize> = HashMap::new();
+    let mut expansions = 0;
+
+    best.insert(from, 0);
+    open.push(Reverse((
+        perception::manhattan(from, to, width),
+        0u32,
+        from,
+    )));
+
+    while let Some(Reverse((_, cost, cid))) = open.pop() {
+        if cid == to {
+            return Some(reconstruct(&prev, from, to, cost));
+        }
+        if best.get(&cid).is_some_and(|&b| cost > b) {
+            continue;
+        }
+        expansions += 1;
+        if expansions > max_expansions {
+            return None;
+        }
+
+        for (nid, _) in world.neighbor_cells_info(cid) {
+            if !passable(world, nid) {
+                continue;
+            }
+            let next = cost.saturating_add(world.cell(nid).terrain_cost());
+            if best.get(&nid).is_none_or(|&b| next < b) {
+                best.insert(nid, next);
+                prev.insert(nid, cid);
+                let estimate = next as usize + perception::manhattan(nid, to, width);
+                open.push(Reverse((estimate, next, nid)));
+            }
+        }
+    }
+    None
+}
+
+/// Computes the cheapest cost to every cell reachable within a cost budget
+/// with Dijkstra's algorithm.
+///
+/// Entering a cell costs its [`crate::Cell::terrain_cost`]; full cells are
+/// not entered.
+///
+/// ### Parameters
+/// - `world`: The world whose grid is searched.
+/// - `from`: Id of the start cell.
+/// - `max_cost`: Cells that cost more than this to reach are ignored.
+///
+/// ### Returns
+/// A map from cell id to its cheapest cost, including `from` at cost `0`.
+pub fn reachable_costs(world: &World, from: usize, max_cost: u32) -> HashMap<usize, u32> {
+    let mut best: HashMap<usize, u32> = HashMap::new();
+    if from >= world.cells().len() {
+        return best;
+    }
+    let mut open = BinaryHeap::new();
+    best.insert(from, 0);
+    open.push(Reverse((0u32, from)));
+
+    while let Some(Reverse((cost, cid))) = open.pop() {
+        if best.get(&cid).is_some_and(|&b| cost > b) {
+            continue;
+        }
+        for (nid, _) in world.neighbor_cells_info(cid) {
+            if !passable(world, nid) {
+                continue;
+            }
+            let next = cost.saturating_add(world.cell(nid).terrain_cost());
+            if next <= max_cost && best.get(&nid).is_none_or(|&b| next < b) {
+                best.insert(nid, next);
+                open.push(Reverse((next, nid)));
+            }
+        }
+    }
+    best
+}
+
+/// A route an agent is currently following.
+#[derive(Debug, Clone)]
+struct Plan {
+    target: usize,
+    steps: VecDeque<usize>,
+}
+
+/// Plans routes to rich cells and follows them.
+///
+/// A hungry agent picks the richest cell within the world's perception
+/// radius (see [`World::perceive`]) and plans the cheapest route to it with
+/// [`shortest_path`], expanding at most `budget` cells. The route is cached
+/// per agent and followed one step per tick, so agents commit to a target
+/// instead of oscillating between equal neighbors.
+///
+/// The agent replans when:
+/// - the target cell has been depleted
+/// - a step was blocked by a full cell
+/// - the next step is no longer adjacent (e.g. the agent was displaced)
+///
+/// Fed agents stay and drop their plan.
+#[derive(Debug)]
+pub struct ForagingPolicy {
+    budget: usize,
+    plans: Mutex<HashMap<usize, Plan>>,
+}
+
+impl ForagingPolicy {
+    /// Creates a foraging policy with a planning budget.
+    ///
+    /// ### Parameters
+    /// - `budget`: Maximum number of cells expanded per planning attempt.
+    ///
+    /// ### Returns
+    /// A new [`ForagingPolicy`] without cached plans.
+    pub fn new(budget: usize) -> Self {
+        Self {
+            budget,
+            plans: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the remaining cached route of an agent.
+    ///
+    /// ### Parameters
+    /// - `agent_id`: The [`Agent::id`] to look up.
+    ///
+    /// ### Returns
+    /// - `Some((target, steps))` if the agent follows a plan.
+    /// - `None` otherwise.
+    pub fn plan_of(&self, agent_id: usize) -> Option<(usize, Vec<usize>)> {
+        let plans = self.plans.lock().unwrap_or_else(|e| e.into_inner());
+        plans
+            .get(&agent_id)
+            .map(|p| (p.target, p.steps.iter().copied().collect()))
+    }
+
+    /// Plans a new route for an agent standing on `cid`.
+    ///
+    /// Visible cells with resource are tried from richest to poorest (closer
+    /// first on ties) until one of them can be reached within the budget.
+    fn plan(&self, world: &World, cid: usize) -> Option<Plan> {
+        let view = world.perceive(cid);
+        let mut targets: Vec<_> = view
+            .cells
+            .iter()
+            .filter(|c| c.resource > 0 && c.cid != cid)
+            .collect();
+        targets.sort_by_key(|c| (Reverse(c.resource), c.distance, c.cid));
+
+        targets.into_iter().find_map(|target| {
+            let path = shortest_path(world, cid, target.cid, self.budget)?;
+            Some(Plan {
+                target: target.cid,
+                steps: path.cells.into(),
+            })
+        })
+    }
+}
+
+impl Default for ForagingPolicy {
+    /// Returns a foraging policy with a budget of 256 expanded cells.
+    fn default() -> Self {
+        Self::new(256)
+    }
+}
+
+impl MovementPolicy for ForagingPolicy {
+    fn name(&self) -> &'static str {
+        "forager"
+    }
+
+    fn decide(&self, world: &World, agent: &Agent) -> Option<usize> {
+        let mut plans = self.plans.lock().unwrap_or_else(|e| e.into_inner());
+        if !agent.is_hungry() {
+            plans.remove(&agent.id());
+            return None;
+        }
+
+        let cid = agent.cid();
+        let still_valid = plans.get(&agent.id()).is_some_and(|plan| {
+            world.cell(plan.target).cur_resource() > 0
+                && plan.steps.front().is_some_and(|&next| {
+                    perception::manhattan(cid, next, world.size().0) == 1
+                        && !world.is_cell_full(next)
+                })
+        });
+        if !still_valid {
+            match self.plan(world, cid) {
+                Some(plan) => {
+                    plans.insert(agent.id(), plan);
+                }
+                None => {
+                    plans.remove(&agent.id());
+                    return None;
+                }
+            }
+        }
+        plans
+            .get(&agent.id())
+            .and_then(|p| p.steps.front().copied())
+    }
+
+    fn on_outcome(&mut self, agent: &Agent, outcome: MoveOutcome) {
+        let plans = self.plans.get_mut().unwrap_or_else(|e| e.into_inner());
+        if !agent.is_alive() {
+            plans.remove(&agent.id());
+            return;
+        }
+        match outcome {
+            MoveOutcome::Moved { to, .. } => {
+                if let Some(plan) = plans.get_mut(&agent.id()) {
+                    if plan.steps.front() == Some(&to) {
+                        plan.steps.pop_front();
+                    }
+                    if plan.steps.is_empty() {
+                        plans.remove(&agent.id());
+                    }
+                }
+            }
+            MoveOutcome::Blocked { .. } => {
+                plans.remove(&agent.id());
+            }
+            MoveOutcome::Stayed => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cell, PerceptionConfig, Updatable};
+
+    /// 5×5 grid with a wall of expensive terrain in column 2, except for a
+    /// cheap gap in the bottom row. `rich` lists `(cell_id, resource)`.
+    fn walled_world(rich: &[(usize, u32)], agents: Vec<Agent>) -> World {
+        let mut cells: Vec<Cell> = (0..25).map(|id| Cell::new(id, 0, 100, 0, 5)).collect();
+        for y in 0..4 {
+            cells[y * 5 + 2].set_terrain_cost(10);
+        }
+        for &(cid, resource) in rich {
+            cells[cid].add_resource(resource);
+        }
+        let mut world = World::new((5, 5), cells, agents);
+        world.set_perception(PerceptionConfig {
+            radius: 8,
+            ..PerceptionConfig::default()
+        });
+        world
+    }
+
+    #[test]
+    fn shortest_path_goes_around_expensive_terrain() {
+        let world = walled_world(&[], Vec::new());
+        // From (0,0) to (4,0): straight through the wall costs 13,
+        // around through the gap in row 4 costs 12.
+        let path = shortest_path(&world, 0, 4, 100).unwrap();
+        assert_eq!(path.cost, 12);
+        assert_eq!(path.cells.last(), Some(&4));
+        assert!(path.cells.contains(&22));
+        assert_eq!(path.cells.len(), 12);
+    }
+
+    #[test]
+    fn shortest_path_respects_budget_and_full_cells() {
+        let world = walled_world(&[], Vec::new());
+        assert!(shortest_path(&world, 0, 4, 3).is_none());
+        assert_eq!(shortest_path(&world, 7, 7, 0), Some(Path::default()));
+
+        let cells = (0..3).map(|id| Cell::new(id, 0, 100, 0, 5)).collect();
+        let agents = vec![Agent::new(0, 1, 1, 0, 5, true)];
+        let mut world = World::new((3, 1), cells, agents);
+        world.set_cell_capacity(Some(1));
+        assert!(shortest_path(&world, 0, 2, 100).is_none());
+    }
+
+    #[test]
+    fn reachable_costs_stop_at_budget() {
+        let world = walled_world(&[], Vec::new());
+        let costs = reachable_costs(&world, 0, 2);
+        assert_eq!(costs.get(&0), Some(&0));
+        assert_eq!(costs.get(&1), Some(&1));
+        assert_eq!(costs.get(&10), Some(&2));
+        assert!(!costs.contains_key(&2));
+    }
+
+    #[test]
+    fn forager_commits_to_its_plan_and_reaches_the_target() {
+        let agents = vec![Agent::new(0, 0, 1, 0, 100, true)];
+        let mut world = walled_world(&[(4, 90)], agents);
+        world.set_policy(Box::new(ForagingPolicy::new(100)));
+
+        let mut visited = vec![world.agents()[0].cid()];
+        for _ in 0..12 {
+            world.update().unwrap();
+            visited.push(world.agents()[0].cid());
+        }
+        assert_eq!(world.agents()[0].cid(), 4);
+        // Never re-enters a cell it has left (no oscillation).
+        let mut unique = visited.clone();
+        unique.sort_unstable();
+        unique.dedup();
+        assert_eq!(unique.len(), visited.len());
+    }
+
+    #[test]
+    fn forager_replans_when_target_is_depleted() {
+        let agent = Agent::new(0, 0, 1, 0, 100, true);
+        let policy = ForagingPolicy::new(100);
+
+        let world = walled_world(&[(4, 50), (20, 40)], Vec::new());
+        let step = policy.decide(&world, &agent);
+        let (target, steps) = policy.plan_of(0).unwrap();
+        assert_eq!(target, 4);
+        assert_eq!(step, steps.first().copied());
+
+        let depleted = walled_world(&[(20, 40)], Vec::new());
+        policy.decide(&depleted, &agent);
+        assert_eq!(policy.plan_of(0).unwrap().0, 20);
+    }
+}
@@ -0,0 +1,225 @@
+use crate::policy::MovementPolicy;
+use crate::{Agent, World};
+
+/// A cell seen by an agent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VisibleCell {
+    pub cid: usize,
+    pub distance: usize,
+    pub resource: u32,
+}
+
+/// Another living agent seen by an agent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VisibleAgent {
+    pub id: usize,
+    pub cid: usize,
+    pub distance: usize,
+    pub health_point: u32,
+}
+
+/// A read-only snapshot of everything within an agent's perception radius.
+///
+/// Distances are Manhattan distances on the grid, i.e. the number of
+/// single-cell moves needed to get there. The origin cell itself is
+/// included at distance `0`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Perception {
+    pub origin: usize,
+    pub radius: usize,
+    pub cells: Vec<VisibleCell>,
+    pub agents: Vec<VisibleAgent>,
+}
+
+impl Perception {
+    /// Returns the richest visible cell.
+    ///
+    /// Ties are broken by the shorter distance, then by the lower cell id.
+    ///
+    /// ### Returns
+    /// - `Some(&VisibleCell)` with the best cell.
+    /// - `None` if no cell is visible.
+    pub fn best_cell(&self) -> Option<&VisibleCell> {
+        self.cells.iter().min_by(|a, b| {
+            b.resource
+                .cmp(&a.resource)
+                .then(a.distance.cmp(&b.distance))
+                .then(a.cid.cmp(&b.cid))
+        })
+    }
+}
+
+/// Perception settings shared by all agents of a world.
+///
+/// - `radius`: how far (in moves) agents can see; `1` matches the
+///   four-neighbor view of the greedy policy
+/// - `see_agents`: whether other living agents are included in the view
+/// - `cost_per_radius`: extra resource an agent needs per step for every
+///   cell of radius beyond `1`; `0` makes perception free
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PerceptionConfig {
+    pub radius: usize,
+    pub see_agents: bool,
+    pub cost_per_radius: u32,
+}
+
+impl PerceptionConfig {
+    /// Returns the per-step upkeep caused by this perception radius.
+    ///
+    /// ### Returns
+    /// `cost_per_radius * (radius - 1)`, saturating.
+    pub fn upkeep(&self) -> u32 {
+        let extra = u32::try_from(self.radius.saturating_sub(1)).unwrap_or(u32::MAX);
+        self.cost_per_radius.saturating_mul(extra)
+    }
+}
+
+impl Default for PerceptionConfig {
+    /// Returns the classic four-neighbor view without upkeep.
+    fn default() -> Self {
+        Self {
+            radius: 1,
+            see_agents: false,
+            cost_per_radius: 0,
+        }
+    }
+}
+
+/// Converts a cell id into `(x, y)` grid coordinates.
+///
+/// ### Parameters
+/// - `cid`: Cell id in row-major indexing.
+/// - `width`: Width of the grid.
+///
+/// ### Returns
+/// The `(x, y)` coordinates of the cell.
+pub fn coords(cid: usize, width: usize) -> (usize, usize) {
+    (cid % width, cid / width)
+}
+
+/// Returns the Manhattan distance between two cells.
+///
+/// ### Parameters
+/// - `a`, `b`: Cell ids in row-major indexing.
+/// - `width`: Width of the grid.
+///
+/// ### Returns
+/// The number of single-cell moves between `a` and `b`.
+pub fn manhattan(a: usize, b: usize, width: usize) -> usize {
+    let (ax, ay) = coords(a, width);
+    let (bx, by) = coords(b, width);
+    ax.abs_diff(bx) + ay.abs_diff(by)
+}
+
+/// Heads toward the richest visible cell, one step at a time.
+///
+/// A hungry agent looks at every cell within the world's perception radius
+/// (see [`World::perceive`]) and picks the richest one. If that cell is its
+/// own cell, or holds no resource, it stays. Otherwise it steps to the
+/// neighbor that brings it closer to the target, preferring the richer
+/// neighbor when two of them do. Fed agents stay.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VisionPolicy;
+
+impl MovementPolicy for VisionPolicy {
+    fn name(&self) -> &'static str {
+        "vision"
+    }
+
+    fn decide(&self, world: &World, agent: &Agent) -> Option<usize> {
+        if !agent.is_hungry() {
+            return None;
+        }
+        let view = world.perceive(agent.cid());
+        let target = view.best_cell()?;
+        if target.resource == 0 || target.cid == agent.cid() {
+            return None;
+        }
+        world.step_toward(agent.cid(), target.cid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cell, Updatable};
+
+    fn strip(resources: &[u32]) -> Vec<Cell> {
+        resources
+            .iter()
+            .enumerate()
+            .map(|(id, &r)| Cell::new(id, r, 100, 0, 5))
+            .collect()
+    }
+
+    #[test]
+    fn manhattan_counts_moves() {
+        assert_eq!(manhattan(0, 8, 3), 4);
+        assert_eq!(manhattan(4, 4, 3), 0);
+        assert_eq!(coords(5, 3), (2, 1));
+    }
+
+    #[test]
+    fn upkeep_grows_with_radius() {
+        let cfg = PerceptionConfig {
+            radius: 4,
+            see_agents: false,
+            cost_per_radius: 2,
+        };
+        assert_eq!(cfg.upkeep(), 6);
+        assert_eq!(PerceptionConfig::default().upkeep(), 0);
+    }
+
+    #[test]
+    fn best_cell_prefers_resource_then_distance() {
+        let view = Perception {
+            origin: 0,
+            radius: 2,
+            cells: vec![
+                VisibleCell {
+                    cid: 3,
+                    distance: 2,
+                    resource: 9,
+                },
+                VisibleCell {
+                    cid: 1,
+                    distance: 1,
+                    resource: 9,
+                },
+                VisibleCell {
+                    cid: 2,
+                    distance: 1,
+                    resource: 4,
+                },
+            ],
+            agents: Vec::new(),
+        };
+        assert_eq!(view.best_cell().unwrap().cid, 1);
+    }
+
+    #[test]
+    fn vision_policy_walks_to_distant_riches() {
+        let cells = strip(&[0, 0, 0, 0, 30]);
+        let agents = vec![Agent::new(0, 0, 2, 0, 10, true)];
+        let mut world = World::new((5, 1), cells, agents);
+        world.set_perception(PerceptionConfig {
+            radius: 4,
+            ..PerceptionConfig::default()
+        });
+        world.set_policy(Box::new(VisionPolicy));
+
+        for _ in 0..4 {
+            world.update().unwrap();
+        }
+        assert_eq!(world.agents()[0].cid(), 4);
+        assert!(world.agents()[0].is_alive());
+    }
+
+    #[test]
+    fn vision_policy_stays_when_nothing_is_visible() {
+        let cells = strip(&[0, 0, 0, 0, 30]);
+        let world = World::new((5, 1), cells, Vec::new());
+        let agent = Agent::new(0, 0, 2, 0, 10, true);
+        assert_eq!(VisionPolicy.decide(&world, &agent), None);
+    }
+}
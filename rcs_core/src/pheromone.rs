@@ -0,0 +1,286 @@
+use crate::policy::MovementPolicy;
+use crate::{Agent, World};
+
+/// Pheromone settings shared by all agents of a world.
+///
+/// The layer is optional: unless `enabled`, agents leave no trails and the
+/// field is neither diffused nor evaporated.
+///
+/// - `deposit`: amount an agent leaves on its cell in a tick in which it
+///   found food
+/// - `evaporation`: fraction of the pheromone on each cell lost per tick
+/// - `diffusion`: fraction of the pheromone on each cell spread evenly to
+///   its four-neighbors per tick
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PheromoneConfig {
+    pub enabled: bool,
+    pub deposit: f32,
+    pub evaporation: f32,
+    pub diffusion: f32,
+}
+
+impl PheromoneConfig {
+    /// Returns whether the settings are usable.
+    ///
+    /// ### Returns
+    /// `true` if `deposit` is finite and non-negative and both rates lie
+    /// in `[0, 1]`.
+    pub fn is_valid(&self) -> bool {
+        self.deposit.is_finite()
+            && self.deposit >= 0.0
+            && (0.0..=1.0).contains(&self.evaporation)
+            && (0.0..=1.0).contains(&self.diffusion)
+    }
+}
+
+impl Default for PheromoneConfig {
+    /// Returns a disabled layer with unit deposits that fades over a few
+    /// dozen ticks.
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            deposit: 1.0,
+            evaporation: 0.05,
+            diffusion: 0.1,
+        }
+    }
+}
+
+/// A per-cell pheromone concentration over the grid.
+///
+/// The field is stored in row-major order, like [`World::cells`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PheromoneField {
+    width: usize,
+    height: usize,
+    values: Vec<f32>,
+}
+
+impl PheromoneField {
+    /// Creates an empty field.
+    ///
+    /// ### Parameters
+    /// - `size`: `(width, height)` of the grid.
+    ///
+    /// ### Returns
+    /// A [`PheromoneField`] with zero concentration everywhere.
+    pub fn new(size: (usize, usize)) -> Self {
+        let (width, height) = size;
+        Self {
+            width,
+            height,
+            values: vec![0.0; width * height],
+        }
+    }
+
+    /// Returns the concentration on a cell.
+    ///
+    /// ### Parameters
+    /// - `cid`: Cell id in row-major indexing.
+    ///
+    /// ### Returns
+    /// The pheromone on `cid`, or `0.0` for ids outside the grid.
+    pub fn get(&self, cid: usize) -> f32 {
+        self.values.get(cid).copied().unwrap_or(0.0)
+    }
+
+    /// Returns the concentrations of all cells.
+    ///
+    /// ### Returns
+    /// A slice in row-major order.
+    pub fn values(&self) -> &[f32] {
+        &self.values
+    }
+
+    /// Returns the highest concentration on the grid.
+    ///
+    /// ### Returns
+    /// The maximum value, or `0.0` for an empty field.
+    pub fn max(&self) -> f32 {
+        self.values.iter().copied().fold(0.0, f32::max)
+    }
+
+    /// Adds pheromone to a cell.
+    ///
+    /// Ids outside the grid are ignored.
+    ///
+    /// ### Parameters
+    /// - `cid`: Cell id in row-major indexing.
+    /// - `amount`: Pheromone to add.
+    pub fn deposit(&mut self, cid: usize, amount: f32) {
+        if let Some(value) = self.values.get_mut(cid) {
+            *value += amount;
+        }
+    }
+
+    /// Advances the field by one tick: diffusion, then evaporation.
+    ///
+    /// Each cell hands `diffusion` of its pheromone to its four-neighbors
+    /// in equal shares, so diffusion alone conserves the total. Then every
+    /// cell loses `evaporation` of what it holds.
+    ///
+    /// ### Parameters
+    /// - `cfg`: Rates to apply.
+    pub fn step(&mut self, cfg: &PheromoneConfig) {
+        let (width, height) = (self.width, self.height);
+        let mut next = self.values.clone();
+
+        if cfg.diffusion > 0.0 {
+            for (cid, &value) in self.values.iter().enumerate() {
+                if value == 0.0 {
+                    continue;
+                }
+                let (x, y) = (cid % width, cid / width);
+                let mut neighbors = [0usize; 4];
+                let mut n = 0;
+                if y > 0 {
+                    neighbors[n] = cid - width;
+                    n += 1;
+                }
+                if y + 1 < height {
+                    neighbors[n] = cid + width;
+                    n += 1;
+                }
+                if x > 0 {
+                    neighbors[n] = cid - 1;
+                    n += 1;
+                }
+                if x + 1 < width {
+                    neighbors[n] = cid + 1;
+                    n += 1;
+                }
+                if n == 0 {
+                    continue;
+                }
+                let spread = value * cfg.diffusion;
+                next[cid] -= spread;
+                for &nid in &neighbors[..n] {
+                    next[nid] += spread / n as f32;
+                }
+            }
+        }
+
+        let keep = 1.0 - cfg.evaporation;
+        for value in &mut next {
+            *value = (*value * keep).max(0.0);
+        }
+        self.values = next;
+    }
+}
+
+/// Follows pheromone trails left by agents that found food.
+///
+/// A hungry agent moves to its richest neighbor if any neighbor holds
+/// resource. Otherwise it climbs the pheromone gradient: it moves to the
+/// neighbor with the most pheromone, provided that neighbor holds more
+/// than its own cell. Fed agents stay.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PheromonePolicy;
+
+impl MovementPolicy for PheromonePolicy {
+    fn name(&self) -> &'static str {
+        "pheromone"
+    }
+
+    fn decide(&self, world: &World, agent: &Agent) -> Option<usize> {
+        if !agent.is_hungry() {
+            return None;
+        }
+        let cid = agent.cid();
+        let neighbors = world.neighbor_cells_info(cid);
+        if let Some(target) = agent.decide_move(&neighbors) {
+            return Some(target);
+        }
+
+        let field = world.pheromones();
+        let here = field.get(cid);
+        neighbors
+            .iter()
+            .map(|&(nid, _)| (nid, field.get(nid)))
+            .filter(|&(_, level)| level > here)
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(nid, _)| nid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Cell;
+
+    fn no_evaporation(diffusion: f32) -> PheromoneConfig {
+        PheromoneConfig {
+            enabled: true,
+            deposit: 1.0,
+            evaporation: 0.0,
+            diffusion,
+        }
+    }
+
+    #[test]
+    fn diffusion_conserves_total_and_spreads_evenly() {
+        let mut field = PheromoneField::new((3, 3));
+        field.deposit(4, 8.0);
+        field.step(&no_evaporation(0.5));
+
+        assert!((field.get(4) - 4.0).abs() < 1e-6);
+        for nid in [1, 3, 5, 7] {
+            assert!((field.get(nid) - 1.0).abs() < 1e-6);
+        }
+        assert_eq!(field.get(0), 0.0);
+        let total: f32 = field.values().iter().sum();
+        assert!((total - 8.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn evaporation_decays_the_field() {
+        let mut field = PheromoneField::new((2, 1));
+        field.deposit(0, 10.0);
+        field.deposit(9, 10.0);
+        let cfg = PheromoneConfig {
+            enabled: true,
+            deposit: 1.0,
+            evaporation: 0.5,
+            diffusion: 0.0,
+        };
+        field.step(&cfg);
+        assert!((field.get(0) - 5.0).abs() < 1e-6);
+        assert_eq!(field.max(), field.get(0));
+    }
+
+    #[test]
+    fn config_validation_rejects_bad_rates() {
+        assert!(PheromoneConfig::default().is_valid());
+        let bad = PheromoneConfig {
+            evaporation: 1.5,
+            ..PheromoneConfig::default()
+        };
+        assert!(!bad.is_valid());
+    }
+
+    #[test]
+    fn pheromone_policy_climbs_the_gradient() {
+        let cells = (0..5).map(|id| Cell::new(id, 0, 100, 0, 5)).collect();
+        let mut world = World::new((5, 1), cells, Vec::new());
+        world.deposit_pheromone(3, 2.0);
+        world.deposit_pheromone(1, 1.0);
+
+        let agent = Agent::new(0, 2, 1, 0, 10, true);
+        assert_eq!(PheromonePolicy.decide(&world, &agent), Some(3));
+
+        // Already on the peak: stay.
+        let agent = Agent::new(0, 3, 1, 0, 10, true);
+        assert_eq!(PheromonePolicy.decide(&world, &agent), None);
+    }
+
+    #[test]
+    fn pheromone_policy_prefers_visible_food() {
+        let mut cells: Vec<Cell> = (0..3).map(|id| Cell::new(id, 0, 100, 0, 5)).collect();
+        cells[0] = Cell::new(0, 4, 100, 0, 5);
+        let mut world = World::new((3, 1), cells, Vec::new());
+        world.deposit_pheromone(2, 5.0);
+
+        let agent = Agent::new(0, 1, 1, 0, 10, true);
+        assert_eq!(PheromonePolicy.decide(&world, &agent), Some(0));
+    }
+}
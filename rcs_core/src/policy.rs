@@ -0,0 +1,188 @@
+use crate::evolution::NeuralPolicy;
+use crate::memory::MemoryPolicy;
+use crate::pathfinding::ForagingPolicy;
+use crate::perception::VisionPolicy;
+use crate::pheromone::PheromonePolicy;
+use crate::rl::QLearningPolicy;
+use crate::{Agent, World};
+
+/// Result of an agent's movement request in one step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveOutcome {
+    /// The agent did not ask to move.
+    Stayed,
+    /// The agent moved from one cell to another.
+    Moved { from: usize, to: usize },
+    /// The agent asked to move to `target`, but the cell was full.
+    Blocked { target: usize },
+}
+
+/// Decides where agents move and receives the outcome of each request.
+///
+/// The world owns a single policy that is asked for every living agent
+/// each step. Policies that need per-agent state should key it by
+/// [`Agent::id`], not by the agent's position in [`World::agents`].
+///
+/// Policies must be `Send + Sync` so that worlds can be moved across threads.
+pub trait MovementPolicy: Send + Sync {
+    /// Returns a short human-readable name for this policy.
+    ///
+    /// ### Returns
+    /// A static label such as `"greedy"`.
+    fn name(&self) -> &'static str;
+
+    /// Chooses the destination of a living agent for this step.
+    ///
+    /// ### Parameters
+    /// - `world`: Read-only view of the world at decision time.
+    /// - `agent`: The agent that is about to act.
+    ///
+    /// ### Returns
+    /// - `Some(cell_id)` of a neighboring cell to move to.
+    /// - `None` to stay.
+    fn decide(&self, world: &World, agent: &Agent) -> Option<usize>;
+
+    /// Returns whether [`MovementPolicy::decide`] gives the same answers
+    /// whatever order the agents are asked in.
+    ///
+    /// With the `parallel` feature, synchronous steps only ask such
+    /// policies in parallel. The default implementation returns `true`;
+    /// policies that draw from shared random state must return `false`.
+    ///
+    /// ### Returns
+    /// `true` if decisions may be computed in any order.
+    fn order_independent(&self) -> bool {
+        true
+    }
+
+    /// Reports what happened to the agent's movement request.
+    ///
+    /// The default implementation ignores the outcome.
+    ///
+    /// ### Parameters
+    /// - `agent`: The agent after the request was resolved.
+    /// - `outcome`: Whether it stayed, moved or was blocked.
+    fn on_outcome(&mut self, agent: &Agent, outcome: MoveOutcome) {
+        let _ = (agent, outcome);
+    }
+
+    /// Reports that an agent died, e.g. to drop its per-agent state.
+    ///
+    /// The default implementation does nothing.
+    ///
+    /// ### Parameters
+    /// - `agent`: The agent, already marked as dead.
+    fn on_death(&mut self, agent: &Agent) {
+        let _ = agent;
+    }
+}
+
+/// The default policy: hungry agents move to their richest neighbor.
+///
+/// This delegates to [`Agent::decide_move`] with the four cardinal
+/// neighbors returned by [`World::neighbor_cells_info`]. Fed agents stay.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GreedyPolicy;
+
+impl MovementPolicy for GreedyPolicy {
+    fn name(&self) -> &'static str {
+        "greedy"
+    }
+
+    fn decide(&self, world: &World, agent: &Agent) -> Option<usize> {
+        if !agent.is_hungry() {
+            return None;
+        }
+        agent.decide_move(&world.neighbor_cells_info(agent.cid()))
+    }
+}
+
+/// The built-in movement policies, selectable from a [`crate::WorldConfig`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PolicyKind {
+    /// [`GreedyPolicy`]: move to the richest of the four neighbors.
+    #[default]
+    Greedy,
+    /// [`VisionPolicy`]: walk toward the richest cell within the perception radius.
+    Vision,
+    /// [`ForagingPolicy`]: plan and follow a cheapest route to a rich visible cell.
+    Forager,
+    /// [`MemoryPolicy`]: avoid recently visited cells and return to known patches.
+    Memory,
+    /// [`PheromonePolicy`]: follow pheromone trails toward food.
+    Pheromone,
+    /// [`QLearningPolicy`]: learn where to move from health and survival.
+    QLearning,
+    /// [`NeuralPolicy`]: let each agent's evolved genome choose the move.
+    Neural,
+}
+
+impl PolicyKind {
+    /// All built-in policies, e.g. for populating a selector.
+    pub const ALL: [PolicyKind; 7] = [
+        PolicyKind::Greedy,
+        PolicyKind::Vision,
+        PolicyKind::Forager,
+        PolicyKind::Memory,
+        PolicyKind::Pheromone,
+        PolicyKind::QLearning,
+        PolicyKind::Neural,
+    ];
+
+    /// Returns a short human-readable name for this policy.
+    ///
+    /// ### Returns
+    /// The same label as the built policy's [`MovementPolicy::name`].
+    pub fn label(&self) -> &'static str {
+        match self {
+            PolicyKind::Greedy => "greedy",
+            PolicyKind::Vision => "vision",
+            PolicyKind::Forager => "forager",
+            PolicyKind::Memory => "memory",
+            PolicyKind::Pheromone => "pheromone",
+            PolicyKind::QLearning => "q-learning",
+            PolicyKind::Neural => "neural",
+        }
+    }
+
+    /// Creates a fresh instance of this policy.
+    ///
+    /// ### Returns
+    /// A boxed [`MovementPolicy`] ready to be installed on a world.
+    pub fn build(&self) -> Box<dyn MovementPolicy> {
+        match self {
+            PolicyKind::Greedy => Box::new(GreedyPolicy),
+            PolicyKind::Vision => Box::new(VisionPolicy),
+            PolicyKind::Forager => Box::new(ForagingPolicy::default()),
+            PolicyKind::Memory => Box::new(MemoryPolicy::default()),
+            PolicyKind::Pheromone => Box::new(PheromonePolicy),
+            PolicyKind::QLearning => Box::new(QLearningPolicy::default()),
+            PolicyKind::Neural => Box::new(NeuralPolicy),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Cell;
+
+    #[test]
+    fn greedy_policy_moves_only_hungry_agents() {
+        let cells = vec![Cell::new(0, 0, 10, 0, 1), Cell::new(1, 8, 10, 0, 1)];
+        let world = World::new((2, 1), cells, Vec::new());
+
+        let hungry = Agent::new(0, 0, 3, 0, 5, true);
+        assert_eq!(GreedyPolicy.decide(&world, &hungry), Some(1));
+
+        let fed = Agent::new(1, 0, 3, 3, 5, true);
+        assert_eq!(GreedyPolicy.decide(&world, &fed), None);
+    }
+
+    #[test]
+    fn policy_kind_builds_matching_policy() {
+        for kind in PolicyKind::ALL {
+            assert_eq!(kind.build().name(), kind.label());
+        }
+    }
+}
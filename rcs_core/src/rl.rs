@@ -0,0 +1,503 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::errors::SimulationError;
+use crate::policy::MovementPolicy;
+use crate::{Agent, World};
+
+/// A discrete action of a learning agent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Action {
+    #[default]
+    Stay,
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Action {
+    /// All actions, in the order used to index [`QTable`] rows.
+    pub const ALL: [Action; 5] = [
+        Action::Stay,
+        Action::Up,
+        Action::Down,
+        Action::Left,
+        Action::Right,
+    ];
+
+    /// Returns the position of this action in [`Action::ALL`].
+    ///
+    /// ### Returns
+    /// An index in `0..5`.
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+
+    /// Returns the cell this action leads to.
+    ///
+    /// ### Parameters
+    /// - `cid`: Id of the current cell.
+    /// - `size`: `(width, height)` of the grid.
+    ///
+    /// ### Returns
+    /// - `Some(cell_id)` of the neighbor in that direction.
+    /// - `None` for [`Action::Stay`] or a move off the grid.
+    pub fn target(&self, cid: usize, size: (usize, usize)) -> Option<usize> {
+        let (width, height) = size;
+        let (x, y) = (cid % width, cid / width);
+        match self {
+            Action::Stay => None,
+            Action::Up => (y > 0).then(|| cid - width),
+            Action::Down => (y + 1 < height).then(|| cid + width),
+            Action::Left => (x > 0).then(|| cid - 1),
+            Action::Right => (x + 1 < width).then(|| cid + 1),
+        }
+    }
+}
+
+/// What a learning agent observes before acting.
+///
+/// `patch` is the square of side `2 * radius + 1` centered on the agent,
+/// in row-major order. Each entry holds the resource of that cell, or
+/// `None` if it lies outside the grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Observation {
+    pub radius: usize,
+    pub patch: Vec<Option<u32>>,
+    pub health_point: u32,
+    pub energy: u32,
+    pub need: u32,
+    pub hungry: bool,
+}
+
+impl Observation {
+    /// Returns the observed resource at an offset from the agent.
+    ///
+    /// ### Parameters
+    /// - `dx`, `dy`: Offset from the center, each within `±radius`.
+    ///
+    /// ### Returns
+    /// The entry of [`Observation::patch`] at that offset.
+    pub fn at(&self, dx: isize, dy: isize) -> Option<u32> {
+        let side = 2 * self.radius as isize + 1;
+        let (x, y) = (dx + self.radius as isize, dy + self.radius as isize);
+        if !(0..side).contains(&x) || !(0..side).contains(&y) {
+            return None;
+        }
+        self.patch[(y * side + x) as usize]
+    }
+
+    /// Compresses the observation into a small discrete state.
+    ///
+    /// The own cell and its four neighbors are each classified as off-grid,
+    /// empty, holding less than the agent needs, or holding enough; the
+    /// hunger flag is added on top. This gives `2 * 4^5` possible states.
+    ///
+    /// ### Returns
+    /// A state id suitable as a [`QTable`] key.
+    pub fn state_key(&self) -> u32 {
+        let class = |cell: Option<u32>| match cell {
+            None => 0,
+            Some(0) => 1,
+            Some(r) if r < self.need => 2,
+            Some(_) => 3,
+        };
+        let mut key = u32::from(self.hungry);
+        for (dx, dy) in [(0, 0), (0, -1), (0, 1), (-1, 0), (1, 0)] {
+            key = key * 4 + class(self.at(dx, dy));
+        }
+        key
+    }
+}
+
+/// How the reward of a learning agent is computed after each tick.
+///
+/// Health points act as the agent's energy: the reward is the change in
+/// health plus `survival_bonus` while the agent lives, or minus
+/// `death_penalty` on the tick it dies.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RewardConfig {
+    pub survival_bonus: f32,
+    pub death_penalty: f32,
+}
+
+impl RewardConfig {
+    /// Computes the reward for one tick.
+    ///
+    /// ### Parameters
+    /// - `hp_before`: Health before the tick.
+    /// - `hp_after`: Health after the tick.
+    /// - `alive`: Whether the agent survived the tick.
+    ///
+    /// ### Returns
+    /// The scalar reward.
+    pub fn reward(&self, hp_before: u32, hp_after: u32, alive: bool) -> f32 {
+        let delta = hp_after as f32 - hp_before as f32;
+        if alive {
+            delta + self.survival_bonus
+        } else {
+            delta - self.death_penalty
+        }
+    }
+}
+
+impl Default for RewardConfig {
+    /// Returns a small bonus per tick survived and a large death penalty.
+    fn default() -> Self {
+        Self {
+            survival_bonus: 0.1,
+            death_penalty: 10.0,
+        }
+    }
+}
+
+/// Result of [`World::step_with_actions`], indexed like [`World::agents`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StepResult {
+    pub rewards: Vec<f32>,
+    pub done: Vec<bool>,
+    /// The failure reported by the update, if any; the tick ran anyway.
+    pub error: Option<SimulationError>,
+}
+
+/// Action values per discrete state, shared between learning policies.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QTable {
+    values: HashMap<u32, [f32; 5]>,
+}
+
+impl QTable {
+    /// Returns the action values of a state.
+    ///
+    /// ### Parameters
+    /// - `state`: A key from [`Observation::state_key`].
+    ///
+    /// ### Returns
+    /// One value per entry of [`Action::ALL`]; zeros for unseen states.
+    pub fn values(&self, state: u32) -> [f32; 5] {
+        self.values.get(&state).copied().unwrap_or_default()
+    }
+
+    /// Returns the best known action of a state.
+    ///
+    /// Ties are broken in favour of the earlier entry of [`Action::ALL`].
+    ///
+    /// ### Parameters
+    /// - `state`: A key from [`Observation::state_key`].
+    ///
+    /// ### Returns
+    /// The greedy [`Action`].
+    pub fn best_action(&self, state: u32) -> Action {
+        let values = self.values(state);
+        let mut best = 0;
+        for (i, &v) in values.iter().enumerate() {
+            if v > values[best] {
+                best = i;
+            }
+        }
+        Action::ALL[best]
+    }
+
+    /// Returns the number of states visited so far.
+    ///
+    /// ### Returns
+    /// The number of rows in the table.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns whether no state has been visited yet.
+    ///
+    /// ### Returns
+    /// `true` for a fresh table.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Applies one Q-learning update.
+    ///
+    /// ### Parameters
+    /// - `state`, `action`: The state-action pair being updated.
+    /// - `reward`: Reward received after taking `action`.
+    /// - `next`: The following state, or `None` if the episode ended.
+    /// - `alpha`: Learning rate.
+    /// - `gamma`: Discount factor.
+    pub fn learn(
+        &mut self,
+        state: u32,
+        action: Action,
+        reward: f32,
+        next: Option<u32>,
+        alpha: f32,
+        gamma: f32,
+    ) {
+        let future = next.map_or(0.0, |s| {
+            self.values(s).into_iter().fold(f32::NEG_INFINITY, f32::max)
+        });
+        let row = self.values.entry(state).or_default();
+        let q = &mut row[action.index()];
+        *q += alpha * (reward + gamma * future - *q);
+    }
+}
+
+/// A learning agent's last decision, waiting for its reward.
+#[derive(Debug, Clone, Copy)]
+struct Pending {
+    state: u32,
+    action: Action,
+    hp: u32,
+}
+
+/// Tabular Q-learning over [`Observation::state_key`] states.
+///
+/// The policy learns online while the world runs: when an agent decides,
+/// the reward of its previous decision (see [`RewardConfig`]) is computed
+/// from its health change and used to update the table. Deaths end the
+/// agent's episode. Actions are chosen epsilon-greedily; set `epsilon` to
+/// `0.0` to evaluate a trained table.
+///
+/// The table can be shared between worlds with [`QLearningPolicy::table`]
+/// and [`QLearningPolicy::with_table`], e.g. to train on many short runs.
+pub struct QLearningPolicy {
+    pub alpha: f32,
+    pub gamma: f32,
+    pub epsilon: f32,
+    pub reward: RewardConfig,
+    table: Arc<Mutex<QTable>>,
+    pending: Mutex<HashMap<usize, Pending>>,
+    rng: Mutex<StdRng>,
+}
+
+impl QLearningPolicy {
+    /// Creates a learner with an empty table.
+    ///
+    /// ### Parameters
+    /// - `seed`: Seed for the exploration random number generator.
+    ///
+    /// ### Returns
+    /// A [`QLearningPolicy`] with `alpha = 0.2`, `gamma = 0.9` and
+    /// `epsilon = 0.1`.
+    pub fn new(seed: u64) -> Self {
+        Self::with_table(Arc::new(Mutex::new(QTable::default())), seed)
+    }
+
+    /// Creates a learner that reads and updates an existing table.
+    ///
+    /// ### Parameters
+    /// - `table`: The shared [`QTable`].
+    /// - `seed`: Seed for the exploration random number generator.
+    ///
+    /// ### Returns
+    /// A [`QLearningPolicy`] with the default rates.
+    pub fn with_table(table: Arc<Mutex<QTable>>, seed: u64) -> Self {
+        Self {
+            alpha: 0.2,
+            gamma: 0.9,
+            epsilon: 0.1,
+            reward: RewardConfig::default(),
+            table,
+            pending: Mutex::new(HashMap::new()),
+            rng: Mutex::new(StdRng::seed_from_u64(seed)),
+        }
+    }
+
+    /// Returns a handle to the learned table.
+    ///
+    /// ### Returns
+    /// The shared [`QTable`].
+    pub fn table(&self) -> Arc<Mutex<QTable>> {
+        Arc::clone(&self.table)
+    }
+}
+
+impl Default for QLearningPolicy {
+    /// Returns a fresh learner seeded with `0`.
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl MovementPolicy for QLearningPolicy {
+    fn name(&self) -> &'static str {
+        "q-learning"
+    }
+
+    /// Exploration and learning share one random generator and one table.
+    fn order_independent(&self) -> bool {
+        false
+    }
+
+    fn decide(&self, world: &World, agent: &Agent) -> Option<usize> {
+        let state = world.observe(agent, 1).state_key();
+        let mut table = self.table.lock().unwrap_or_else(|e| e.into_inner());
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+
+        if let Some(prev) = pending.get(&agent.id()) {
+            let reward = self.reward.reward(prev.hp, agent.health_point(), true);
+            table.learn(
+                prev.state,
+                prev.action,
+                reward,
+                Some(state),
+                self.alpha,
+                self.gamma,
+            );
+        }
+
+        let mut rng = self.rng.lock().unwrap_or_else(|e| e.into_inner());
+        let action = if rng.gen_bool(f64::from(self.epsilon.clamp(0.0, 1.0))) {
+            Action::ALL[rng.gen_range(0..Action::ALL.len())]
+        } else {
+            table.best_action(state)
+        };
+        pending.insert(
+            agent.id(),
+            Pending {
+                state,
+                action,
+                hp: agent.health_point(),
+            },
+        );
+        action.target(agent.cid(), world.size())
+    }
+
+    fn on_death(&mut self, agent: &Agent) {
+        let pending = self.pending.get_mut().unwrap_or_else(|e| e.into_inner());
+        if let Some(prev) = pending.remove(&agent.id()) {
+            let reward = self.reward.reward(prev.hp, agent.health_point(), false);
+            let mut table = self.table.lock().unwrap_or_else(|e| e.into_inner());
+            table.learn(
+                prev.state,
+                prev.action,
+                reward,
+                None,
+                self.alpha,
+                self.gamma,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::MoveOutcome;
+    use crate::{Cell, Updatable};
+
+    fn strip(resources: &[u32], regen: u32) -> Vec<Cell> {
+        resources
+            .iter()
+            .enumerate()
+            .map(|(id, &r)| Cell::new(id, r, 100, regen, 5))
+            .collect()
+    }
+
+    #[test]
+    fn action_targets_respect_the_grid() {
+        let size = (3, 2);
+        assert_eq!(Action::Stay.target(4, size), None);
+        assert_eq!(Action::Up.target(4, size), Some(1));
+        assert_eq!(Action::Down.target(4, size), None);
+        assert_eq!(Action::Left.target(3, size), None);
+        assert_eq!(Action::Right.target(4, size), Some(5));
+    }
+
+    #[test]
+    fn observation_patch_marks_off_grid_cells() {
+        let world = World::new((3, 1), strip(&[1, 2, 3], 0), Vec::new());
+        let agent = Agent::new(0, 0, 2, 0, 5, true);
+        let obs = world.observe(&agent, 1);
+
+        assert_eq!(obs.patch.len(), 9);
+        assert_eq!(obs.at(0, 0), Some(1));
+        assert_eq!(obs.at(1, 0), Some(2));
+        assert_eq!(obs.at(-1, 0), None);
+        assert_eq!(obs.at(0, -1), None);
+        assert!(obs.hungry);
+        // Hungry, own cell below need, up/down/left off-grid, right enough.
+        assert_eq!(obs.state_key(), 0b01_10_00_00_00_11);
+    }
+
+    #[test]
+    fn q_update_moves_toward_target() {
+        let mut table = QTable::default();
+        table.learn(7, Action::Right, 1.0, None, 0.5, 0.9);
+        assert_eq!(table.values(7)[Action::Right.index()], 0.5);
+        assert_eq!(table.best_action(7), Action::Right);
+
+        table.learn(3, Action::Up, 0.0, Some(7), 1.0, 0.9);
+        assert!((table.values(3)[Action::Up.index()] - 0.45).abs() < 1e-6);
+        assert_eq!(table.len(), 2);
+    }
+
+    #[test]
+    fn step_with_actions_moves_and_rewards_agents() {
+        let agents = vec![
+            Agent::new(0, 0, 1, 0, 5, true),
+            Agent::new(1, 2, 1, 0, 1, true),
+        ];
+        let mut world = World::new((3, 1), strip(&[0, 0, 0], 0), agents);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        world.set_policy(Box::new(Recorder(Arc::clone(&seen))));
+        let result =
+            world.step_with_actions(&[Action::Right, Action::Stay], RewardConfig::default());
+
+        assert_eq!(result.error, None);
+        assert_eq!(world.agents()[0].cid(), 1);
+        // Moved (-1) and starved (-1), then survived.
+        assert!((result.rewards[0] - (-2.0 + 0.1)).abs() < 1e-6);
+        assert!(result.done[1]);
+        assert!((result.rewards[1] - (-1.0 - 10.0)).abs() < 1e-6);
+        // The world's own policy stays in place and hears about the tick.
+        assert_eq!(world.policy().name(), "recorder");
+        assert_eq!(*seen.lock().unwrap(), ["outcome 0", "outcome 1", "death 1"]);
+    }
+
+    /// Stays put and records the outcomes and deaths it is told about.
+    struct Recorder(Arc<Mutex<Vec<String>>>);
+
+    impl MovementPolicy for Recorder {
+        fn name(&self) -> &'static str {
+            "recorder"
+        }
+
+        fn decide(&self, _: &World, _: &Agent) -> Option<usize> {
+            None
+        }
+
+        fn on_outcome(&mut self, agent: &Agent, _: MoveOutcome) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(format!("outcome {}", agent.id()));
+        }
+
+        fn on_death(&mut self, agent: &Agent) {
+            self.0.lock().unwrap().push(format!("death {}", agent.id()));
+        }
+    }
+
+    #[test]
+    fn q_learning_finds_food_in_a_corridor() {
+        let table = Arc::new(Mutex::new(QTable::default()));
+        for episode in 0..300 {
+            let agents = vec![Agent::new(0, 0, 1, 0, 30, true)];
+            let mut world = World::new((3, 1), strip(&[0, 0, 50], 0), agents);
+            let mut policy = QLearningPolicy::with_table(Arc::clone(&table), episode);
+            policy.epsilon = 0.3;
+            world.set_policy(Box::new(policy));
+            for _ in 0..12 {
+                world.update().unwrap();
+            }
+        }
+
+        let world = World::new((3, 1), strip(&[0, 0, 50], 0), Vec::new());
+        let agent = Agent::new(0, 1, 1, 0, 30, true);
+        let state = world.observe(&agent, 1).state_key();
+        assert_eq!(table.lock().unwrap().best_action(state), Action::Right);
+    }
+}
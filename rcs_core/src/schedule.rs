@@ -0,0 +1,160 @@
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+/// Order in which agents are stepped during [`crate::World::update`].
+///
+/// - `Sequential`: agents act one after another by index, so low-index
+///   agents always move first and later agents see their effects.
+/// - `Shuffled`: agents act one after another in a fresh random order each
+///   tick, drawn from the world's seeded RNG.
+/// - `Synchronous`: every agent decides on the same snapshot of the world,
+///   then all decisions are applied and conflicts are resolved.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UpdateOrder {
+    #[default]
+    Sequential,
+    Shuffled,
+    Synchronous,
+}
+
+impl UpdateOrder {
+    /// All available update orders, e.g. for populating a selector.
+    pub const ALL: [UpdateOrder; 3] = [
+        UpdateOrder::Sequential,
+        UpdateOrder::Shuffled,
+        UpdateOrder::Synchronous,
+    ];
+
+    /// Returns a short human-readable name for this order.
+    ///
+    /// ### Returns
+    /// A static label such as `"sequential"`.
+    pub fn label(&self) -> &'static str {
+        match self {
+            UpdateOrder::Sequential => "sequential",
+            UpdateOrder::Shuffled => "shuffled",
+            UpdateOrder::Synchronous => "synchronous",
+        }
+    }
+
+    /// Returns the agent indices in the order they should act this tick.
+    ///
+    /// Only `Shuffled` consumes randomness; the other orders return
+    /// `0..len` (synchronous decisions do not depend on the order in which
+    /// they are applied).
+    ///
+    /// ### Parameters
+    /// - `len`: Number of agents in the world.
+    /// - `rng`: Random number generator used for shuffling.
+    ///
+    /// ### Returns
+    /// A permutation of `0..len`.
+    pub fn agent_order<R: Rng + ?Sized>(&self, len: usize, rng: &mut R) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..len).collect();
+        if *self == UpdateOrder::Shuffled {
+            order.shuffle(rng);
+        }
+        order
+    }
+}
+
+/// Rule for resolving simultaneous movement requests into a full cell.
+///
+/// Conflicts only arise when a cell capacity is set
+/// (see [`crate::World::set_cell_capacity`]). With `Sequential` and
+/// `Shuffled` update orders agents move one at a time, so requests are
+/// always served first-come in scheduling order. With `Synchronous` update
+/// order all requests of a tick compete, and this rule decides who is
+/// served first; requests that find the cell full are blocked.
+///
+/// - `FirstCome`: lower agent index first.
+/// - `Stronger`: higher health first, ties broken by agent index.
+/// - `Random`: random order drawn from the world's seeded RNG.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConflictRule {
+    #[default]
+    FirstCome,
+    Stronger,
+    Random,
+}
+
+impl ConflictRule {
+    /// All available conflict rules, e.g. for populating a selector.
+    pub const ALL: [ConflictRule; 3] = [
+        ConflictRule::FirstCome,
+        ConflictRule::Stronger,
+        ConflictRule::Random,
+    ];
+
+    /// Returns a short human-readable name for this rule.
+    ///
+    /// ### Returns
+    /// A static label such as `"first-come"`.
+    pub fn label(&self) -> &'static str {
+        match self {
+            ConflictRule::FirstCome => "first-come",
+            ConflictRule::Stronger => "stronger wins",
+            ConflictRule::Random => "random",
+        }
+    }
+
+    /// Sorts agent indices into the order in which their requests are served.
+    ///
+    /// ### Parameters
+    /// - `order`: Agent indices, initially in index order.
+    /// - `strength`: Returns the strength (health) of an agent by index.
+    /// - `rng`: Random number generator used by `Random`.
+    pub fn serve_order<R, F>(&self, order: &mut [usize], strength: F, rng: &mut R)
+    where
+        R: Rng + ?Sized,
+        F: Fn(usize) -> u32,
+    {
+        match self {
+            ConflictRule::FirstCome => {}
+            ConflictRule::Stronger => order.sort_by_key(|&i| std::cmp::Reverse(strength(i))),
+            ConflictRule::Random => order.shuffle(rng),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn sequential_and_synchronous_keep_index_order() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let expected: Vec<usize> = (0..5).collect();
+        assert_eq!(UpdateOrder::Sequential.agent_order(5, &mut rng), expected);
+        assert_eq!(UpdateOrder::Synchronous.agent_order(5, &mut rng), expected);
+    }
+
+    #[test]
+    fn shuffled_returns_a_seeded_permutation() {
+        let mut a = StdRng::seed_from_u64(7);
+        let mut b = StdRng::seed_from_u64(7);
+        let order_a = UpdateOrder::Shuffled.agent_order(20, &mut a);
+        let order_b = UpdateOrder::Shuffled.agent_order(20, &mut b);
+        assert_eq!(order_a, order_b);
+
+        let mut sorted = order_a.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..20).collect::<Vec<_>>());
+        assert_ne!(order_a, sorted);
+    }
+
+    #[test]
+    fn stronger_rule_serves_highest_strength_first_with_stable_ties() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let hp = [2, 5, 5, 1];
+        let mut order = vec![0, 1, 2, 3];
+        ConflictRule::Stronger.serve_order(&mut order, |i| hp[i], &mut rng);
+        assert_eq!(order, vec![1, 2, 0, 3]);
+
+        let mut order = vec![0, 1, 2, 3];
+        ConflictRule::FirstCome.serve_order(&mut order, |i| hp[i], &mut rng);
+        assert_eq!(order, vec![0, 1, 2, 3]);
+    }
+}
@@ -0,0 +1,553 @@
+use crate::errors::SimulationError;
+use crate::policy::PolicyKind;
+use crate::schedule::{ConflictRule, UpdateOrder};
+use crate::traits::Updatable;
+use crate::world::{CORPSE_REGEN_BONUS, CORPSE_RESOURCE, WorldConfig};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Marks the end of a cell's agent list.
+const NONE: u32 = u32::MAX;
+
+/// A headless world for very large runs, stored as struct-of-arrays.
+///
+/// `SoaWorld` runs the base simulation of [`crate::World`] — resource
+/// regeneration, equal sharing, greedy movement with terrain costs,
+/// starvation and corpses — with every cell and agent field in its own
+/// contiguous column. Which agents stand on which cell is kept in a
+/// persistent index (an intrusive doubly linked list per cell) that is
+/// updated when agents move or die, instead of being rebuilt every tick.
+///
+/// For a [`WorldConfig`] it accepts (see [`SoaWorld::from_config`]), a
+/// `SoaWorld` produces exactly the same cells and agents, tick by tick, as
+/// the [`crate::World`] built from the same config and seed. The optional
+/// layers (memory, evolution, aging, disease, territory, combat, groups),
+/// cell capacities, compaction and other policies are not supported;
+/// pheromones are not tracked because greedy agents ignore them.
+///
+/// Cells and agents are addressed by their index; agents keep their index
+/// after they die.
+pub struct SoaWorld {
+    size: (usize, usize),
+    tick: u64,
+    rng: StdRng,
+    update_order: UpdateOrder,
+    conflict_rule: ConflictRule,
+
+    resource: Vec<u32>,
+    max_resource: Vec<u32>,
+    regen_rate: Vec<u32>,
+    max_regen_rate: Vec<u32>,
+    terrain_cost: Vec<u32>,
+    occupants: Vec<u32>,
+    head: Vec<u32>,
+
+    cid: Vec<u32>,
+    need: Vec<u32>,
+    health_point: Vec<u32>,
+    allocated: Vec<u32>,
+    alive: Vec<bool>,
+    next: Vec<u32>,
+    prev: Vec<u32>,
+    living: usize,
+}
+
+impl SoaWorld {
+    /// Builds a randomized world, drawing exactly like
+    /// [`crate::World::try_from_config`].
+    ///
+    /// ### Parameters
+    /// - `cfg`: World generation parameters.
+    ///
+    /// ### Returns
+    /// - `Ok(SoaWorld)` sampled according to `cfg`.
+    /// - `Err(SimulationError::InvalidConfig(..))` if `cfg` is invalid or
+    ///   uses a feature this engine does not support.
+    pub fn from_config(cfg: WorldConfig) -> Result<Self, SimulationError> {
+        cfg.validate()?;
+        let num_cells = cfg.width.saturating_mul(cfg.height);
+        let rules = [
+            (
+                cfg.policy == PolicyKind::Greedy,
+                "the struct-of-arrays world only supports the greedy policy",
+            ),
+            (
+                cfg.cell_capacity.is_none(),
+                "the struct-of-arrays world does not support cell capacities",
+            ),
+            (
+                cfg.compaction_interval.is_none(),
+                "the struct-of-arrays world keeps dead agents and cannot compact them",
+            ),
+            (
+                !(cfg.evolution.enabled
+                    || cfg.aging.enabled
+                    || cfg.disease.enabled
+                    || cfg.territory.enabled
+                    || cfg.combat.enabled
+                    || cfg.groups.enabled),
+                "the struct-of-arrays world does not support optional layers",
+            ),
+            (
+                num_cells < NONE as usize && cfg.max_agents < NONE as usize,
+                "the struct-of-arrays world needs fewer than u32::MAX cells and agents",
+            ),
+        ];
+        if let Some((_, reason)) = rules.iter().find(|(ok, _)| !ok) {
+            return Err(SimulationError::InvalidConfig((*reason).to_string()));
+        }
+
+        let mut rng = match cfg.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+
+        let mut resource = Vec::with_capacity(num_cells);
+        let mut regen_rate = Vec::with_capacity(num_cells);
+        let mut terrain_cost = Vec::with_capacity(num_cells);
+        for _ in 0..num_cells {
+            resource.push(rng.gen_range(cfg.min_resource..=cfg.max_resource));
+            regen_rate.push(rng.gen_range(cfg.min_regen_rate..=cfg.max_regen_rate));
+            let cost = if cfg.min_terrain_cost < cfg.max_terrain_cost {
+                rng.gen_range(cfg.min_terrain_cost..=cfg.max_terrain_cost)
+            } else {
+                cfg.min_terrain_cost
+            };
+            terrain_cost.push(cost.max(1));
+        }
+
+        let num_agents = rng.gen_range(cfg.min_agents..=cfg.max_agents);
+        let upkeep = cfg.perception.upkeep();
+        let mut world = Self {
+            size: (cfg.width, cfg.height),
+            tick: 0,
+            rng: StdRng::seed_from_u64(0),
+            update_order: cfg.update_order,
+            conflict_rule: cfg.conflict_rule,
+            resource,
+            max_resource: vec![cfg.max_resource; num_cells],
+            regen_rate,
+            max_regen_rate: vec![cfg.max_regen_rate; num_cells],
+            terrain_cost,
+            occupants: vec![0; num_cells],
+            head: vec![NONE; num_cells],
+            cid: Vec::with_capacity(num_agents),
+            need: Vec::with_capacity(num_agents),
+            health_point: vec![cfg.agent_hp; num_agents],
+            allocated: vec![0; num_agents],
+            alive: vec![true; num_agents],
+            next: vec![NONE; num_agents],
+            prev: vec![NONE; num_agents],
+            living: num_agents,
+        };
+        for id in 0..num_agents {
+            let x = rng.gen_range(0..cfg.width);
+            let y = rng.gen_range(0..cfg.height);
+            let consumption = rng.gen_range(cfg.min_consumption_rate..=cfg.max_consumption_rate);
+            world.cid.push((y * cfg.width + x) as u32);
+            world.need.push(consumption.saturating_add(upkeep));
+            world.link(id);
+        }
+        world.rng = rng;
+        Ok(world)
+    }
+
+    /// Returns the size of the world as `(width, height)`.
+    ///
+    /// ### Returns
+    /// The `(width, height)` of this world.
+    pub fn size(&self) -> (usize, usize) {
+        self.size
+    }
+
+    /// Returns the number of completed update steps.
+    ///
+    /// ### Returns
+    /// The current tick.
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Returns the number of agents that are still alive.
+    ///
+    /// ### Returns
+    /// The living population.
+    pub fn living_agents(&self) -> usize {
+        self.living
+    }
+
+    /// Returns the current resource of every cell.
+    ///
+    /// ### Returns
+    /// One entry per cell in row-major order.
+    pub fn resources(&self) -> &[u32] {
+        &self.resource
+    }
+
+    /// Returns the regeneration rate of every cell.
+    ///
+    /// ### Returns
+    /// One entry per cell in row-major order.
+    pub fn regen_rates(&self) -> &[u32] {
+        &self.regen_rate
+    }
+
+    /// Returns the cell every agent stands on.
+    ///
+    /// ### Returns
+    /// One cell id per agent; dead agents keep their last cell.
+    pub fn agent_cells(&self) -> &[u32] {
+        &self.cid
+    }
+
+    /// Returns the health of every agent.
+    ///
+    /// ### Returns
+    /// One entry per agent.
+    pub fn health_points(&self) -> &[u32] {
+        &self.health_point
+    }
+
+    /// Returns whether each agent is alive.
+    ///
+    /// ### Returns
+    /// One entry per agent.
+    pub fn alive(&self) -> &[bool] {
+        &self.alive
+    }
+
+    /// Returns the number of living agents on a cell.
+    ///
+    /// ### Parameters
+    /// - `cid`: Cell id in row-major indexing.
+    ///
+    /// ### Returns
+    /// The number of living agents on `cid`, or `0` outside the grid.
+    pub fn occupancy(&self, cid: usize) -> u32 {
+        self.occupants.get(cid).copied().unwrap_or(0)
+    }
+
+    /// Returns the living agents on a cell, read from the persistent index.
+    ///
+    /// ### Parameters
+    /// - `cid`: Cell id in row-major indexing.
+    ///
+    /// ### Returns
+    /// An iterator over agent indices, in no particular order.
+    pub fn agents_on(&self, cid: usize) -> impl Iterator<Item = usize> + '_ {
+        let first = self.head.get(cid).copied().unwrap_or(NONE);
+        std::iter::successors((first != NONE).then_some(first), |&a| {
+            let next = self.next[a as usize];
+            (next != NONE).then_some(next)
+        })
+        .map(|a| a as usize)
+    }
+
+    /// Adds an agent to the list of its current cell.
+    ///
+    /// ### Parameters
+    /// - `id`: Index of the agent.
+    fn link(&mut self, id: usize) {
+        let cid = self.cid[id] as usize;
+        let first = self.head[cid];
+        self.prev[id] = NONE;
+        self.next[id] = first;
+        if first != NONE {
+            self.prev[first as usize] = id as u32;
+        }
+        self.head[cid] = id as u32;
+        self.occupants[cid] += 1;
+    }
+
+    /// Removes an agent from the list of its current cell.
+    ///
+    /// ### Parameters
+    /// - `id`: Index of the agent.
+    fn unlink(&mut self, id: usize) {
+        let cid = self.cid[id] as usize;
+        let (prev, next) = (self.prev[id], self.next[id]);
+        if prev == NONE {
+            self.head[cid] = next;
+        } else {
+            self.next[prev as usize] = next;
+        }
+        if next != NONE {
+            self.prev[next as usize] = prev;
+        }
+        self.prev[id] = NONE;
+        self.next[id] = NONE;
+        self.occupants[cid] -= 1;
+    }
+
+    /// Regenerates every cell, in parallel with the `parallel` feature.
+    fn regenerate(&mut self) {
+        let regenerate = |(resource, (&rate, &max)): (&mut u32, (&u32, &u32))| {
+            *resource = resource.saturating_add(rate).min(max);
+        };
+        #[cfg(feature = "parallel")]
+        self.resource
+            .par_iter_mut()
+            .zip(self.regen_rate.par_iter().zip(self.max_resource.par_iter()))
+            .for_each(regenerate);
+        #[cfg(not(feature = "parallel"))]
+        self.resource
+            .iter_mut()
+            .zip(self.regen_rate.iter().zip(self.max_resource.iter()))
+            .for_each(regenerate);
+    }
+
+    /// Splits each cell's resource equally among the agents on it.
+    ///
+    /// Each agent takes up to its need from its share; the leftover stays
+    /// on the cell.
+    fn allocate(&mut self) {
+        for cid in 0..self.head.len() {
+            let n = self.occupants[cid];
+            let total = self.resource[cid];
+            if n == 0 || total == 0 {
+                continue;
+            }
+            let share = total / n;
+            let mut spent = 0;
+            let mut a = self.head[cid];
+            while a != NONE {
+                let i = a as usize;
+                let take = share.min(self.need[i]);
+                self.allocated[i] = take;
+                spent += take;
+                a = self.next[i];
+            }
+            self.resource[cid] -= spent;
+        }
+    }
+
+    /// Returns the four-neighbor cells of a cell.
+    ///
+    /// ### Parameters
+    /// - `cid`: Id of the central cell.
+    ///
+    /// ### Returns
+    /// Up to four cell ids in the order up, down, left, right, matching
+    /// [`crate::World::neighbor_cells_info`].
+    fn neighbors(&self, cid: usize) -> impl Iterator<Item = usize> {
+        let (width, height) = self.size;
+        let (x, y) = (cid % width, cid / width);
+        [
+            (y > 0).then(|| cid - width),
+            (y + 1 < height).then(|| cid + width),
+            (x > 0).then(|| cid - 1),
+            (x + 1 < width).then(|| cid + 1),
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    /// Chooses where a living agent moves, like [`crate::GreedyPolicy`].
+    ///
+    /// ### Parameters
+    /// - `id`: Index of the agent.
+    ///
+    /// ### Returns
+    /// - `Some(cell_id)` of the richest neighbor if the agent is hungry and
+    ///   that neighbor has resource.
+    /// - `None` otherwise.
+    fn decide(&self, id: usize) -> Option<usize> {
+        if !self.alive[id] || self.allocated[id] >= self.need[id] {
+            return None;
+        }
+        let best = self
+            .neighbors(self.cid[id] as usize)
+            .map(|nid| (nid, self.resource[nid]))
+            .max_by_key(|&(_, resource)| resource);
+        match best {
+            Some((nid, resource)) if resource > 0 => Some(nid),
+            _ => None,
+        }
+    }
+
+    /// Moves and metabolizes one agent.
+    ///
+    /// ### Parameters
+    /// - `id`: Index of the agent.
+    /// - `target`: Destination chosen by [`SoaWorld::decide`].
+    fn apply(&mut self, id: usize, target: Option<usize>) {
+        if !self.alive[id] {
+            return;
+        }
+        if let Some(target) = target {
+            self.unlink(id);
+            self.cid[id] = target as u32;
+            self.link(id);
+            self.health_point[id] = self.health_point[id].saturating_sub(self.terrain_cost[target]);
+            if self.health_point[id] == 0 {
+                self.die(id);
+                return;
+            }
+        }
+        if self.allocated[id] < self.need[id] {
+            self.health_point[id] = self.health_point[id].saturating_sub(1);
+        }
+        self.allocated[id] = 0;
+        if self.health_point[id] == 0 {
+            self.die(id);
+        }
+    }
+
+    /// Removes a dead agent from the index and leaves its corpse.
+    ///
+    /// ### Parameters
+    /// - `id`: Index of the agent.
+    fn die(&mut self, id: usize) {
+        self.alive[id] = false;
+        self.living -= 1;
+        self.unlink(id);
+        let cid = self.cid[id] as usize;
+        self.resource[cid] = self.resource[cid]
+            .saturating_add(CORPSE_RESOURCE)
+            .min(self.max_resource[cid]);
+        self.regen_rate[cid] = self.regen_rate[cid]
+            .saturating_add(CORPSE_REGEN_BONUS)
+            .min(self.max_regen_rate[cid]);
+    }
+
+    /// Steps every agent following the world's [`UpdateOrder`].
+    ///
+    /// Synchronous decisions are computed in parallel with the `parallel`
+    /// feature.
+    fn step_agents(&mut self) {
+        let len = self.alive.len();
+        let mut order = self.update_order.agent_order(len, &mut self.rng);
+        if self.update_order != UpdateOrder::Synchronous {
+            for id in order {
+                let target = self.decide(id);
+                self.apply(id, target);
+            }
+            return;
+        }
+
+        #[cfg(feature = "parallel")]
+        let decisions: Vec<_> = (0..len).into_par_iter().map(|id| self.decide(id)).collect();
+        #[cfg(not(feature = "parallel"))]
+        let decisions: Vec<_> = (0..len).map(|id| self.decide(id)).collect();
+        let health = &self.health_point;
+        self.conflict_rule
+            .serve_order(&mut order, |i| health[i], &mut self.rng);
+        for id in order {
+            self.apply(id, decisions[id]);
+        }
+    }
+}
+
+impl Updatable for SoaWorld {
+    /// Advances the world by one step: regeneration, allocation, then
+    /// movement and metabolism, in the same order as [`crate::World`].
+    ///
+    /// ### Returns
+    /// Always `Ok(())`; the columns cannot get out of sync.
+    fn update(&mut self) -> Result<(), SimulationError> {
+        self.regenerate();
+        self.allocate();
+        self.step_agents();
+        self.tick += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::World;
+
+    fn assert_same(soa: &SoaWorld, world: &World) {
+        let cells: Vec<u32> = world.cells().iter().map(|c| c.cur_resource()).collect();
+        assert_eq!(soa.resources(), cells.as_slice());
+        let agents: Vec<(u32, u32, bool)> = world
+            .agents()
+            .iter()
+            .map(|a| (a.cid() as u32, a.health_point(), a.is_alive()))
+            .collect();
+        let columns: Vec<(u32, u32, bool)> = (0..soa.alive().len())
+            .map(|i| (soa.agent_cells()[i], soa.health_points()[i], soa.alive()[i]))
+            .collect();
+        assert_eq!(columns, agents);
+    }
+
+    #[test]
+    fn matches_the_world_for_every_update_order() {
+        for (update_order, conflict_rule) in [
+            (UpdateOrder::Sequential, ConflictRule::FirstCome),
+            (UpdateOrder::Shuffled, ConflictRule::FirstCome),
+            (UpdateOrder::Synchronous, ConflictRule::Random),
+        ] {
+            let cfg = WorldConfig {
+                width: 25,
+                height: 20,
+                min_agents: 300,
+                max_agents: 400,
+                max_terrain_cost: 3,
+                seed: Some(9),
+                update_order,
+                conflict_rule,
+                ..WorldConfig::default()
+            };
+            let mut soa = SoaWorld::from_config(cfg).unwrap();
+            let mut world = World::from_config(cfg);
+            assert_same(&soa, &world);
+            // Past the tick where a compacting world would first diverge.
+            for _ in 0..120 {
+                soa.update().unwrap();
+                world.update().unwrap();
+                assert_same(&soa, &world);
+            }
+            let living = world.agents().iter().filter(|a| a.is_alive()).count();
+            assert_eq!(soa.living_agents(), living);
+        }
+    }
+
+    #[test]
+    fn index_follows_moves_and_deaths() {
+        let cfg = WorldConfig {
+            width: 10,
+            height: 10,
+            min_agents: 60,
+            max_agents: 60,
+            seed: Some(2),
+            ..WorldConfig::default()
+        };
+        let mut soa = SoaWorld::from_config(cfg).unwrap();
+        for _ in 0..15 {
+            soa.update().unwrap();
+            for cid in 0..100 {
+                let on_cell: Vec<usize> = soa.agents_on(cid).collect();
+                assert_eq!(on_cell.len() as u32, soa.occupancy(cid));
+                assert!(
+                    on_cell
+                        .iter()
+                        .all(|&a| soa.alive()[a] && soa.agent_cells()[a] as usize == cid)
+                );
+            }
+        }
+        let indexed: u32 = (0..100).map(|cid| soa.occupancy(cid)).sum();
+        assert_eq!(indexed as usize, soa.living_agents());
+    }
+
+    #[test]
+    fn rejects_unsupported_features() {
+        let vision = WorldConfig {
+            policy: PolicyKind::Vision,
+            ..WorldConfig::default()
+        };
+        let compacting = WorldConfig {
+            compaction_interval: Some(100),
+            ..WorldConfig::default()
+        };
+        for cfg in [vision, compacting] {
+            assert!(matches!(
+                SoaWorld::from_config(cfg),
+                Err(SimulationError::InvalidConfig(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use crate::Agent;
+use crate::perception;
+
+/// Which living agents stand on which cell.
+///
+/// The index stores positions in [`crate::World::agents`], sorted within
+/// each cell, so iterating a cell visits its agents in agent order. The
+/// world keeps it up to date as agents move, are born, die and get
+/// compacted, which makes cell and region queries independent of the
+/// total number of agents.
+///
+/// Living agents on cells outside the grid are kept aside as strays, so
+/// that the world can still report them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpatialIndex {
+    size: (usize, usize),
+    cells: Vec<Vec<usize>>,
+    strays: Vec<usize>,
+}
+
+impl SpatialIndex {
+    /// Builds the index for a set of agents.
+    ///
+    /// ### Parameters
+    /// - `size`: `(width, height)` of the grid.
+    /// - `agents`: The world's agents; dead ones are skipped.
+    ///
+    /// ### Returns
+    /// A [`SpatialIndex`] holding every living agent.
+    pub fn new(size: (usize, usize), agents: &[Agent]) -> Self {
+        let mut index = Self {
+            size,
+            cells: vec![Vec::new(); size.0 * size.1],
+            strays: Vec::new(),
+        };
+        for (i, agent) in agents.iter().enumerate() {
+            if agent.is_alive() {
+                index.insert(i, agent.cid());
+            }
+        }
+        index
+    }
+
+    /// Returns the agents on a cell.
+    ///
+    /// ### Parameters
+    /// - `cid`: Cell id in row-major indexing.
+    ///
+    /// ### Returns
+    /// Positions of the living agents on `cid` in ascending order; empty
+    /// for cells outside the grid.
+    pub fn in_cell(&self, cid: usize) -> &[usize] {
+        self.cells.get(cid).map_or(&[], |agents| agents)
+    }
+
+    /// Returns the living agents standing outside the grid.
+    ///
+    /// ### Returns
+    /// Their positions in ascending order.
+    pub fn strays(&self) -> &[usize] {
+        &self.strays
+    }
+
+    /// Returns the agents in a rectangle of cells.
+    ///
+    /// ### Parameters
+    /// - `min`: `(x, y)` of the top-left corner.
+    /// - `max`: `(x, y)` of the bottom-right corner, inclusive; clamped to
+    ///   the grid.
+    ///
+    /// ### Returns
+    /// Agent positions, row by row and cell by cell.
+    pub fn in_rect(
+        &self,
+        min: (usize, usize),
+        max: (usize, usize),
+    ) -> impl Iterator<Item = usize> + '_ {
+        let (width, height) = self.size;
+        let xs = min.0..=max.0.min(width.saturating_sub(1));
+        let ys = min.1..=max.1.min(height.saturating_sub(1));
+        ys.flat_map(move |y| xs.clone().map(move |x| y * width + x))
+            .flat_map(|cid| self.in_cell(cid).iter().copied())
+    }
+
+    /// Returns the agents within a Manhattan distance of a cell.
+    ///
+    /// ### Parameters
+    /// - `cid`: Cell id of the centre.
+    /// - `radius`: Largest distance included.
+    ///
+    /// ### Returns
+    /// Agent positions, row by row and cell by cell; nothing if `cid` is
+    /// outside the grid.
+    pub fn within(&self, cid: usize, radius: usize) -> impl Iterator<Item = usize> + '_ {
+        let (width, height) = self.size;
+        let centre = (cid < self.cells.len()).then(|| perception::coords(cid, width));
+        centre.into_iter().flat_map(move |(x, y)| {
+            let ys = y.saturating_sub(radius)..=y.saturating_add(radius).min(height - 1);
+            ys.flat_map(move |ny| {
+                let reach = radius - y.abs_diff(ny);
+                (x.saturating_sub(reach)..=x.saturating_add(reach).min(width - 1))
+                    .map(move |nx| ny * width + nx)
+            })
+            .flat_map(|nid| self.in_cell(nid).iter().copied())
+        })
+    }
+
+    /// Adds a living agent.
+    ///
+    /// ### Parameters
+    /// - `index`: Position of the agent in the world's agent list.
+    /// - `cid`: Cell the agent stands on.
+    pub(crate) fn insert(&mut self, index: usize, cid: usize) {
+        let list = self.cells.get_mut(cid).unwrap_or(&mut self.strays);
+        if let Err(at) = list.binary_search(&index) {
+            list.insert(at, index);
+        }
+    }
+
+    /// Removes an agent, e.g. because it died.
+    ///
+    /// ### Parameters
+    /// - `index`: Position of the agent in the world's agent list.
+    /// - `cid`: Cell the agent stands on.
+    pub(crate) fn remove(&mut self, index: usize, cid: usize) {
+        let list = self.cells.get_mut(cid).unwrap_or(&mut self.strays);
+        if let Ok(at) = list.binary_search(&index) {
+            list.remove(at);
+        }
+    }
+
+    /// Moves an agent between cells.
+    ///
+    /// ### Parameters
+    /// - `index`: Position of the agent in the world's agent list.
+    /// - `from`: Cell it left.
+    /// - `to`: Cell it entered.
+    pub(crate) fn relocate(&mut self, index: usize, from: usize, to: usize) {
+        self.remove(index, from);
+        self.insert(index, to);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agents(cids: &[usize]) -> Vec<Agent> {
+        cids.iter()
+            .enumerate()
+            .map(|(id, &cid)| Agent::new(id, cid, 1, 0, 5, true))
+            .collect()
+    }
+
+    #[test]
+    fn queries_cover_cells_rects_and_radii() {
+        // 3×3 grid, agents on cells 0, 4, 4, 8 and one outside.
+        let mut agents = agents(&[4, 0, 4, 8, 42]);
+        agents.push(Agent::new(5, 1, 1, 0, 0, false));
+        let index = SpatialIndex::new((3, 3), &agents);
+
+        assert_eq!(index.in_cell(4), &[0, 2]);
+        assert!(index.in_cell(1).is_empty());
+        assert!(index.in_cell(42).is_empty());
+        assert_eq!(index.strays(), &[4]);
+        assert_eq!(index.in_rect((0, 0), (1, 1)).collect::<Vec<_>>(), [1, 0, 2]);
+        assert_eq!(index.in_rect((1, 1), (9, 9)).count(), 3);
+        assert_eq!(index.within(4, 1).collect::<Vec<_>>(), [0, 2]);
+        assert_eq!(index.within(0, 4).count(), 4);
+        assert_eq!(index.within(8, usize::MAX).count(), 4);
+        assert_eq!(index.within(42, 1).count(), 0);
+    }
+
+    #[test]
+    fn updates_keep_cells_sorted() {
+        let mut index = SpatialIndex::new((2, 1), &agents(&[0, 0, 1]));
+        index.relocate(2, 1, 0);
+        assert_eq!(index.in_cell(0), &[0, 1, 2]);
+        index.remove(1, 0);
+        index.remove(1, 0);
+        assert_eq!(index.in_cell(0), &[0, 2]);
+        index.insert(7, 1);
+        assert_eq!(index.in_cell(1), &[7]);
+    }
+}
@@ -0,0 +1,229 @@
+use std::collections::HashSet;
+
+/// Settings of the territory layer.
+///
+/// When `enabled`, an agent that stays on a cell for `claim_after`
+/// consecutive ticks while the cell's owner is elsewhere becomes its new
+/// owner; unowned cells are claimed the same way. Ownership lapses when
+/// the owner dies or, if `abandon_after > 0`, after it has been away for
+/// that many ticks.
+///
+/// On its own territory an owner is fed first when `owner_priority` is
+/// set, and other agents pay `intrusion_cost` extra health points to
+/// enter a cell owned by someone else.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TerritoryConfig {
+    pub enabled: bool,
+    pub claim_after: u32,
+    pub abandon_after: u32,
+    pub owner_priority: bool,
+    pub intrusion_cost: u32,
+}
+
+impl TerritoryConfig {
+    /// Returns whether the settings are usable.
+    ///
+    /// ### Returns
+    /// `true` if claiming takes at least one tick.
+    pub fn is_valid(&self) -> bool {
+        self.claim_after > 0
+    }
+}
+
+impl Default for TerritoryConfig {
+    /// Returns a disabled configuration with a short claim time.
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            claim_after: 3,
+            abandon_after: 20,
+            owner_priority: true,
+            intrusion_cost: 1,
+        }
+    }
+}
+
+/// Ownership and claim progress of a single cell.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Claim {
+    owner: Option<usize>,
+    claimant: Option<usize>,
+    progress: u32,
+    absent: u32,
+}
+
+/// Which agent owns which cell.
+///
+/// Owners and claimants are agent ids (see [`crate::Agent::id`]).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TerritoryMap {
+    claims: Vec<Claim>,
+}
+
+impl TerritoryMap {
+    /// Creates a map without owners.
+    ///
+    /// ### Parameters
+    /// - `num_cells`: Number of cells in the grid.
+    ///
+    /// ### Returns
+    /// A [`TerritoryMap`] where no cell is owned.
+    pub fn new(num_cells: usize) -> Self {
+        Self {
+            claims: vec![Claim::default(); num_cells],
+        }
+    }
+
+    /// Returns the owner of a cell.
+    ///
+    /// ### Parameters
+    /// - `cid`: Cell id in row-major indexing.
+    ///
+    /// ### Returns
+    /// - `Some(agent_id)` if the cell is owned.
+    /// - `None` if it is unowned or outside the grid.
+    pub fn owner(&self, cid: usize) -> Option<usize> {
+        self.claims.get(cid).and_then(|c| c.owner)
+    }
+
+    /// Returns the owner of every cell.
+    ///
+    /// ### Returns
+    /// An iterator over owners in row-major cell order.
+    pub fn owners(&self) -> impl Iterator<Item = Option<usize>> + '_ {
+        self.claims.iter().map(|c| c.owner)
+    }
+
+    /// Returns the number of cells owned by an agent.
+    ///
+    /// ### Parameters
+    /// - `agent`: Id of the agent.
+    ///
+    /// ### Returns
+    /// The size of the agent's territory.
+    pub fn territory_size(&self, agent: usize) -> usize {
+        self.claims
+            .iter()
+            .filter(|c| c.owner == Some(agent))
+            .count()
+    }
+
+    /// Advances ownership by one tick.
+    ///
+    /// For every cell:
+    /// - ownership lapses if the owner is dead, or has been away for
+    ///   `abandon_after` ticks
+    /// - if the owner is present, pending claims are reset
+    /// - otherwise the first present agent (in `occupants` order) becomes
+    ///   or stays the claimant; after `claim_after` ticks it becomes owner
+    ///
+    /// ### Parameters
+    /// - `cfg`: Claim and abandon times.
+    /// - `occupants`: Ids of the living agents on each cell.
+    /// - `living`: Ids of all living agents.
+    ///
+    /// ### Returns
+    /// `(cell_id, agent_id)` for every cell that changed hands this tick.
+    pub fn update(
+        &mut self,
+        cfg: &TerritoryConfig,
+        occupants: &[Vec<usize>],
+        living: &HashSet<usize>,
+    ) -> Vec<(usize, usize)> {
+        let mut claimed = Vec::new();
+        for (cid, (claim, present)) in self.claims.iter_mut().zip(occupants).enumerate() {
+            if let Some(owner) = claim.owner {
+                if present.contains(&owner) {
+                    claim.absent = 0;
+                    claim.claimant = None;
+                    claim.progress = 0;
+                    continue;
+                }
+                claim.absent += 1;
+                if !living.contains(&owner)
+                    || (cfg.abandon_after > 0 && claim.absent >= cfg.abandon_after)
+                {
+                    claim.owner = None;
+                    claim.absent = 0;
+                }
+            }
+
+            match present.first() {
+                None => {
+                    claim.claimant = None;
+                    claim.progress = 0;
+                }
+                Some(&first) => {
+                    let stays = claim.claimant.is_some_and(|c| present.contains(&c));
+                    if !stays {
+                        claim.claimant = Some(first);
+                        claim.progress = 0;
+                    }
+                    claim.progress += 1;
+                    if claim.progress >= cfg.claim_after {
+                        let new_owner = claim.claimant.unwrap_or(first);
+                        claim.owner = Some(new_owner);
+                        claim.claimant = None;
+                        claim.progress = 0;
+                        claim.absent = 0;
+                        claimed.push((cid, new_owner));
+                    }
+                }
+            }
+        }
+        claimed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cfg(claim_after: u32, abandon_after: u32) -> TerritoryConfig {
+        TerritoryConfig {
+            enabled: true,
+            claim_after,
+            abandon_after,
+            ..TerritoryConfig::default()
+        }
+    }
+
+    #[test]
+    fn staying_claims_a_cell() {
+        let mut map = TerritoryMap::new(2);
+        let living = HashSet::from([7]);
+        let occupants = vec![vec![7], vec![]];
+
+        assert!(map.update(&cfg(2, 0), &occupants, &living).is_empty());
+        assert_eq!(map.update(&cfg(2, 0), &occupants, &living), vec![(0, 7)]);
+        assert_eq!(map.owner(0), Some(7));
+        assert_eq!(map.owner(1), None);
+        assert_eq!(map.territory_size(7), 1);
+    }
+
+    #[test]
+    fn absent_owners_lose_cells_to_intruders_and_time() {
+        let mut map = TerritoryMap::new(2);
+        let living = HashSet::from([1, 2]);
+        map.update(&cfg(1, 3), &[vec![1], vec![1]], &living);
+        assert_eq!(map.owner(0), Some(1));
+
+        // An intruder stays while the owner is away.
+        map.update(&cfg(1, 3), &[vec![2], vec![]], &living);
+        assert_eq!(map.owner(0), Some(2));
+
+        // Cell 1 is abandoned after three ticks without its owner.
+        map.update(&cfg(1, 3), &[vec![], vec![]], &living);
+        assert_eq!(map.owner(1), Some(1));
+        map.update(&cfg(1, 3), &[vec![], vec![]], &living);
+        assert_eq!(map.owner(1), None);
+    }
+
+    #[test]
+    fn dead_owners_lose_their_territory() {
+        let mut map = TerritoryMap::new(1);
+        map.update(&cfg(1, 0), &[vec![4]], &HashSet::from([4]));
+        map.update(&cfg(1, 0), &[vec![]], &HashSet::new());
+        assert_eq!(map.owner(0), None);
+    }
+}
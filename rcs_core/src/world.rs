@@ -1,7 +1,33 @@
-use crate::errors::SimulationError;
+use crate::combat::CombatConfig;
+use crate::disease::{DiseaseConfig, HealthState};
+use crate::errors::{Entity, SimulationError};
+use crate::evolution::{EvolutionConfig, Genome};
+use crate::groups::{GroupConfig, GroupStatistics};
+use crate::hooks::WorldHook;
+use crate::ids::AgentIds;
+use crate::ledger::{self, ResourceLedger};
+use crate::lifecycle::{AgingConfig, DeathCause, GraveRecord, Statistics, WorldEvent};
+use crate::perception::{self, Perception, PerceptionConfig, VisibleAgent, VisibleCell};
+use crate::pheromone::{PheromoneConfig, PheromoneField};
+use crate::policy::{GreedyPolicy, MoveOutcome, MovementPolicy, PolicyKind};
+use crate::rl::{Action, Observation, RewardConfig, StepResult};
+use crate::schedule::{ConflictRule, UpdateOrder};
+use crate::spatial::SpatialIndex;
+use crate::territory::{TerritoryConfig, TerritoryMap};
 use crate::traits::Updatable;
 use crate::{Agent, Cell};
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::ops::{Deref, DerefMut};
+
+/// Resource a dead agent returns to its cell.
+pub(crate) const CORPSE_RESOURCE: u32 = 5;
+
+/// Regeneration-rate bonus a dead agent gives its cell.
+pub(crate) const CORPSE_REGEN_BONUS: u32 = 1;
 
 /// Configuration for constructing a randomized world.
 ///
@@ -9,9 +35,24 @@ use rand::Rng;
 /// - grid size (`width`, `height`)
 /// - initial resource range for each cell
 /// - regeneration-rate range for each cell
+/// - terrain-cost range for each cell (health points paid to enter it)
 /// - number of agents to spawn
 /// - per-agent consumption-rate range
 /// - initial hit points for each agent
+/// - the random seed (`None` draws one from the OS)
+/// - the order in which agents are stepped
+/// - an optional per-cell agent capacity and the rule for contested cells
+/// - the agents' perception settings and movement policy
+/// - how many visited cells each agent remembers (`0` disables memory)
+/// - the pheromone deposit, evaporation and diffusion rates
+/// - the evolutionary mode (energy, reproduction and genome mutation)
+/// - agent aging (maximum lifespan and age-dependent consumption)
+/// - the optional epidemic layer
+/// - the optional territory layer (cell ownership)
+/// - the optional contest mode (fights over scarce cells)
+/// - the optional group layer (food sharing between group members)
+/// - how often dead agents are compacted away (`None`, the default, keeps
+///   them)
 ///
 /// The world is generated by [`World::from_config`].
 #[derive(Debug, Clone, Copy)]
@@ -22,11 +63,105 @@ pub struct WorldConfig {
     pub max_resource: u32,
     pub min_regen_rate: u32,
     pub max_regen_rate: u32,
+    pub min_terrain_cost: u32,
+    pub max_terrain_cost: u32,
     pub min_agents: usize,
     pub max_agents: usize,
     pub min_consumption_rate: u32,
     pub max_consumption_rate: u32,
     pub agent_hp: u32,
+    pub seed: Option<u64>,
+    pub update_order: UpdateOrder,
+    pub cell_capacity: Option<usize>,
+    pub conflict_rule: ConflictRule,
+    pub perception: PerceptionConfig,
+    pub policy: PolicyKind,
+    pub agent_memory: usize,
+    pub pheromone: PheromoneConfig,
+    pub evolution: EvolutionConfig,
+    pub aging: AgingConfig,
+    pub disease: DiseaseConfig,
+    pub territory: TerritoryConfig,
+    pub combat: CombatConfig,
+    pub groups: GroupConfig,
+    pub compaction_interval: Option<u64>,
+}
+
+impl WorldConfig {
+    /// Checks that the configuration describes a valid world.
+    ///
+    /// ### Returns
+    /// - `Ok(())` if all ranges are consistent.
+    /// - `Err(SimulationError::InvalidConfig(..))` naming the first broken rule.
+    pub fn validate(&self) -> Result<(), SimulationError> {
+        let rules = [
+            (self.width > 0 && self.height > 0, "world size must be > 0"),
+            (
+                self.min_resource <= self.max_resource,
+                "min_resource <= max_resource",
+            ),
+            (
+                self.min_regen_rate <= self.max_regen_rate,
+                "min_regen_rate <= max_regen_rate",
+            ),
+            (
+                self.min_terrain_cost > 0 && self.min_terrain_cost <= self.max_terrain_cost,
+                "0 < min_terrain_cost <= max_terrain_cost",
+            ),
+            (
+                self.min_agents > 0 && self.min_agents <= self.max_agents,
+                "0 < min_agents <= max_agents",
+            ),
+            (
+                self.min_consumption_rate <= self.max_consumption_rate,
+                "min_consumption_rate <= max_consumption_rate",
+            ),
+            (self.cell_capacity != Some(0), "cell_capacity must be > 0"),
+            (
+                self.compaction_interval != Some(0),
+                "compaction_interval must be > 0",
+            ),
+            (self.perception.radius > 0, "perception radius must be > 0"),
+            (
+                self.pheromone.is_valid(),
+                "pheromone deposit must be >= 0 and rates in [0, 1]",
+            ),
+            (
+                !self.aging.enabled || self.aging.is_valid(),
+                "aging needs 0 < min_lifespan <= max_lifespan",
+            ),
+            (
+                !self.disease.enabled || self.disease.is_valid(),
+                "disease needs transmission in [0, 1] and duration > 0",
+            ),
+            (
+                !self.territory.enabled || self.territory.is_valid(),
+                "territory needs claim_after > 0",
+            ),
+            (
+                !self.combat.enabled || self.combat.is_valid(),
+                "combat needs 0 <= min_aggression <= max_aggression <= 1",
+            ),
+            (
+                !self.groups.enabled || self.groups.is_valid(),
+                "groups need at least one group and altruistic_groups <= groups",
+            ),
+            (
+                !self.evolution.enabled || self.evolution.is_valid(),
+                "evolution needs hidden units, birth cost and child hp > 0 and mutation rate in [0, 1]",
+            ),
+            (
+                self.cell_capacity.is_none_or(|cap| {
+                    self.max_agents <= self.width.saturating_mul(self.height).saturating_mul(cap)
+                }),
+                "max_agents must fit into width * height * cell_capacity",
+            ),
+        ];
+        match rules.iter().find(|(ok, _)| !ok) {
+            Some((_, reason)) => Err(SimulationError::InvalidConfig((*reason).to_string())),
+            None => Ok(()),
+        }
+    }
 }
 
 impl Default for WorldConfig {
@@ -42,11 +177,28 @@ impl Default for WorldConfig {
             max_resource: 20,
             min_regen_rate: 0,
             max_regen_rate: 3,
+            min_terrain_cost: 1,
+            max_terrain_cost: 1,
             min_agents: 1,
             max_agents: 50,
             min_consumption_rate: 1,
             max_consumption_rate: 5,
             agent_hp: 3,
+            seed: None,
+            update_order: UpdateOrder::Sequential,
+            cell_capacity: None,
+            conflict_rule: ConflictRule::FirstCome,
+            perception: PerceptionConfig::default(),
+            policy: PolicyKind::Greedy,
+            agent_memory: 0,
+            pheromone: PheromoneConfig::default(),
+            evolution: EvolutionConfig::default(),
+            aging: AgingConfig::default(),
+            disease: DiseaseConfig::default(),
+            territory: TerritoryConfig::default(),
+            combat: CombatConfig::default(),
+            groups: GroupConfig::default(),
+            compaction_interval: None,
         }
     }
 }
@@ -57,12 +209,55 @@ impl Default for WorldConfig {
 /// - a fixed `size` in cells
 /// - a flat vector of [`Cell`]s
 /// - a vector of [`Agent`]s
+/// - the number of completed update steps (`tick`)
+/// - optional debug state: a per-tick [`ResourceLedger`] and invariant checks
+/// - a seeded random number generator and the agent [`UpdateOrder`]
+/// - the [`MovementPolicy`] deciding where agents move
+/// - an optional per-cell agent capacity with its [`ConflictRule`]
+/// - the [`PerceptionConfig`] shared by all agents
+/// - the optional layers: pheromones ([`PheromoneConfig`] and the
+///   [`PheromoneField`]), evolution, aging, disease, territory (with the
+///   [`TerritoryMap`] of owners), combat and groups
+/// - the [`AgentIds`] that keep agent ids stable, and the graveyard of
+///   [`GraveRecord`]s filled every `compaction_interval` ticks
+/// - the registered [`WorldHook`]s, the [`WorldEvent`]s of the last tick
+///   and the running [`Statistics`]
 ///
 /// Use [`World::from_config`] to generate a random world
 pub struct World {
     size: (usize, usize),
     cells: Vec<Cell>,
     agents: Vec<Agent>,
+    tick: u64,
+    rng: StdRng,
+    update_order: UpdateOrder,
+    policy: Box<dyn MovementPolicy>,
+    actions: Option<HashMap<usize, Action>>,
+    cell_capacity: Option<usize>,
+    conflict_rule: ConflictRule,
+    spatial: SpatialIndex,
+    perception: PerceptionConfig,
+    pheromone: PheromoneConfig,
+    pheromones: PheromoneField,
+    evolution: EvolutionConfig,
+    aging: AgingConfig,
+    disease: DiseaseConfig,
+    territory: TerritoryConfig,
+    territories: TerritoryMap,
+    combat: CombatConfig,
+    groups: GroupConfig,
+    ids: AgentIds,
+    graveyard: Vec<GraveRecord>,
+    graves: HashMap<usize, usize>,
+    compaction_interval: Option<u64>,
+    mid_tick: bool,
+    hooks: Vec<Box<dyn WorldHook>>,
+    events: Vec<WorldEvent>,
+    statistics: Statistics,
+    debug_checks: bool,
+    ledger: Option<ResourceLedger>,
+    #[cfg(feature = "parallel")]
+    parallel: bool,
 }
 
 impl World {
@@ -73,13 +268,60 @@ impl World {
     /// - `cells`: Flat list of cells; its length should be `width * height`.
     /// - `agents`: Initial set of agents in the world.
     ///
+    /// The random number generator is seeded from the OS; call
+    /// [`World::set_seed`] for reproducible runs. Agents keep their ids,
+    /// except that an id skipping more than [`crate::ids::MAX_SLOT_GAP`]
+    /// unused slots is replaced with a fresh one.
+    ///
     /// ### Returns
     /// A [`World`] instance using the provided data.
-    pub fn new(size: (usize, usize), cells: Vec<Cell>, agents: Vec<Agent>) -> Self {
+    pub fn new(size: (usize, usize), cells: Vec<Cell>, mut agents: Vec<Agent>) -> Self {
+        let num_cells = cells.len();
+        let spatial = SpatialIndex::new(size, &agents);
+        let mut ids = AgentIds::default();
+        let mut by_id: Vec<usize> = (0..agents.len()).collect();
+        by_id.sort_by_key(|&index| agents[index].id());
+        let refused: Vec<usize> = by_id
+            .into_iter()
+            .filter(|&index| ids.insert(agents[index].id(), index).is_err())
+            .collect();
+        for index in refused {
+            agents[index].set_id(ids.allocate(index));
+        }
         Self {
             size,
             cells,
             agents,
+            tick: 0,
+            rng: StdRng::from_entropy(),
+            update_order: UpdateOrder::default(),
+            policy: Box::new(GreedyPolicy),
+            actions: None,
+            cell_capacity: None,
+            conflict_rule: ConflictRule::default(),
+            spatial,
+            perception: PerceptionConfig::default(),
+            pheromone: PheromoneConfig::default(),
+            pheromones: PheromoneField::new(size),
+            evolution: EvolutionConfig::default(),
+            aging: AgingConfig::default(),
+            disease: DiseaseConfig::default(),
+            territory: TerritoryConfig::default(),
+            territories: TerritoryMap::new(num_cells),
+            combat: CombatConfig::default(),
+            groups: GroupConfig::default(),
+            ids,
+            graveyard: Vec::new(),
+            graves: HashMap::new(),
+            compaction_interval: None,
+            mid_tick: false,
+            hooks: Vec::new(),
+            events: Vec::new(),
+            statistics: Statistics::default(),
+            debug_checks: false,
+            ledger: None,
+            #[cfg(feature = "parallel")]
+            parallel: true,
         }
     }
 
@@ -110,262 +352,2033 @@ impl World {
         &self.cells[cid]
     }
 
-    /// Returns an immutable view of all agents.
+    /// Returns a mutable reference to a single cell by id.
+    ///
+    /// This is meant for [`WorldHook`]s and tools that edit the world
+    /// between ticks.
+    ///
+    /// ### Parameters
+    /// - `cid`: Cell id in row-major indexing.
     ///
     /// ### Returns
-    /// A slice of all agents.
-    pub fn agents(&self) -> &[Agent] {
-        &self.agents
+    /// A mutable reference to the cell at `cid`.
+    pub fn cell_mut(&mut self, cid: usize) -> &mut Cell {
+        &mut self.cells[cid]
     }
 
-    /// Constructs a randomized world from a configuration.
+    /// Returns a single cell by id, checking that the id is valid.
     ///
-    /// This method:
-    /// - creates a `width × height` grid of cells, each with
-    ///   random starting resources and regeneration rate
-    /// - spawns a random number of agents between `min_agents` and `max_agents`,
-    ///   each placed at a random cell with random consumption rate
+    /// ### Parameters
+    /// - `cid`: Cell id in row-major indexing.
+    ///
+    /// ### Returns
+    /// - `Ok(&Cell)` if `cid` is inside the grid.
+    /// - `Err(SimulationError::InvalidCellId { .. })` otherwise.
+    pub fn try_cell(&self, cid: usize) -> Result<&Cell, SimulationError> {
+        self.cells.get(cid).ok_or(SimulationError::InvalidCellId {
+            cid,
+            num_cells: self.cells.len(),
+        })
+    }
+
+    /// Returns the number of update steps completed so far.
     ///
-    /// Internal `assert!`s ensure configuration sanity, so invalid configs
-    /// will panic during development rather than silently misbehaving.
+    /// ### Returns
+    /// The current tick counter, starting at `0` for a fresh world.
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Reseeds the world's random number generator.
     ///
     /// ### Parameters
-    /// - `cfg`: World generation parameters.
+    /// - `seed`: Seed for all randomness used by later updates.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    /// Returns the order in which agents are stepped.
     ///
     /// ### Returns
-    /// A new [`World`] sampled according to `cfg`.
-    pub fn from_config(cfg: WorldConfig) -> Self {
-        assert!(cfg.width > 0 && cfg.height > 0, "world size must be > 0");
-        assert!(
-            cfg.min_resource <= cfg.max_resource,
-            "min_resource <= max_resource"
-        );
-        assert!(
-            cfg.min_regen_rate <= cfg.max_regen_rate,
-            "min_regen_rate <= max_regen_rate"
-        );
-        assert!(
-            cfg.min_agents > 0 && cfg.min_agents <= cfg.max_agents,
-            "0 < min_agents <= max_agents"
-        );
-        assert!(
-            cfg.min_consumption_rate <= cfg.max_consumption_rate,
-            "min_consumption_rate <= max_consumption_rate"
-        );
+    /// The current [`UpdateOrder`].
+    pub fn update_order(&self) -> UpdateOrder {
+        self.update_order
+    }
 
-        let mut cells = Vec::with_capacity(cfg.width * cfg.height);
+    /// Selects the order in which agents are stepped.
+    ///
+    /// ### Parameters
+    /// - `order`: The [`UpdateOrder`] used by later updates.
+    pub fn set_update_order(&mut self, order: UpdateOrder) {
+        self.update_order = order;
+    }
 
-        let mut rng = rand::thread_rng();
+    /// Returns the movement policy used by the agents.
+    ///
+    /// ### Returns
+    /// A reference to the current [`MovementPolicy`].
+    pub fn policy(&self) -> &dyn MovementPolicy {
+        self.policy.as_ref()
+    }
 
-        for y in 0..cfg.height {
-            for x in 0..cfg.width {
-                let id = y * cfg.width + x;
-                let rand_resource = rng.gen_range(cfg.min_resource..=cfg.max_resource);
-                let rand_regen_rate = rng.gen_range(cfg.min_regen_rate..=cfg.max_regen_rate);
+    /// Replaces the movement policy used by the agents.
+    ///
+    /// ### Parameters
+    /// - `policy`: The [`MovementPolicy`] used by later updates.
+    pub fn set_policy(&mut self, policy: Box<dyn MovementPolicy>) {
+        self.policy = policy;
+    }
 
-                cells.push(Cell::new(
-                    id,
-                    rand_resource,
-                    cfg.max_resource,
-                    rand_regen_rate,
-                    cfg.max_regen_rate,
-                ));
-            }
-        }
+    /// Registers a hook that is called during every later update.
+    ///
+    /// Hooks run in registration order (see [`WorldHook`]).
+    ///
+    /// ### Parameters
+    /// - `hook`: The [`WorldHook`] to add.
+    pub fn add_hook(&mut self, hook: Box<dyn WorldHook>) {
+        self.hooks.push(hook);
+    }
 
-        let num_agents = rng.gen_range(cfg.min_agents..=cfg.max_agents);
-        let mut agents = Vec::with_capacity(num_agents);
+    /// Removes all registered hooks.
+    ///
+    /// While a hook callback runs, the registered hooks are taken out of the
+    /// world. Called from inside a callback, this therefore only removes the
+    /// hooks added during that callback; call it between ticks to remove
+    /// the others.
+    ///
+    /// ### Returns
+    /// The removed hooks, in registration order.
+    pub fn clear_hooks(&mut self) -> Vec<Box<dyn WorldHook>> {
+        std::mem::take(&mut self.hooks)
+    }
 
-        for id in 0..num_agents {
-            let rand_x = rng.gen_range(0..cfg.width);
-            let rand_y = rng.gen_range(0..cfg.height);
-            let cid = rand_y * cfg.width + rand_x;
-            let rand_consumption_rate =
-                rng.gen_range(cfg.min_consumption_rate..=cfg.max_consumption_rate);
+    /// Returns the number of registered hooks.
+    ///
+    /// ### Returns
+    /// How many hooks [`World::add_hook`] has registered.
+    pub fn hook_count(&self) -> usize {
+        self.hooks.len()
+    }
 
-            agents.push(Agent::new(
-                id,
-                cid,
-                rand_consumption_rate,
-                0,
-                cfg.agent_hp,
-                true,
-            ));
+    /// Calls every hook with mutable access to the world.
+    ///
+    /// The hooks are taken out of the world for the duration of the call;
+    /// hooks registered meanwhile are kept after the existing ones. In the
+    /// middle of a recorded tick, resource the hooks add to or take from
+    /// the cells is entered in the ledger.
+    ///
+    /// ### Parameters
+    /// - `call`: Invokes one callback on one hook.
+    fn run_hooks(&mut self, mut call: impl FnMut(&mut dyn WorldHook, &mut World)) {
+        if self.hooks.is_empty() {
+            return;
+        }
+        let recorded = self
+            .ledger
+            .filter(|_| self.mid_tick)
+            .map(|ledger| (ledger::total_resource(&self.cells), ledger.expected_total()));
+        let mut hooks = std::mem::take(&mut self.hooks);
+        for hook in &mut hooks {
+            call(hook.as_mut(), self);
+        }
+        hooks.append(&mut self.hooks);
+        self.hooks = hooks;
+        if let (Some((total, expected)), Some(ledger)) = (recorded, &mut self.ledger) {
+            let actual = ledger::total_resource(&self.cells) as i64 - total as i64;
+            let explained = ledger.expected_total() as i64 - expected as i64;
+            ledger.record_hook(actual - explained);
+        }
+    }
+
+    /// Calls every hook with read access to the world.
+    ///
+    /// ### Parameters
+    /// - `call`: Invokes one callback on one hook.
+    fn notify_hooks(&mut self, mut call: impl FnMut(&mut dyn WorldHook, &World)) {
+        if self.hooks.is_empty() {
+            return;
+        }
+        let mut hooks = std::mem::take(&mut self.hooks);
+        for hook in &mut hooks {
+            call(hook.as_mut(), self);
         }
+        self.hooks = hooks;
+    }
 
-        World::new((cfg.width, cfg.height), cells, agents)
+    /// Returns the maximum number of living agents per cell.
+    ///
+    /// ### Returns
+    /// - `Some(capacity)` if cells have a limit.
+    /// - `None` if any number of agents may share a cell.
+    pub fn cell_capacity(&self) -> Option<usize> {
+        self.cell_capacity
     }
 
-    /// Returns `(cell_id, resource)` pairs for all neighbors of `cid`.
+    /// Sets the maximum number of living agents per cell.
     ///
-    /// The neighbors are in the four cardinal directions (up, down, left, right),
-    /// clipped to the bounds of the grid.
+    /// Agents already sharing a cell are not evicted; the limit only
+    /// applies to later moves.
     ///
     /// ### Parameters
-    /// - `cid`: Id of the central cell.
+    /// - `capacity`: `Some(limit)` to cap cells, `None` to remove the cap.
+    pub fn set_cell_capacity(&mut self, capacity: Option<usize>) {
+        self.cell_capacity = capacity;
+    }
+
+    /// Returns the rule used to resolve simultaneous requests for full cells.
     ///
     /// ### Returns
-    /// A `Vec<(cell_id, resource)>` describing the neighbors.
-    fn neighbor_cells_info(&self, cid: usize) -> Vec<(usize, u32)> {
-        let (width, height) = self.size;
-        let x = cid % width;
-        let y = cid / width;
+    /// The current [`ConflictRule`].
+    pub fn conflict_rule(&self) -> ConflictRule {
+        self.conflict_rule
+    }
 
-        let mut neighbors = Vec::with_capacity(4);
+    /// Sets the rule used to resolve simultaneous requests for full cells.
+    ///
+    /// ### Parameters
+    /// - `rule`: The [`ConflictRule`] used by later updates.
+    pub fn set_conflict_rule(&mut self, rule: ConflictRule) {
+        self.conflict_rule = rule;
+    }
 
-        if y > 0 {
-            let ny = y - 1;
-            let nid = ny * width + x;
-            neighbors.push((nid, self.cells[nid].cur_resource()));
-        }
+    /// Returns the perception settings of the agents.
+    ///
+    /// ### Returns
+    /// The current [`PerceptionConfig`].
+    pub fn perception(&self) -> PerceptionConfig {
+        self.perception
+    }
 
-        if y + 1 < height {
-            let ny = y + 1;
-            let nid = ny * width + x;
-            neighbors.push((nid, self.cells[nid].cur_resource()));
+    /// Changes the perception settings of the agents.
+    ///
+    /// Every agent's upkeep is set to [`PerceptionConfig::upkeep`], so a
+    /// wider view makes agents hungrier when perception has a cost.
+    ///
+    /// ### Parameters
+    /// - `perception`: The [`PerceptionConfig`] used from now on.
+    pub fn set_perception(&mut self, perception: PerceptionConfig) {
+        self.perception = perception;
+        let upkeep = perception.upkeep();
+        for agent in &mut self.agents {
+            agent.set_upkeep(upkeep);
         }
+    }
 
-        if x > 0 {
-            let nx = x - 1;
-            let nid = y * width + nx;
-            neighbors.push((nid, self.cells[nid].cur_resource()));
-        }
+    /// Returns the pheromone settings.
+    ///
+    /// ### Returns
+    /// The current [`PheromoneConfig`].
+    pub fn pheromone(&self) -> PheromoneConfig {
+        self.pheromone
+    }
 
-        if x + 1 < width {
-            let nx = x + 1;
-            let nid = y * width + nx;
-            neighbors.push((nid, self.cells[nid].cur_resource()));
-        }
+    /// Changes the pheromone settings used by later updates.
+    ///
+    /// ### Parameters
+    /// - `pheromone`: The [`PheromoneConfig`] used from now on.
+    pub fn set_pheromone(&mut self, pheromone: PheromoneConfig) {
+        self.pheromone = pheromone;
+    }
 
-        neighbors
+    /// Returns the pheromone concentration of every cell.
+    ///
+    /// ### Returns
+    /// The world's [`PheromoneField`].
+    pub fn pheromones(&self) -> &PheromoneField {
+        &self.pheromones
     }
 
-    /// Allocates resources from each cell to the agents currently on it.
+    /// Adds pheromone to a cell, e.g. to seed a trail.
     ///
-    /// The algorithm:
-    /// 1. Collects living agents per cell.
-    /// 2. Splits the cell's current resource equally among local agents.
-    /// 3. Each agent calls [`Agent::retrieve_resource`] with its share.
-    /// 4. Any unused resource is returned to the cell via [`Cell::take_up_to`].
-    fn allocate_resources(&mut self) {
-        let mut cell_to_agents: Vec<Vec<usize>> = vec![Vec::new(); self.cells.len()];
-        for (i, agent) in self.agents.iter().enumerate() {
-            if !agent.is_alive() {
-                continue;
-            }
-            let cid = agent.cid();
-            cell_to_agents[cid].push(i);
-        }
+    /// ### Parameters
+    /// - `cid`: Cell id in row-major indexing.
+    /// - `amount`: Pheromone to add.
+    pub fn deposit_pheromone(&mut self, cid: usize, amount: f32) {
+        self.pheromones.deposit(cid, amount);
+    }
 
-        for (cid, agent_indices) in cell_to_agents.iter().enumerate() {
-            if agent_indices.is_empty() {
-                continue;
-            }
+    /// Returns the settings of the evolutionary mode.
+    ///
+    /// ### Returns
+    /// The current [`EvolutionConfig`].
+    pub fn evolution(&self) -> EvolutionConfig {
+        self.evolution
+    }
 
-            let total = self.cells[cid].cur_resource();
-            if total == 0 {
-                continue;
-            }
+    /// Changes the settings of the evolutionary mode.
+    ///
+    /// Every agent's appetite is set to [`EvolutionConfig::appetite`] if
+    /// the mode is enabled, and to `0` otherwise. Existing genomes are kept.
+    ///
+    /// ### Parameters
+    /// - `evolution`: The [`EvolutionConfig`] used from now on.
+    pub fn set_evolution(&mut self, evolution: EvolutionConfig) {
+        self.evolution = evolution;
+        let appetite = if evolution.enabled {
+            evolution.appetite
+        } else {
+            0
+        };
+        for agent in &mut self.agents {
+            agent.set_appetite(appetite);
+        }
+    }
 
-            let n = agent_indices.len() as u32;
-            let base_share = total / n;
-            let mut remaining = total - base_share * n;
+    /// Returns the aging settings.
+    ///
+    /// ### Returns
+    /// The current [`AgingConfig`].
+    pub fn aging(&self) -> AgingConfig {
+        self.aging
+    }
 
-            for &i in agent_indices {
-                let leftover = self.agents[i].retrieve_resource(base_share);
-                remaining += leftover;
+    /// Changes the aging settings and applies them to every agent.
+    ///
+    /// If aging is enabled, each agent draws a new lifespan from
+    /// `[min_lifespan, max_lifespan]` and gets the configured senescence;
+    /// agents already past their new lifespan die of old age at their next
+    /// metabolism. If aging is disabled, lifespans are removed.
+    ///
+    /// ### Parameters
+    /// - `aging`: The [`AgingConfig`] used from now on.
+    pub fn set_aging(&mut self, aging: AgingConfig) {
+        self.aging = aging;
+        for agent in &mut self.agents {
+            if aging.enabled && aging.is_valid() {
+                let lifespan = self.rng.gen_range(aging.min_lifespan..=aging.max_lifespan);
+                agent.set_lifespan(Some(lifespan));
+                agent.set_senescence(aging.senescence);
+            } else {
+                agent.set_lifespan(None);
+                agent.set_senescence(0);
             }
-            let spent = total - remaining;
-            let _ = self.cells[cid].take_up_to(spent);
         }
     }
 
-    /// Handles side effects when an agent dies on a given cell.
+    /// Returns the settings of the epidemic layer.
     ///
-    /// Currently this:
-    /// - returns a fixed amount of resource to the cell
-    /// - slightly boosts that cell's regeneration rate
+    /// ### Returns
+    /// The current [`DiseaseConfig`].
+    pub fn disease(&self) -> DiseaseConfig {
+        self.disease
+    }
+
+    /// Changes the settings of the epidemic layer used by later updates.
+    ///
+    /// Agents that are already infected keep their current infection.
     ///
     /// ### Parameters
-    /// - `id`: Index of the dead agent in `self.agents`.
-    fn handle_agent_death(&mut self, id: usize) {
-        let cid = self.agents[id].cid();
-        let corpse_resource: u32 = 5;
-        let regen_bonus: u32 = 1;
+    /// - `disease`: The [`DiseaseConfig`] used from now on.
+    pub fn set_disease(&mut self, disease: DiseaseConfig) {
+        self.disease = disease;
+    }
 
-        self.cells[cid].add_resource(corpse_resource);
-        self.cells[cid].increase_rate(regen_bonus);
+    /// Returns whether the parallel stepping path is used.
+    ///
+    /// ### Returns
+    /// `true` by default when the `parallel` feature is enabled.
+    #[cfg(feature = "parallel")]
+    pub fn parallel(&self) -> bool {
+        self.parallel
     }
 
-    /// Advances a single agent by one simulation step.
+    /// Switches between the parallel and the sequential stepping path.
     ///
-    /// The logic for a living agent:
-    /// - if hungry, look at neighbor cells and possibly move toward it
-    /// - then call [`Agent::update`] (metabolism)
-    /// - if the agent is dead after movement or metabolism, call
-    ///   [`World::handle_agent_death`]
+    /// Both paths produce identical results for the same seed; only
+    /// cell regeneration, allocation planning and synchronous move
+    /// decisions run in parallel, while everything that draws random
+    /// numbers stays sequential.
     ///
-    /// Dead agents are ignored.
+    /// With [`UpdateOrder::Sequential`] and [`UpdateOrder::Shuffled`] every
+    /// agent decides after the moves of the agents before it, so move
+    /// decisions stay sequential whatever this flag says. Large worlds gain
+    /// most from the parallel path with [`UpdateOrder::Synchronous`].
     ///
     /// ### Parameters
-    /// - `id`: Index of the agent to update.
-    fn step_agent(&mut self, id: usize) {
-        if !self.agents[id].is_alive() {
-            return;
-        }
+    /// - `parallel`: Whether to use rayon for the parallel phases.
+    #[cfg(feature = "parallel")]
+    pub fn set_parallel(&mut self, parallel: bool) {
+        self.parallel = parallel;
+    }
 
-        if self.agents[id].is_hungry() {
-            let cid = self.agents[id].cid();
-            let neighbors = self.neighbor_cells_info(cid);
+    /// Returns the settings of the contest mode.
+    ///
+    /// ### Returns
+    /// The current [`CombatConfig`].
+    pub fn combat(&self) -> CombatConfig {
+        self.combat
+    }
 
-            if let Some(target_cid) = self.agents[id].decide_move(&neighbors) {
-                let _ = self.agents[id].move_to(target_cid);
+    /// Changes the settings of the contest mode.
+    ///
+    /// When the mode becomes enabled, every agent draws a new aggression
+    /// from the configured range. While it stays enabled, aggressions are
+    /// kept and only clamped into a changed range. When it is disabled, all
+    /// aggressions are reset to zero.
+    ///
+    /// ### Parameters
+    /// - `combat`: The [`CombatConfig`] used from now on.
+    pub fn set_combat(&mut self, combat: CombatConfig) {
+        let was_active = self.combat.enabled && self.combat.is_valid();
+        self.combat = combat;
+        for agent in &mut self.agents {
+            if !(combat.enabled && combat.is_valid()) {
+                agent.set_aggression(0.0);
+            } else if was_active {
+                let aggression = agent
+                    .aggression()
+                    .clamp(combat.min_aggression, combat.max_aggression);
+                agent.set_aggression(aggression);
+            } else {
+                let aggression = self
+                    .rng
+                    .gen_range(combat.min_aggression..=combat.max_aggression);
+                agent.set_aggression(aggression);
             }
         }
+    }
 
-        if !self.agents[id].is_alive() {
-            self.handle_agent_death(id);
-            return;
-        }
-
-        let _ = self.agents[id].update();
+    /// Returns the settings of the group layer.
+    ///
+    /// ### Returns
+    /// The current [`GroupConfig`].
+    pub fn groups(&self) -> GroupConfig {
+        self.groups
+    }
 
-        if !self.agents[id].is_alive() {
-            self.handle_agent_death(id);
+    /// Changes the settings of the group layer.
+    ///
+    /// When the layer becomes enabled, every agent joins a random group.
+    /// While it stays enabled, agents keep their group; only members of
+    /// groups that no longer exist join a random one. When it is disabled,
+    /// all agents leave their groups.
+    ///
+    /// ### Parameters
+    /// - `groups`: The [`GroupConfig`] used from now on.
+    pub fn set_groups(&mut self, groups: GroupConfig) {
+        let was_active = self.groups.enabled && self.groups.is_valid();
+        self.groups = groups;
+        for agent in &mut self.agents {
+            if !(groups.enabled && groups.is_valid()) {
+                agent.set_group(None);
+            } else if !was_active || agent.group().is_none_or(|g| g >= groups.groups) {
+                agent.set_group(Some(self.rng.gen_range(0..groups.groups)));
+            }
         }
     }
 
-    /// Advances all agents once.
-    fn step_all_agents(&mut self) {
-        let len = self.agents.len();
-        for id in 0..len {
-            self.step_agent(id);
+    /// Summarizes every group, living, dead and archived members included.
+    ///
+    /// ### Returns
+    /// One [`GroupStatistics`] per configured group, in group order; empty
+    /// while the group layer is disabled.
+    pub fn group_statistics(&self) -> Vec<GroupStatistics> {
+        if !self.groups.enabled {
+            return Vec::new();
         }
+        let mut stats: Vec<GroupStatistics> = (0..self.groups.groups)
+            .map(|group| GroupStatistics {
+                group,
+                ..GroupStatistics::default()
+            })
+            .collect();
+        for agent in &self.agents {
+            let Some(group) = agent.group().and_then(|g| stats.get_mut(g)) else {
+                continue;
+            };
+            if agent.is_alive() {
+                group.living += 1;
+                group.energy += u64::from(agent.energy());
+            } else {
+                group.dead += 1;
+            }
+            group.births += u64::from(agent.offspring());
+            group.shared += agent.donated();
+        }
+        for record in &self.graveyard {
+            let Some(group) = record.group.and_then(|g| stats.get_mut(g)) else {
+                continue;
+            };
+            group.dead += 1;
+            group.births += u64::from(record.offspring);
+            group.shared += record.donated;
+        }
+        stats
     }
-}
 
-impl Updatable for World {
-    /// Advances the entire world by one simulation step.
-    ///
-    /// The step order is:
-    /// 1. Update all cells (resource regeneration).
-    /// 2. Allocate resources from cells to agents.
-    /// 3. Step each agent (movement + metabolism + death handling).
+    /// Returns the settings of the territory layer.
     ///
     /// ### Returns
-    /// - `Ok(())` on success. At the moment this implementation never fails,
-    ///   but the `Result` type allows for future error propagation.
-    fn update(&mut self) -> Result<(), SimulationError> {
-        for cell in &mut self.cells {
-            let _ = cell.update();
-        }
+    /// The current [`TerritoryConfig`].
+    pub fn territory(&self) -> TerritoryConfig {
+        self.territory
+    }
 
-        self.allocate_resources();
-        self.step_all_agents();
+    /// Changes the settings of the territory layer used by later updates.
+    ///
+    /// Disabling the layer releases all owned cells.
+    ///
+    /// ### Parameters
+    /// - `territory`: The [`TerritoryConfig`] used from now on.
+    pub fn set_territory(&mut self, territory: TerritoryConfig) {
+        self.territory = territory;
+        if !territory.enabled {
+            self.territories = TerritoryMap::new(self.cells.len());
+        }
+    }
 
-        Ok(())
+    /// Returns which agent owns which cell.
+    ///
+    /// ### Returns
+    /// The world's [`TerritoryMap`]; empty while the layer is disabled.
+    pub fn territories(&self) -> &TerritoryMap {
+        &self.territories
     }
-}
+
+    /// Returns the events of the most recent tick.
+    ///
+    /// ### Returns
+    /// Deaths and births in the order they happened; empty before the
+    /// first update.
+    pub fn events(&self) -> &[WorldEvent] {
+        &self.events
+    }
+
+    /// Returns totals over all ticks so far.
+    ///
+    /// ### Returns
+    /// The world's [`Statistics`].
+    pub fn statistics(&self) -> &Statistics {
+        &self.statistics
+    }
+
+    /// Records an event of the current tick.
+    ///
+    /// ### Parameters
+    /// - `event`: The event to add to [`World::events`] and
+    ///   [`World::statistics`].
+    fn record_event(&mut self, event: WorldEvent) {
+        self.statistics.record(&event);
+        self.events.push(event);
+    }
+
+    /// Returns the genome of the most successful agent so far.
+    ///
+    /// Agents are ranked by offspring count, then by age; dead agents,
+    /// including those archived in the graveyard, are included.
+    ///
+    /// ### Returns
+    /// - `Some(&Genome)` of the best agent carrying a genome.
+    /// - `None` if no agent has a genome.
+    pub fn best_genome(&self) -> Option<&Genome> {
+        let archived = self
+            .graveyard
+            .iter()
+            .filter_map(|r| Some((r.offspring, r.age, r.id, r.genome.as_ref()?)));
+        self.agents
+            .iter()
+            .filter_map(|a| Some((a.offspring(), a.age(), a.id(), a.genome()?)))
+            .chain(archived)
+            .max_by_key(|&(offspring, age, id, _)| (offspring, age, std::cmp::Reverse(id)))
+            .map(|(.., genome)| genome)
+    }
+
+    /// Returns what an agent standing on `cid` can see.
+    ///
+    /// Every cell within the perception radius (Manhattan distance) is
+    /// listed with its current resource, ordered by distance and then by
+    /// cell id. If [`PerceptionConfig::see_agents`] is set, the living
+    /// agents on those cells are listed as well.
+    ///
+    /// ### Parameters
+    /// - `cid`: Id of the cell the view is centred on.
+    ///
+    /// ### Returns
+    /// A read-only [`Perception`] snapshot.
+    pub fn perceive(&self, cid: usize) -> Perception {
+        let (width, height) = self.size;
+        let radius = self.perception.radius;
+        let (x, y) = perception::coords(cid, width);
+
+        let mut cells = Vec::new();
+        for ny in y.saturating_sub(radius)..=y.saturating_add(radius).min(height - 1) {
+            let reach = radius - y.abs_diff(ny);
+            for nx in x.saturating_sub(reach)..=x.saturating_add(reach).min(width - 1) {
+                let nid = ny * width + nx;
+                cells.push(VisibleCell {
+                    cid: nid,
+                    distance: x.abs_diff(nx) + y.abs_diff(ny),
+                    resource: self.cells[nid].cur_resource(),
+                });
+            }
+        }
+        cells.sort_by_key(|c| (c.distance, c.cid));
+
+        let agents = if self.perception.see_agents {
+            let mut visible: Vec<usize> = self.spatial.within(cid, radius).collect();
+            visible.sort_unstable();
+            visible
+                .into_iter()
+                .map(|i| &self.agents[i])
+                .map(|a| VisibleAgent {
+                    id: a.id(),
+                    cid: a.cid(),
+                    distance: perception::manhattan(cid, a.cid(), width),
+                    health_point: a.health_point(),
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Perception {
+            origin: cid,
+            radius,
+            cells,
+            agents,
+        }
+    }
+
+    /// Returns the neighbor of `from` that leads toward `to`.
+    ///
+    /// Among the neighbors that reduce the Manhattan distance to `to`, the
+    /// one with the most resource is chosen (the first in
+    /// [`World::neighbor_cells_info`] order on ties).
+    ///
+    /// ### Parameters
+    /// - `from`: Id of the current cell.
+    /// - `to`: Id of the target cell.
+    ///
+    /// ### Returns
+    /// - `Some(cell_id)` of the next cell on the way.
+    /// - `None` if `from == to`.
+    pub fn step_toward(&self, from: usize, to: usize) -> Option<usize> {
+        let width = self.size.0;
+        let distance = perception::manhattan(from, to, width);
+        self.neighbor_cells_info(from)
+            .into_iter()
+            .filter(|&(nid, _)| perception::manhattan(nid, to, width) < distance)
+            .fold(
+                None,
+                |best: Option<(usize, u32)>, (nid, resource)| match best {
+                    Some((_, best_resource)) if best_resource >= resource => best,
+                    _ => Some((nid, resource)),
+                },
+            )
+            .map(|(nid, _)| nid)
+    }
+
+    /// Returns what a learning agent observes.
+    ///
+    /// ### Parameters
+    /// - `agent`: The observing agent.
+    /// - `radius`: Half the side of the square patch around the agent.
+    ///
+    /// ### Returns
+    /// An [`Observation`] with the resource patch and the agent's own state.
+    pub fn observe(&self, agent: &Agent, radius: usize) -> Observation {
+        let (width, height) = self.size;
+        let (x, y) = perception::coords(agent.cid(), width);
+        let r = radius as isize;
+        let mut patch = Vec::with_capacity((2 * radius + 1).pow(2));
+        for dy in -r..=r {
+            for dx in -r..=r {
+                let (px, py) = (x as isize + dx, y as isize + dy);
+                let inside =
+                    (0..width as isize).contains(&px) && (0..height as isize).contains(&py);
+                patch.push(
+                    inside.then(|| self.cells[py as usize * width + px as usize].cur_resource()),
+                );
+            }
+        }
+        Observation {
+            radius,
+            patch,
+            health_point: agent.health_point(),
+            energy: agent.energy(),
+            need: agent.need(),
+            hungry: agent.is_hungry(),
+        }
+    }
+
+    /// Advances the world by one tick with externally chosen actions.
+    ///
+    /// `actions[i]` is applied to `self.agents()[i]`; agents without an
+    /// entry stay. The actions replace the decisions of the world's policy
+    /// for this tick, but the policy still receives the move outcomes and
+    /// deaths. Everything else (allocation, metabolism, capacity,
+    /// pheromones) runs as in [`Updatable::update`].
+    ///
+    /// ### Parameters
+    /// - `actions`: One [`Action`] per agent.
+    /// - `reward`: How rewards are derived from the health change.
+    ///
+    /// ### Returns
+    /// A [`StepResult`] with per-agent rewards and whether each agent is
+    /// done (dead), in the order of [`World::agents`] before the tick.
+    /// Agents that were already dead get a reward of `0.0`. If the update
+    /// reported a failure, the tick still counts and the failure is kept in
+    /// [`StepResult::error`].
+    pub fn step_with_actions(&mut self, actions: &[Action], reward: RewardConfig) -> StepResult {
+        let before: Vec<(usize, u32, bool)> = self
+            .agents
+            .iter()
+            .map(|a| (a.id(), a.health_point(), a.is_alive()))
+            .collect();
+        self.actions = Some(
+            self.agents
+                .iter()
+                .zip(actions)
+                .map(|(agent, &action)| (agent.id(), action))
+                .collect(),
+        );
+        let error = self.update().err();
+        self.actions = None;
+
+        let (rewards, done) = before
+            .into_iter()
+            .map(|(id, hp, was_alive)| {
+                // Agents archived by a compaction during this tick are dead.
+                let (hp_now, alive) = self
+                    .agent(id)
+                    .map_or((0, false), |a| (a.health_point(), a.is_alive()));
+                let r = if was_alive {
+                    reward.reward(hp, hp_now, alive)
+                } else {
+                    0.0
+                };
+                (r, !alive)
+            })
+            .unzip();
+        StepResult {
+            rewards,
+            done,
+            error,
+        }
+    }
+
+    /// Returns whether a cell has reached its agent capacity.
+    ///
+    /// ### Parameters
+    /// - `cid`: Cell id in row-major indexing.
+    ///
+    /// ### Returns
+    /// `true` if a capacity is set and the cell holds that many living
+    /// agents; always `false` without a capacity.
+    pub fn is_cell_full(&self, cid: usize) -> bool {
+        self.cell_capacity
+            .is_some_and(|cap| cid >= self.cells.len() || self.spatial.in_cell(cid).len() >= cap)
+    }
+
+    /// Counts the living agents on a cell.
+    ///
+    /// ### Parameters
+    /// - `cid`: Cell id in row-major indexing.
+    ///
+    /// ### Returns
+    /// The number of living agents whose current cell is `cid`.
+    pub fn living_agents_on(&self, cid: usize) -> usize {
+        if cid < self.cells.len() {
+            return self.spatial.in_cell(cid).len();
+        }
+        self.spatial
+            .strays()
+            .iter()
+            .filter(|&&i| self.agents[i].cid() == cid)
+            .count()
+    }
+
+    /// Returns the living agents on a cell.
+    ///
+    /// ### Parameters
+    /// - `cid`: Cell id in row-major indexing.
+    ///
+    /// ### Returns
+    /// An iterator over the agents on `cid`, in agent order; empty for
+    /// cells outside the grid.
+    pub fn agents_in_cell(&self, cid: usize) -> impl Iterator<Item = &Agent> + '_ {
+        self.spatial.in_cell(cid).iter().map(|&i| &self.agents[i])
+    }
+
+    /// Returns the living agents in a rectangle of cells.
+    ///
+    /// ### Parameters
+    /// - `min`: `(x, y)` of the top-left corner.
+    /// - `max`: `(x, y)` of the bottom-right corner, inclusive; clamped to
+    ///   the grid.
+    ///
+    /// ### Returns
+    /// An iterator over the agents in the rectangle, row by row.
+    pub fn agents_in_rect(
+        &self,
+        min: (usize, usize),
+        max: (usize, usize),
+    ) -> impl Iterator<Item = &Agent> + '_ {
+        self.spatial.in_rect(min, max).map(|i| &self.agents[i])
+    }
+
+    /// Returns the living agents within a Manhattan distance of a cell.
+    ///
+    /// ### Parameters
+    /// - `cid`: Cell id of the centre.
+    /// - `radius`: Largest distance included.
+    ///
+    /// ### Returns
+    /// An iterator over the agents in range, row by row; empty if `cid`
+    /// is outside the grid.
+    pub fn agents_within(&self, cid: usize, radius: usize) -> impl Iterator<Item = &Agent> + '_ {
+        self.spatial.within(cid, radius).map(|i| &self.agents[i])
+    }
+
+    /// Returns the index behind [`World::agents_in_cell`] and the other
+    /// spatial queries.
+    ///
+    /// ### Returns
+    /// The world's [`SpatialIndex`].
+    pub fn spatial_index(&self) -> &SpatialIndex {
+        &self.spatial
+    }
+
+    /// Enables or disables the debug mode.
+    ///
+    /// While enabled, every [`World::update`] records a [`ResourceLedger`]
+    /// and checks the world invariants at the end of the tick. Violations
+    /// are returned as [`SimulationError::InvariantViolation`]s.
+    ///
+    /// ### Parameters
+    /// - `enabled`: Whether to record ledgers and check invariants.
+    pub fn set_debug_checks(&mut self, enabled: bool) {
+        self.debug_checks = enabled;
+        if !enabled {
+            self.ledger = None;
+        }
+    }
+
+    /// Returns whether the debug mode is enabled.
+    ///
+    /// ### Returns
+    /// `true` if ledgers are recorded and invariants are checked.
+    pub fn debug_checks(&self) -> bool {
+        self.debug_checks
+    }
+
+    /// Returns the ledger of the most recent tick.
+    ///
+    /// ### Returns
+    /// - `Some(&ResourceLedger)` if the debug mode was enabled during the
+    ///   last update.
+    /// - `None` otherwise.
+    pub fn last_ledger(&self) -> Option<&ResourceLedger> {
+        self.ledger.as_ref()
+    }
+
+    /// Returns an immutable view of all agents.
+    ///
+    /// Positions in this slice change when the world is compacted; use
+    /// [`World::agent`] to look an agent up by its stable id.
+    ///
+    /// ### Returns
+    /// A slice of all agents.
+    pub fn agents(&self) -> &[Agent] {
+        &self.agents
+    }
+
+    /// Returns an agent by its stable id.
+    ///
+    /// ### Parameters
+    /// - `id`: Id of the agent (see [`Agent::id`]).
+    ///
+    /// ### Returns
+    /// - `Some(&Agent)` if the agent is still in [`World::agents`].
+    /// - `None` if the id is unknown or the agent has been archived.
+    pub fn agent(&self, id: usize) -> Option<&Agent> {
+        self.ids.index_of(id).map(|index| &self.agents[index])
+    }
+
+    /// Gives mutable access to an agent by its stable id.
+    ///
+    /// Changes are reconciled with the rest of the world when the returned
+    /// [`AgentMut`] is dropped, so hooks and tools can move, wound or
+    /// reconfigure agents without breaking the spatial index or the ids.
+    ///
+    /// ### Parameters
+    /// - `id`: Stable id of the agent.
+    ///
+    /// ### Returns
+    /// - `Some(AgentMut)` if the agent is in [`World::agents`].
+    /// - `None` if it was archived or never existed.
+    pub fn agent_mut(&mut self, id: usize) -> Option<AgentMut<'_>> {
+        let index = self.ids.index_of(id)?;
+        let agent = &self.agents[index];
+        Some(AgentMut {
+            id,
+            index,
+            cid: agent.cid(),
+            alive: agent.is_alive(),
+            world: self,
+        })
+    }
+
+    /// Returns the map from stable agent ids to positions in
+    /// [`World::agents`].
+    ///
+    /// ### Returns
+    /// The world's [`AgentIds`].
+    pub fn agent_ids(&self) -> &AgentIds {
+        &self.ids
+    }
+
+    /// Returns the agents removed by [`World::compact`], oldest first.
+    ///
+    /// ### Returns
+    /// A slice of [`GraveRecord`]s.
+    pub fn graveyard(&self) -> &[GraveRecord] {
+        &self.graveyard
+    }
+
+    /// Looks up an archived agent by its stable id.
+    ///
+    /// ### Parameters
+    /// - `id`: Stable id of the agent.
+    ///
+    /// ### Returns
+    /// - `Some(&GraveRecord)` if [`World::compact`] archived the agent.
+    /// - `None` if the agent is still in [`World::agents`] or never existed.
+    pub fn grave(&self, id: usize) -> Option<&GraveRecord> {
+        self.graves.get(&id).map(|&index| &self.graveyard[index])
+    }
+
+    /// Returns how often dead agents are compacted away.
+    ///
+    /// ### Returns
+    /// - `Some(ticks)` if [`World::compact`] runs every `ticks` ticks.
+    /// - `None` if dead agents are kept.
+    pub fn compaction_interval(&self) -> Option<u64> {
+        self.compaction_interval
+    }
+
+    /// Sets how often dead agents are compacted away.
+    ///
+    /// ### Parameters
+    /// - `interval`: `Some(ticks)` to compact every `ticks` ticks (values
+    ///   below one are treated as one), or `None` to keep dead agents.
+    pub fn set_compaction_interval(&mut self, interval: Option<u64>) {
+        self.compaction_interval = interval.map(|ticks| ticks.max(1));
+    }
+
+    /// Moves all dead agents into the graveyard.
+    ///
+    /// Living agents keep their relative order and their ids; only their
+    /// positions in [`World::agents`] change. The ids of archived agents
+    /// no longer resolve through [`World::agent`].
+    ///
+    /// Compacting moves agents, which the checks at the end of a tick
+    /// cannot follow, so this does nothing when called from the
+    /// [`WorldHook::after_regen`] and [`WorldHook::after_allocation`]
+    /// hooks. Compact from [`WorldHook::before_tick`] or
+    /// [`WorldHook::after_tick`] instead.
+    ///
+    /// ### Returns
+    /// The number of agents archived; `0` in the middle of a tick.
+    pub fn compact(&mut self) -> usize {
+        if self.mid_tick || self.agents.iter().all(|a| a.is_alive()) {
+            return 0;
+        }
+        let before = self.graveyard.len();
+        let agents = std::mem::take(&mut self.agents);
+        for agent in agents {
+            if agent.is_alive() {
+                self.ids.relocate(agent.id(), self.agents.len());
+                self.agents.push(agent);
+            } else {
+                self.ids.release(agent.id());
+                self.graves.insert(agent.id(), self.graveyard.len());
+                self.graveyard.push(GraveRecord::from(agent));
+            }
+        }
+        self.spatial = SpatialIndex::new(self.size, &self.agents);
+        self.graveyard.len() - before
+    }
+
+    /// Constructs a randomized world from a configuration.
+    ///
+    /// This method:
+    /// - creates a `width × height` grid of cells, each with
+    ///   random starting resources, regeneration rate and terrain cost
+    /// - spawns a random number of agents between `min_agents` and `max_agents`,
+    ///   each placed at a random cell with random consumption rate
+    ///   (cells that reached `cell_capacity` are skipped)
+    ///
+    /// With `cfg.seed` set, the same configuration always produces the same
+    /// world and the same sequence of updates.
+    ///
+    /// Invalid configs panic during development rather than silently
+    /// misbehaving; use [`World::try_from_config`] to handle them as errors.
+    ///
+    /// ### Parameters
+    /// - `cfg`: World generation parameters.
+    ///
+    /// ### Returns
+    /// A new [`World`] sampled according to `cfg`.
+    pub fn from_config(cfg: WorldConfig) -> Self {
+        Self::try_from_config(cfg).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Constructs a randomized world from a configuration, validating it first.
+    ///
+    /// ### Parameters
+    /// - `cfg`: World generation parameters.
+    ///
+    /// ### Returns
+    /// - `Ok(World)` sampled according to `cfg`.
+    /// - `Err(SimulationError::InvalidConfig(..))` if `cfg` is inconsistent.
+    pub fn try_from_config(cfg: WorldConfig) -> Result<Self, SimulationError> {
+        cfg.validate()?;
+
+        let mut cells = Vec::with_capacity(cfg.width * cfg.height);
+
+        let mut rng = match cfg.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+
+        for y in 0..cfg.height {
+            for x in 0..cfg.width {
+                let id = y * cfg.width + x;
+                let rand_resource = rng.gen_range(cfg.min_resource..=cfg.max_resource);
+                let rand_regen_rate = rng.gen_range(cfg.min_regen_rate..=cfg.max_regen_rate);
+
+                let mut cell = Cell::new(
+                    id,
+                    rand_resource,
+                    cfg.max_resource,
+                    rand_regen_rate,
+                    cfg.max_regen_rate,
+                );
+                if cfg.min_terrain_cost < cfg.max_terrain_cost {
+                    cell.set_terrain_cost(
+                        rng.gen_range(cfg.min_terrain_cost..=cfg.max_terrain_cost),
+                    );
+                } else {
+                    cell.set_terrain_cost(cfg.min_terrain_cost);
+                }
+                cells.push(cell);
+            }
+        }
+
+        let num_agents = rng.gen_range(cfg.min_agents..=cfg.max_agents);
+        let mut agents = Vec::with_capacity(num_agents);
+        let mut occupancy = vec![0usize; cells.len()];
+
+        for id in 0..num_agents {
+            let cid = loop {
+                let rand_x = rng.gen_range(0..cfg.width);
+                let rand_y = rng.gen_range(0..cfg.height);
+                let cid = rand_y * cfg.width + rand_x;
+                if cfg.cell_capacity.is_none_or(|cap| occupancy[cid] < cap) {
+                    break cid;
+                }
+            };
+            occupancy[cid] += 1;
+            let rand_consumption_rate =
+                rng.gen_range(cfg.min_consumption_rate..=cfg.max_consumption_rate);
+
+            let mut agent = Agent::new(id, cid, rand_consumption_rate, 0, cfg.agent_hp, true);
+            agent.enable_memory(cfg.agent_memory);
+            agents.push(agent);
+        }
+
+        let mut world = World::new((cfg.width, cfg.height), cells, agents);
+        world.rng = rng;
+        world.update_order = cfg.update_order;
+        world.cell_capacity = cfg.cell_capacity;
+        world.compaction_interval = cfg.compaction_interval;
+        world.conflict_rule = cfg.conflict_rule;
+        world.policy = cfg.policy.build();
+        world.set_perception(cfg.perception);
+        world.pheromone = cfg.pheromone;
+        world.set_evolution(cfg.evolution);
+        world.set_aging(cfg.aging);
+        world.disease = cfg.disease;
+        world.territory = cfg.territory;
+        if cfg.disease.enabled {
+            let count = cfg.disease.initial_infected.min(world.agents.len());
+            for i in rand::seq::index::sample(&mut world.rng, world.agents.len(), count) {
+                world.agents[i].infect(cfg.disease.duration, cfg.disease.extra_need);
+            }
+        }
+        if cfg.evolution.enabled {
+            for agent in &mut world.agents {
+                agent.set_genome(Some(Genome::random(cfg.evolution.hidden, &mut world.rng)));
+            }
+        }
+        world.set_combat(cfg.combat);
+        world.set_groups(cfg.groups);
+        Ok(world)
+    }
+
+    /// Returns `(cell_id, resource)` pairs for all neighbors of `cid`.
+    ///
+    /// The neighbors are in the four cardinal directions (up, down, left, right),
+    /// clipped to the bounds of the grid.
+    ///
+    /// ### Parameters
+    /// - `cid`: Id of the central cell.
+    ///
+    /// ### Returns
+    /// A `Vec<(cell_id, resource)>` describing the neighbors.
+    pub fn neighbor_cells_info(&self, cid: usize) -> Vec<(usize, u32)> {
+        let (width, height) = self.size;
+        let x = cid % width;
+        let y = cid / width;
+
+        let mut neighbors = Vec::with_capacity(4);
+
+        if y > 0 {
+            let ny = y - 1;
+            let nid = ny * width + x;
+            neighbors.push((nid, self.cells[nid].cur_resource()));
+        }
+
+        if y + 1 < height {
+            let ny = y + 1;
+            let nid = ny * width + x;
+            neighbors.push((nid, self.cells[nid].cur_resource()));
+        }
+
+        if x > 0 {
+            let nx = x - 1;
+            let nid = y * width + nx;
+            neighbors.push((nid, self.cells[nid].cur_resource()));
+        }
+
+        if x + 1 < width {
+            let nx = x + 1;
+            let nid = y * width + nx;
+            neighbors.push((nid, self.cells[nid].cur_resource()));
+        }
+
+        neighbors
+    }
+
+    /// Allocates resources from each cell to the agents currently on it.
+    ///
+    /// The algorithm:
+    /// 1. Collects living agents per cell.
+    /// 2. In contest mode, agents on a scarce cell may fight over it; then
+    ///    only the winner eats (see [`CombatConfig`]).
+    /// 3. If the cell's owner is present and owners have priority (see
+    ///    [`TerritoryConfig`]), the owner is fed first from the whole cell.
+    /// 4. Splits the rest of the cell's resource equally among the other
+    ///    local agents.
+    /// 5. Each agent calls [`Agent::retrieve_resource`] with its share.
+    /// 6. Only the resource actually retrieved is removed from the cell via
+    ///    [`Cell::resource_consumption`].
+    ///
+    /// Steps 3 to 5 are planned per cell, in parallel with the `parallel`
+    /// feature.
+    ///
+    /// Agents killed in fights are handled like any other death once every
+    /// cell has been allocated.
+    ///
+    /// This is the allocation phase of a tick; it is public so that it can
+    /// be run and measured on its own, e.g. by the benchmarks. Hooks must
+    /// not call it: the phase would run twice in that tick.
+    ///
+    /// ### Returns
+    /// - `Ok(())` if every agent stands on a valid cell and every cell
+    ///   could pay for what its agents retrieved.
+    /// - `Err(SimulationError)` otherwise; the remaining cells are still
+    ///   allocated.
+    pub fn allocate_resources(&mut self) -> Result<(), SimulationError> {
+        let mut errors = Vec::new();
+        let num_cells = self.cells.len();
+        for &i in self.spatial.strays() {
+            let cid = self.agents[i].cid();
+            errors.push(SimulationError::InvalidCellId { cid, num_cells });
+        }
+
+        let mut sharers: Vec<Vec<usize>> = (0..num_cells)
+            .map(|cid| self.spatial.in_cell(cid).to_vec())
+            .collect();
+        let mut fighters = Vec::new();
+        if self.combat.enabled {
+            for (cid, ids) in sharers.iter_mut().enumerate() {
+                let total = self.cells[cid].cur_resource();
+                if ids.is_empty() || total == 0 {
+                    continue;
+                }
+                if let Some(winners) = self.contest_cell(cid, ids, total) {
+                    fighters.extend(std::mem::replace(ids, winners));
+                }
+            }
+        }
+
+        for (cid, (offers, spent)) in self.plan_allocations(&sharers).into_iter().enumerate() {
+            if offers.is_empty() {
+                continue;
+            }
+            for (i, offer) in offers {
+                self.agents[i].retrieve_resource(offer);
+            }
+            if let Some(ledger) = &mut self.ledger {
+                ledger.consumed += u64::from(spent);
+            }
+            if let Err(err) = self.cells[cid].resource_consumption(spent) {
+                errors.push(SimulationError::InvariantViolation {
+                    tick: self.tick,
+                    entity: Entity::Cell(cid),
+                    reason: format!("cell {cid} could not pay {spent} allocated resource: {err}"),
+                });
+            }
+        }
+
+        for i in fighters {
+            if !self.agents[i].is_alive() {
+                self.handle_agent_death(i);
+            }
+        }
+
+        SimulationError::collect(errors)
+    }
+
+    /// Plans the allocation of every cell (see [`World::plan_allocation`]).
+    ///
+    /// With the `parallel` feature enabled, cells are planned in parallel.
+    ///
+    /// ### Parameters
+    /// - `sharers`: Indices of the agents eating from each cell.
+    ///
+    /// ### Returns
+    /// One plan per cell, in cell order.
+    fn plan_allocations(&self, sharers: &[Vec<usize>]) -> Vec<(Vec<(usize, u32)>, u32)> {
+        #[cfg(feature = "parallel")]
+        if self.parallel {
+            return sharers
+                .par_iter()
+                .enumerate()
+                .map(|(cid, ids)| self.plan_allocation(cid, ids))
+                .collect();
+        }
+        sharers
+            .iter()
+            .enumerate()
+            .map(|(cid, ids)| self.plan_allocation(cid, ids))
+            .collect()
+    }
+
+    /// Computes how a cell's resource is offered to the agents eating from it.
+    ///
+    /// A present owner with priority is offered the whole cell first; the
+    /// rest is split equally among the others. Each agent takes up to its
+    /// need plus appetite (see [`Agent::retrieve_resource`]).
+    ///
+    /// ### Parameters
+    /// - `cid`: Id of the cell.
+    /// - `sharers`: Indices of the agents eating from the cell.
+    ///
+    /// ### Returns
+    /// `(offers, spent)`: the `(agent_index, amount)` offered to each agent
+    /// and the total the agents will take. Both are empty/zero if the cell
+    /// or the list of agents is empty.
+    fn plan_allocation(&self, cid: usize, sharers: &[usize]) -> (Vec<(usize, u32)>, u32) {
+        let total = self.cells[cid].cur_resource();
+        if sharers.is_empty() || total == 0 {
+            return (Vec::new(), 0);
+        }
+        let take = |i: usize, offer: u32| {
+            let agent = &self.agents[i];
+            offer.min(agent.need().saturating_add(agent.appetite()))
+        };
+
+        let mut offers = Vec::with_capacity(sharers.len());
+        let mut shared = total;
+        let mut spent = 0;
+        let owner = self.priority_owner(cid, sharers).map(|pos| sharers[pos]);
+        if let Some(owner) = owner {
+            let taken = take(owner, total);
+            offers.push((owner, total));
+            shared -= taken;
+            spent += taken;
+        }
+
+        let others = sharers.len() - usize::from(owner.is_some());
+        if others > 0 {
+            let base_share = shared / others as u32;
+            for &i in sharers.iter().filter(|&&i| Some(i) != owner) {
+                offers.push((i, base_share));
+                spent += take(i, base_share);
+            }
+        }
+        (offers, spent)
+    }
+
+    /// Lets the agents on a scarce cell fight over it (contest mode).
+    ///
+    /// A cell is contested if the contest mode is enabled, at least two
+    /// agents stand on it and its resource is below their combined need.
+    /// Each agent then joins the fight with probability equal to its
+    /// aggression. Fighters duel in order: the holder beats the challenger
+    /// with [`CombatConfig::win_chance`], both are wounded by the fight
+    /// cost, and each duel is recorded as a [`WorldEvent::Fight`].
+    ///
+    /// ### Parameters
+    /// - `cid`: Id of the cell.
+    /// - `agent_indices`: Indices of the living agents on the cell.
+    /// - `total`: Resource currently on the cell.
+    ///
+    /// ### Returns
+    /// - `None` if nobody fights; the resource is shared as usual.
+    /// - `Some(winners)` otherwise: the surviving winner, or no one if the
+    ///   last holder died of its wounds.
+    fn contest_cell(
+        &mut self,
+        cid: usize,
+        agent_indices: &[usize],
+        total: u32,
+    ) -> Option<Vec<usize>> {
+        if !self.combat.enabled || agent_indices.len() < 2 {
+            return None;
+        }
+        let demand: u32 = agent_indices.iter().map(|&i| self.agents[i].need()).sum();
+        if total >= demand {
+            return None;
+        }
+        let fighters: Vec<usize> = agent_indices
+            .iter()
+            .copied()
+            .filter(|&i| self.rng.gen_bool(f64::from(self.agents[i].aggression())))
+            .collect();
+        if fighters.is_empty() {
+            return None;
+        }
+
+        let mut holder: Option<usize> = None;
+        for challenger in fighters {
+            let Some(current) = holder else {
+                holder = Some(challenger);
+                continue;
+            };
+            let chance = CombatConfig::win_chance(
+                self.agents[current].health_point(),
+                self.agents[challenger].health_point(),
+            );
+            let (winner, loser) = if self.rng.gen_bool(chance) {
+                (current, challenger)
+            } else {
+                (challenger, current)
+            };
+            self.record_event(WorldEvent::Fight {
+                tick: self.tick,
+                cid,
+                winner: self.agents[winner].id(),
+                loser: self.agents[loser].id(),
+            });
+            self.agents[current].wound(self.combat.fight_cost);
+            self.agents[challenger].wound(self.combat.fight_cost);
+            holder = Some(winner).filter(|&w| self.agents[w].is_alive());
+        }
+        Some(holder.into_iter().collect())
+    }
+
+    /// Finds the owner of a cell among the agents standing on it.
+    ///
+    /// ### Parameters
+    /// - `cid`: Id of the cell.
+    /// - `agent_indices`: Indices of the living agents on the cell.
+    ///
+    /// ### Returns
+    /// - `Some(pos)` with the owner's position in `agent_indices` if the
+    ///   territory layer is enabled with owner priority.
+    /// - `None` otherwise.
+    fn priority_owner(&self, cid: usize, agent_indices: &[usize]) -> Option<usize> {
+        if !self.territory.enabled || !self.territory.owner_priority {
+            return None;
+        }
+        let owner = self.territories.owner(cid)?;
+        agent_indices
+            .iter()
+            .position(|&i| self.agents[i].id() == owner)
+    }
+
+    /// Returns the extra cost an agent pays to enter a cell.
+    ///
+    /// ### Parameters
+    /// - `id`: Index of the moving agent.
+    /// - `cid`: Id of the destination cell.
+    ///
+    /// ### Returns
+    /// [`TerritoryConfig::intrusion_cost`] if the cell is owned by another
+    /// agent, `0` otherwise or when the territory layer is disabled.
+    fn intrusion_cost(&self, id: usize, cid: usize) -> u32 {
+        if !self.territory.enabled {
+            return 0;
+        }
+        match self.territories.owner(cid) {
+            Some(owner) if owner != self.agents[id].id() => self.territory.intrusion_cost,
+            _ => 0,
+        }
+    }
+
+    /// Handles side effects when an agent dies on a given cell.
+    ///
+    /// Currently this:
+    /// - returns a fixed amount of resource to the cell
+    /// - slightly boosts that cell's regeneration rate
+    /// - tells the movement policy (see [`MovementPolicy::on_death`])
+    /// - records a [`WorldEvent::Death`] with the agent's death cause
+    ///
+    /// ### Parameters
+    /// - `id`: Index of the dead agent in `self.agents`.
+    fn handle_agent_death(&mut self, id: usize) {
+        let cid = self.agents[id].cid();
+
+        let before = self.cells[cid].cur_resource();
+        self.cells[cid].add_resource(CORPSE_RESOURCE);
+        self.cells[cid].increase_rate(CORPSE_REGEN_BONUS);
+        self.spatial.remove(id, cid);
+        self.policy.on_death(&self.agents[id]);
+        self.agents[id].set_death_tick(self.tick);
+        let agent = &self.agents[id];
+        self.record_event(WorldEvent::Death {
+            tick: self.tick,
+            agent: agent.id(),
+            cid,
+            age: agent.age(),
+            cause: agent.death_cause().unwrap_or(DeathCause::Starvation),
+        });
+
+        if let Some(ledger) = &mut self.ledger {
+            let after = self.cells[cid].cur_resource();
+            ledger.corpse_deposited += ledger.record_deposit(CORPSE_RESOURCE, before, after);
+        }
+        self.notify_hooks(|hook, world| hook.on_agent_died(world, &world.agents[id]));
+    }
+
+    /// Decides where a single agent wants to move this step.
+    ///
+    /// Living agents ask the world's [`MovementPolicy`], or follow their
+    /// action during [`World::step_with_actions`]; dead agents stay.
+    ///
+    /// ### Parameters
+    /// - `id`: Index of the agent.
+    ///
+    /// ### Returns
+    /// - `Ok(Some(cell_id))` if the agent wants to move to a neighbor.
+    /// - `Ok(None)` if it stays.
+    /// - `Err(SimulationError::InvalidCellId { .. })` if the agent stands on
+    ///   a cell outside the grid.
+    /// - `Err(SimulationError::OutOfBoundsMove { .. })` if the chosen
+    ///   destination is not a neighbor of the agent's cell.
+    fn decide_agent_move(&self, id: usize) -> Result<Option<usize>, SimulationError> {
+        let agent = &self.agents[id];
+        if !agent.is_alive() {
+            return Ok(None);
+        }
+
+        let cid = agent.cid();
+        self.try_cell(cid)?;
+        let neighbors = self.neighbor_cells_info(cid);
+
+        let choice = match &self.actions {
+            Some(actions) => {
+                let action = actions.get(&agent.id()).copied().unwrap_or_default();
+                action.target(cid, self.size)
+            }
+            None => self.policy.decide(self, agent),
+        };
+        match choice {
+            Some(target_cid) if !neighbors.iter().any(|&(nid, _)| nid == target_cid) => {
+                Err(SimulationError::OutOfBoundsMove {
+                    agent: agent.id(),
+                    from: cid,
+                    to: target_cid,
+                })
+            }
+            target => Ok(target),
+        }
+    }
+
+    /// Decides every agent's move against the current world state.
+    ///
+    /// Used by [`UpdateOrder::Synchronous`]. With the `parallel` feature
+    /// enabled, agents are asked in parallel if the policy is
+    /// [order independent](MovementPolicy::order_independent).
+    ///
+    /// ### Returns
+    /// One [`World::decide_agent_move`] result per agent, in index order.
+    fn decide_all_moves(&self) -> Vec<Option<Result<Option<usize>, SimulationError>>> {
+        #[cfg(feature = "parallel")]
+        if self.parallel && (self.actions.is_some() || self.policy.order_independent()) {
+            return (0..self.agents.len())
+                .into_par_iter()
+                .map(|id| Some(self.decide_agent_move(id)))
+                .collect();
+        }
+        (0..self.agents.len())
+            .map(|id| Some(self.decide_agent_move(id)))
+            .collect()
+    }
+
+    /// Applies a movement decision and metabolism to a single agent.
+    ///
+    /// The logic for a living agent:
+    /// - remember the current cell and its resource (agents with memory)
+    /// - deposit pheromone on the current cell if the agent found food there
+    /// - move to `target` if one was chosen and the cell is not full,
+    ///   paying the cell's terrain cost plus the intrusion cost if another
+    ///   agent owns the cell; otherwise the request is blocked
+    ///   and the agent stays
+    /// - remember the cell it arrived on, if it moved
+    /// - report the [`MoveOutcome`] to the movement policy
+    /// - then call [`Agent::update`] (metabolism)
+    /// - if the agent is dead after movement or metabolism, call
+    ///   [`World::handle_agent_death`]
+    ///
+    /// Dead agents are ignored.
+    ///
+    /// ### Parameters
+    /// - `id`: Index of the agent to update.
+    /// - `target`: Destination chosen by [`World::decide_agent_move`].
+    ///
+    /// ### Returns
+    /// - `Ok(())` if the step succeeded.
+    /// - `Err(SimulationError)` if moving or metabolizing failed.
+    fn apply_agent_step(
+        &mut self,
+        id: usize,
+        target: Option<usize>,
+    ) -> Result<(), SimulationError> {
+        if !self.agents[id].is_alive() {
+            return Ok(());
+        }
+
+        let here = self.agents[id].cid();
+        self.agents[id].remember(here, self.cells[here].cur_resource(), self.tick);
+        if self.pheromone.enabled && self.agents[id].allocated_resource() > 0 {
+            self.pheromones.deposit(here, self.pheromone.deposit);
+        }
+
+        let outcome = match target {
+            None => MoveOutcome::Stayed,
+            Some(target_cid) if self.is_cell_full(target_cid) => {
+                MoveOutcome::Blocked { target: target_cid }
+            }
+            Some(target_cid) => {
+                let from = self.agents[id].cid();
+                let cost =
+                    self.cells[target_cid].terrain_cost() + self.intrusion_cost(id, target_cid);
+                self.agents[id].move_to_with_cost(target_cid, cost)?;
+                self.spatial.relocate(id, from, target_cid);
+                self.notify_hooks(|hook, world| {
+                    hook.on_agent_moved(world, &world.agents[id], from)
+                });
+                let resource = self.cells[target_cid].cur_resource();
+                self.agents[id].remember(target_cid, resource, self.tick);
+                MoveOutcome::Moved {
+                    from,
+                    to: target_cid,
+                }
+            }
+        };
+        self.policy.on_outcome(&self.agents[id], outcome);
+
+        if !self.agents[id].is_alive() {
+            self.handle_agent_death(id);
+            return Ok(());
+        }
+
+        self.agents[id].update()?;
+
+        if !self.agents[id].is_alive() {
+            self.handle_agent_death(id);
+        }
+        Ok(())
+    }
+
+    /// Advances a single agent by one simulation step.
+    ///
+    /// The agent decides on the current state of the world and the decision
+    /// is applied immediately (see [`World::decide_agent_move`] and
+    /// [`World::apply_agent_step`]).
+    ///
+    /// ### Parameters
+    /// - `id`: Index of the agent to update.
+    ///
+    /// ### Returns
+    /// - `Ok(())` if the step succeeded.
+    /// - `Err(SimulationError)` if deciding or applying the step failed.
+    fn step_agent(&mut self, id: usize) -> Result<(), SimulationError> {
+        let target = self.decide_agent_move(id)?;
+        self.apply_agent_step(id, target)
+    }
+
+    /// Advances all agents once, following the world's [`UpdateOrder`].
+    ///
+    /// For `Sequential` and `Shuffled`, each agent decides and acts before
+    /// the next one. For `Synchronous`, all agents decide first on the same
+    /// snapshot and the decisions are applied afterwards, so no agent sees
+    /// the effects (e.g. corpses) of another agent's step in the same tick.
+    /// The decisions are applied in the order given by the [`ConflictRule`],
+    /// so that the rule decides who gets the last places in a full cell.
+    ///
+    /// A failing agent does not stop the others from being stepped.
+    ///
+    /// This is the movement phase of a tick; like
+    /// [`World::allocate_resources`] it can be run on its own, but not from
+    /// a hook.
+    ///
+    /// ### Returns
+    /// - `Ok(())` if every agent stepped successfully.
+    /// - `Err(SimulationError)` with the collected failures otherwise.
+    pub fn step_all_agents(&mut self) -> Result<(), SimulationError> {
+        let mut order = self
+            .update_order
+            .agent_order(self.agents.len(), &mut self.rng);
+        let mut errors = Vec::new();
+
+        if self.update_order == UpdateOrder::Synchronous {
+            let mut decisions = self.decide_all_moves();
+            let agents = &self.agents;
+            self.conflict_rule
+                .serve_order(&mut order, |i| agents[i].health_point(), &mut self.rng);
+            for id in order {
+                let decision = decisions[id].take().unwrap_or(Ok(None));
+                if let Err(err) = decision.and_then(|target| self.apply_agent_step(id, target)) {
+                    errors.push(err);
+                }
+            }
+        } else {
+            for id in order {
+                if let Err(err) = self.step_agent(id) {
+                    errors.push(err);
+                }
+            }
+        }
+
+        SimulationError::collect(errors)
+    }
+
+    /// Spreads the disease and advances ongoing infections.
+    ///
+    /// Infections are decided from the infected agents at the start of the
+    /// phase, so agents infected this tick only become contagious on the
+    /// next one. Susceptible agents are visited in index order and catch
+    /// the disease with [`DiseaseConfig::infection_chance`]; each new case is
+    /// recorded as a [`WorldEvent::Infection`]. Then every agent that was
+    /// already infected progresses (see [`Agent::progress_disease`]);
+    /// agents killed by the disease go through
+    /// [`World::handle_agent_death`].
+    ///
+    /// Does nothing unless the epidemic layer is enabled.
+    fn spread_disease(&mut self) {
+        let cfg = self.disease;
+        if !cfg.enabled {
+            return;
+        }
+        let mut infected_on = vec![0u32; self.cells.len()];
+        let mut was_infected = Vec::new();
+        for (i, agent) in self.agents.iter().enumerate() {
+            if agent.is_alive() && agent.health_state().is_infected() {
+                if let Some(count) = infected_on.get_mut(agent.cid()) {
+                    *count += 1;
+                }
+                was_infected.push(i);
+            }
+        }
+        if was_infected.is_empty() {
+            return;
+        }
+
+        for i in 0..self.agents.len() {
+            let agent = &self.agents[i];
+            if !agent.is_alive() || agent.health_state() != HealthState::Susceptible {
+                continue;
+            }
+            let cid = agent.cid();
+            let Some(&same_cell) = infected_on.get(cid) else {
+                continue;
+            };
+            let adjacent = self
+                .neighbor_cells_info(cid)
+                .into_iter()
+                .map(|(nid, _)| infected_on[nid])
+                .sum();
+            let chance = cfg.infection_chance(same_cell, adjacent);
+            if chance > 0.0
+                && self.rng.gen_bool(chance)
+                && self.agents[i].infect(cfg.duration, cfg.extra_need)
+            {
+                self.record_event(WorldEvent::Infection {
+                    tick: self.tick,
+                    agent: self.agents[i].id(),
+                    cid,
+                });
+            }
+        }
+
+        for i in was_infected {
+            self.agents[i].progress_disease(cfg.hp_drain, cfg.lasting_immunity);
+            if !self.agents[i].is_alive() {
+                self.handle_agent_death(i);
+            }
+        }
+    }
+
+    /// Lets agents with enough energy give birth.
+    ///
+    /// Parents are visited in index order. A child is placed on the
+    /// parent's cell, or on the first neighbor that is not full; if every
+    /// candidate is full, or [`EvolutionConfig::max_agents`] agents are
+    /// alive, the parent keeps its energy. Children get the next free id,
+    /// a mutated copy of the parent's genome and the parent's upkeep,
+    /// appetite, lifespan, senescence and memory capacity. Each birth is
+    /// recorded as a [`WorldEvent::Birth`].
+    ///
+    /// Does nothing unless the evolutionary mode is enabled.
+    fn reproduce_agents(&mut self) {
+        let cfg = self.evolution;
+        if !cfg.enabled {
+            return;
+        }
+        let mut alive = self.agents.iter().filter(|a| a.is_alive()).count();
+        for parent in 0..self.agents.len() {
+            if alive >= cfg.max_agents {
+                break;
+            }
+            let agent = &self.agents[parent];
+            if !agent.is_alive() || agent.energy() < cfg.birth_cost {
+                continue;
+            }
+            let cid = agent.cid();
+            let Some(home) = std::iter::once(cid)
+                .chain(
+                    self.neighbor_cells_info(cid)
+                        .into_iter()
+                        .map(|(nid, _)| nid),
+                )
+                .find(|&c| !self.is_cell_full(c))
+            else {
+                continue;
+            };
+            if !self.agents[parent].pay_birth(cfg.birth_cost) {
+                continue;
+            }
+
+            let child_id = self.ids.allocate(self.agents.len());
+            let parent = &self.agents[parent];
+            let parent_id = parent.id();
+            let mut child = Agent::new(
+                child_id,
+                home,
+                parent.consumption_rate(),
+                0,
+                cfg.child_hp,
+                true,
+            );
+            child.set_upkeep(parent.upkeep());
+            child.set_appetite(parent.appetite());
+            child.set_lifespan(parent.lifespan());
+            child.set_senescence(parent.senescence());
+            child.set_aggression(parent.aggression());
+            child.set_group(parent.group());
+            child.enable_memory(parent.memory().map_or(0, |m| m.capacity()));
+            let genome = parent
+                .genome()
+                .map(|g| g.mutated(cfg.mutation_rate, cfg.mutation_size, &mut self.rng));
+            child.set_genome(genome);
+
+            self.spatial.insert(self.agents.len(), home);
+            self.record_event(WorldEvent::Birth {
+                tick: self.tick,
+                parent: parent_id,
+                child: child.id(),
+                cid: home,
+            });
+            self.agents.push(child);
+            alive += 1;
+        }
+    }
+
+    /// Regenerates every cell (see [`Cell::update`]).
+    ///
+    /// With the `parallel` feature enabled, cells are updated in parallel.
+    ///
+    /// ### Returns
+    /// `(result, regen_rate, before, after)` per cell in cell order, where
+    /// `before` and `after` are the cell's resource around the update.
+    fn regenerate_cells(&mut self) -> Vec<(Result<(), SimulationError>, u32, u32, u32)> {
+        let regenerate = |cell: &mut Cell| {
+            let before = cell.cur_resource();
+            let result = cell.update();
+            (result, cell.regen_rate(), before, cell.cur_resource())
+        };
+        #[cfg(feature = "parallel")]
+        if self.parallel {
+            return self.cells.par_iter_mut().map(regenerate).collect();
+        }
+        self.cells.iter_mut().map(regenerate).collect()
+    }
+
+    /// Regenerates every cell (see [`Cell::update`]).
+    ///
+    /// This is the regeneration phase of a tick, run on the rayon thread
+    /// pool with the `parallel` feature; like [`World::allocate_resources`]
+    /// it is public so that it can be measured on its own, but hooks must
+    /// not call it. Unlike [`World::update`], it records nothing in the
+    /// ledger.
+    ///
+    /// ### Returns
+    /// - `Ok(())` if every cell regenerated.
+    /// - `Err(SimulationError)` with the collected failures otherwise.
+    pub fn regenerate_resources(&mut self) -> Result<(), SimulationError> {
+        let errors = self
+            .regenerate_cells()
+            .into_iter()
+            .filter_map(|(result, ..)| result.err())
+            .collect();
+        SimulationError::collect(errors)
+    }
+
+    /// Lets altruistic group members feed their hungry fellows.
+    ///
+    /// Does nothing while the group layer is disabled. Otherwise every
+    /// living member of an altruistic group whose allocation is below its
+    /// need asks the members of its group on its own cell, then (with
+    /// `share_adjacent`) on the neighboring cells, in agent order. Each
+    /// donor gives up to `max_gift` energy above its reserve until the
+    /// deficit is covered (see [`Agent::give_energy`]); every gift is
+    /// added to the recipient's allocation and recorded as a
+    /// [`WorldEvent::Share`].
+    fn share_food(&mut self) {
+        let cfg = self.groups;
+        if !cfg.enabled {
+            return;
+        }
+        let num_cells = self.cells.len();
+        for recipient in 0..self.agents.len() {
+            let agent = &self.agents[recipient];
+            let Some(group) = agent.group().filter(|&g| cfg.is_altruistic(g)) else {
+                continue;
+            };
+            if !agent.is_alive() || agent.cid() >= num_cells {
+                continue;
+            }
+            let mut deficit = agent.need().saturating_sub(agent.allocated_resource());
+            if deficit == 0 {
+                continue;
+            }
+
+            let cid = agent.cid();
+            let mut cells = vec![cid];
+            if cfg.share_adjacent {
+                cells.extend(
+                    self.neighbor_cells_info(cid)
+                        .into_iter()
+                        .map(|(nid, _)| nid),
+                );
+            }
+            let donors: Vec<usize> = cells
+                .iter()
+                .flat_map(|&c| self.spatial.in_cell(c).iter().copied())
+                .filter(|&d| d != recipient && self.agents[d].group() == Some(group))
+                .collect();
+            for donor in donors {
+                let gift = self.agents[donor].give_energy(deficit.min(cfg.max_gift), cfg.reserve);
+                if gift == 0 {
+                    continue;
+                }
+                self.agents[recipient].receive(gift);
+                self.record_event(WorldEvent::Share {
+                    tick: self.tick,
+                    donor: self.agents[donor].id(),
+                    recipient: self.agents[recipient].id(),
+                    amount: gift,
+                });
+                deficit -= gift;
+                if deficit == 0 {
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Lets agents claim the cells they stay on.
+    ///
+    /// Does nothing while the territory layer is disabled. Otherwise the
+    /// living agents are grouped by cell and passed to
+    /// [`TerritoryMap::update`]; each cell that changes hands is recorded
+    /// as a [`WorldEvent::Claim`].
+    fn claim_territory(&mut self) {
+        if !self.territory.enabled {
+            return;
+        }
+        let mut occupants = vec![Vec::new(); self.cells.len()];
+        let mut living = HashSet::new();
+        for agent in self.agents.iter().filter(|a| a.is_alive()) {
+            living.insert(agent.id());
+            if let Some(ids) = occupants.get_mut(agent.cid()) {
+                ids.push(agent.id());
+            }
+        }
+        let claims = self
+            .territories
+            .update(&self.territory, &occupants, &living);
+        for (cid, agent) in claims {
+            self.record_event(WorldEvent::Claim {
+                tick: self.tick,
+                agent,
+                cid,
+            });
+        }
+    }
+}
+
+impl Updatable for World {
+    /// Advances the entire world by one simulation step.
+    ///
+    /// The step order is:
+    /// 1. Update all cells (resource regeneration).
+    /// 2. Allocate resources from cells to agents.
+    /// 3. Let group members share food with hungry fellows (group layer).
+    /// 4. Step each agent (movement + metabolism + death handling).
+    /// 5. Spread the disease and advance infections (epidemic layer).
+    /// 6. Let agents with enough energy reproduce (evolutionary mode).
+    /// 7. Let agents claim the cells they stay on (territory layer).
+    /// 8. Diffuse and evaporate the pheromone field, if the layer is enabled.
+    ///
+    /// Failures in one phase do not abort the step: every phase runs, the
+    /// errors are collected, and the tick counter always advances.
+    ///
+    /// The deaths, fights, gifts, infections, births and claims of the step
+    /// are available from [`World::events`] afterwards.
+    ///
+    /// With the `parallel` feature, cell regeneration, allocation planning
+    /// and synchronous move decisions run on the rayon thread pool; see
+    /// [`World::set_parallel`].
+    ///
+    /// When debug checks are enabled, the step also records a
+    /// [`ResourceLedger`] and checks the world invariants at the end.
+    /// Every [`World::compaction_interval`] ticks, dead agents are then
+    /// moved into the graveyard (see [`World::compact`]).
+    ///
+    /// Registered [`WorldHook`]s are called before the first phase, after
+    /// steps 1 and 2, for every move and death, and at the very end.
+    ///
+    /// ### Returns
+    /// - `Ok(())` if no phase reported a failure.
+    /// - `Err(SimulationError)` with the single failure, or
+    ///   `SimulationError::Multiple` if several were collected.
+    fn update(&mut self) -> Result<(), SimulationError> {
+        let mut errors = Vec::new();
+        self.events.clear();
+        self.run_hooks(|hook, world| hook.before_tick(world));
+
+        self.mid_tick = true;
+        let dead_before = if self.debug_checks {
+            self.ledger = Some(ResourceLedger::open(self.tick, &self.cells));
+            ledger::dead_positions(&self.agents)
+        } else {
+            Vec::new()
+        };
+
+        for (result, regen_rate, before, after) in self.regenerate_cells() {
+            if let Err(err) = result {
+                errors.push(err);
+            }
+            if let Some(ledger) = &mut self.ledger {
+                ledger.regenerated += ledger.record_deposit(regen_rate, before, after);
+            }
+        }
+        self.run_hooks(|hook, world| hook.after_regen(world));
+
+        if let Err(err) = self.allocate_resources() {
+            errors.push(err);
+        }
+        self.run_hooks(|hook, world| hook.after_allocation(world));
+        self.share_food();
+        if let Err(err) = self.step_all_agents() {
+            errors.push(err);
+        }
+        self.spread_disease();
+        self.reproduce_agents();
+        self.claim_territory();
+        if self.pheromone.enabled {
+            self.pheromones.step(&self.pheromone);
+        }
+
+        if let Some(ledger) = &mut self.ledger {
+            ledger.total_after = ledger::total_resource(&self.cells);
+            errors.extend(ledger::check_invariants(
+                ledger,
+                &self.cells,
+                &self.agents,
+                &dead_before,
+            ));
+        }
+        self.mid_tick = false;
+
+        if self
+            .compaction_interval
+            .is_some_and(|interval| (self.tick + 1).is_multiple_of(interval))
+        {
+            self.compact();
+        }
+        self.run_hooks(|hook, world| hook.after_tick(world));
+
+        self.tick += 1;
+        SimulationError::collect(errors)
+    }
+}
+
+/// Mutable access to one agent, returned by [`World::agent_mut`].
+///
+/// The guard dereferences to the [`Agent`]. When it is dropped, the world
+/// catches up with what changed:
+/// - a moved agent is filed under its new cell in the spatial index
+/// - an agent that died is handled like any other death (corpse, event,
+///   policy and [`WorldHook::on_agent_died`])
+/// - an agent replaced by another value keeps its stable id
+pub struct AgentMut<'a> {
+    world: &'a mut World,
+    id: usize,
+    index: usize,
+    cid: usize,
+    alive: bool,
+}
+
+impl Deref for AgentMut<'_> {
+    type Target = Agent;
+
+    fn deref(&self) -> &Agent {
+        &self.world.agents[self.index]
+    }
+}
+
+impl DerefMut for AgentMut<'_> {
+    fn deref_mut(&mut self) -> &mut Agent {
+        &mut self.world.agents[self.index]
+    }
+}
+
+impl Drop for AgentMut<'_> {
+    fn drop(&mut self) {
+        let world = &mut *self.world;
+        let agent = &mut world.agents[self.index];
+        if agent.id() != self.id {
+            agent.set_id(self.id);
+        }
+        let (cid, alive) = (agent.cid(), agent.is_alive());
+        if self.alive && cid != self.cid {
+            world.spatial.relocate(self.index, self.cid, cid);
+        } else if !self.alive && alive {
+            world.spatial.insert(self.index, cid);
+        }
+        if self.alive && !alive {
+            world.handle_agent_death(self.index);
+        }
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -384,6 +2397,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::field_reassign_with_default)]
     fn from_config_creates_world_with_correct_size_and_cell_count() {
         let mut cfg = WorldConfig::default();
         cfg.width = 8;
@@ -448,7 +2462,7 @@ mod tests {
         let size = (1, 1);
         let mut world = World::new(size, cells, agents);
 
-        world.allocate_resources();
+        world.allocate_resources().unwrap();
 
         let cell = world.cell(0);
         assert_eq!(cell.cur_resource(), 0);
@@ -481,6 +2495,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::vec_init_then_push)]
     fn step_agent_moves_toward_richer_neighbor_and_metabolizes() {
         // Two cells in a row: [start][target with resource]
         let size = (2, 1);
@@ -494,8 +2509,8 @@ mod tests {
         let agents = vec![Agent::new(0, 0, 5, 0, 5, true)];
         let mut world = World::new(size, cells, agents);
 
-        world.allocate_resources();
-        world.step_agent(0);
+        world.allocate_resources().unwrap();
+        world.step_agent(0).unwrap();
 
         let a = &world.agents[0];
 
@@ -515,4 +2530,860 @@ mod tests {
             world.update().unwrap();
         }
     }
+
+    #[test]
+    fn try_from_config_rejects_invalid_config() {
+        let cfg = WorldConfig {
+            min_resource: 30,
+            max_resource: 10,
+            ..WorldConfig::default()
+        };
+        let err = World::try_from_config(cfg).err().unwrap();
+        assert!(matches!(err, SimulationError::InvalidConfig(_)));
+        assert!(err.to_string().contains("min_resource"));
+    }
+
+    #[test]
+    fn try_cell_reports_invalid_id() {
+        let world = World::new((1, 1), vec![Cell::new(0, 0, 10, 0, 1)], Vec::new());
+        assert!(world.try_cell(0).is_ok());
+        assert_eq!(
+            world.try_cell(3).err(),
+            Some(SimulationError::InvalidCellId {
+                cid: 3,
+                num_cells: 1
+            })
+        );
+    }
+
+    #[test]
+    fn world_update_surfaces_agents_on_invalid_cells() {
+        let cells = vec![Cell::new(0, 10, 100, 0, 10)];
+        let agents = vec![
+            Agent::new(0, 0, 5, 0, 5, true),
+            Agent::new(1, 7, 5, 0, 5, true),
+        ];
+        let mut world = World::new((1, 1), cells, agents);
+
+        let err = world.update().unwrap_err();
+        // Reported once by allocation and once by the agent step.
+        assert_eq!(
+            err,
+            SimulationError::Multiple(vec![
+                SimulationError::InvalidCellId {
+                    cid: 7,
+                    num_cells: 1
+                };
+                2
+            ])
+        );
+        // The valid agent was still fed and the tick still advanced.
+        assert_eq!(world.cell(0).cur_resource(), 5);
+        assert_eq!(world.agents()[0].health_point(), 5);
+        assert_eq!(world.tick(), 1);
+    }
+
+    #[test]
+    fn debug_checks_record_a_balanced_ledger() {
+        let cells = vec![
+            Cell::new(0, 8, 10, 4, 5),
+            Cell::new(1, 0, 10, 0, 5),
+            Cell::new(2, 0, 10, 0, 5),
+        ];
+        let agents = vec![
+            Agent::new(0, 0, 3, 0, 5, true),
+            // Starving agent with no food in sight; it dies and leaves a corpse.
+            Agent::new(1, 2, 3, 0, 1, true),
+        ];
+        let mut world = World::new((3, 1), cells, agents);
+        world.set_debug_checks(true);
+
+        world.update().unwrap();
+
+        let ledger = *world.last_ledger().unwrap();
+        assert_eq!(ledger.tick, 0);
+        assert_eq!(ledger.total_before, 8);
+        // Cell 0 regenerates 4 but is capped at 10.
+        assert_eq!(ledger.regenerated, 2);
+        assert_eq!(ledger.consumed, 3);
+        assert_eq!(ledger.corpse_deposited, 5);
+        assert_eq!(ledger.wasted, 2);
+        assert_eq!(ledger.total_after, 12);
+        assert!(ledger.is_balanced());
+    }
+
+    #[test]
+    fn debug_checks_report_agents_outside_the_grid() {
+        let cells = vec![Cell::new(0, 0, 10, 0, 5)];
+        let agents = vec![Agent::new(4, 9, 3, 0, 5, false)];
+        let mut world = World::new((1, 1), cells, agents);
+        world.set_debug_checks(true);
+
+        let err = world.update().unwrap_err();
+        assert_eq!(
+            err,
+            SimulationError::InvariantViolation {
+                tick: 0,
+                entity: Entity::Agent(4),
+                reason: "stands on cell 9 outside the grid".into(),
+            }
+        );
+    }
+
+    #[test]
+    fn debug_checks_hold_over_random_worlds() {
+        let mut world = World::from_config(WorldConfig::default());
+        world.set_debug_checks(true);
+        for _ in 0..50 {
+            world.update().unwrap();
+        }
+
+        world.set_debug_checks(false);
+        assert!(world.last_ledger().is_none());
+    }
+
+    /// Builds the "corpse" scenario used by the update-order tests.
+    ///
+    /// A starving agent on cell 0 dies this tick and drops a corpse; a hungry
+    /// agent on cell 1 only moves onto cell 0 if it decides after the corpse
+    /// was dropped. `starving_first` selects which agent gets index 0.
+    fn corpse_world(starving_first: bool, order: UpdateOrder) -> World {
+        let cells = (0..3).map(|id| Cell::new(id, 0, 100, 0, 10)).collect();
+        let starving = Agent::new(0, 0, 3, 0, 1, true);
+        let hungry = Agent::new(1, 1, 3, 0, 5, true);
+        let agents = if starving_first {
+            vec![starving, hungry]
+        } else {
+            vec![hungry, starving]
+        };
+        let mut world = World::new((3, 1), cells, agents);
+        world.set_update_order(order);
+        world
+    }
+
+    /// Returns the cell of the agent with the given id.
+    fn cell_of(world: &World, id: usize) -> usize {
+        world.agents().iter().find(|a| a.id() == id).unwrap().cid()
+    }
+
+    #[test]
+    fn sequential_order_favours_low_indices() {
+        let mut world = corpse_world(true, UpdateOrder::Sequential);
+        world.update().unwrap();
+        assert_eq!(cell_of(&world, 1), 0);
+
+        let mut world = corpse_world(false, UpdateOrder::Sequential);
+        world.update().unwrap();
+        assert_eq!(cell_of(&world, 1), 1);
+    }
+
+    #[test]
+    fn synchronous_order_does_not_depend_on_index() {
+        for starving_first in [true, false] {
+            let mut world = corpse_world(starving_first, UpdateOrder::Synchronous);
+            world.update().unwrap();
+            assert_eq!(cell_of(&world, 1), 1);
+        }
+
+        let cfg = WorldConfig {
+            seed: Some(11),
+            update_order: UpdateOrder::Synchronous,
+            ..WorldConfig::default()
+        };
+        let mut forward = World::from_config(cfg);
+        let mut reversed = World::from_config(cfg);
+        reversed.agents.reverse();
+        for (i, agent) in reversed.agents.iter().enumerate() {
+            reversed.ids.relocate(agent.id(), i);
+        }
+        reversed.spatial = SpatialIndex::new(reversed.size, &reversed.agents);
+
+        for _ in 0..30 {
+            forward.update().unwrap();
+            reversed.update().unwrap();
+        }
+
+        let state = |w: &World| {
+            let mut agents: Vec<_> = w
+                .agents()
+                .iter()
+                .map(|a| (a.id(), a.cid(), a.health_point(), a.is_alive()))
+                .collect();
+            agents.sort_unstable();
+            let cells: Vec<_> = w.cells().iter().map(|c| c.cur_resource()).collect();
+            (agents, cells)
+        };
+        assert_eq!(state(&forward), state(&reversed));
+    }
+
+    #[test]
+    fn shuffled_order_removes_the_index_bias() {
+        let mut moved = 0;
+        for seed in 0..200 {
+            let mut world = corpse_world(false, UpdateOrder::Shuffled);
+            world.set_seed(seed);
+            world.update().unwrap();
+            if cell_of(&world, 1) == 0 {
+                moved += 1;
+            }
+        }
+        // Each agent goes first in roughly half of the ticks.
+        assert!((60..=140).contains(&moved), "moved {moved} times");
+    }
+
+    #[test]
+    fn seeded_worlds_are_reproducible() {
+        let cfg = WorldConfig {
+            seed: Some(3),
+            update_order: UpdateOrder::Shuffled,
+            ..WorldConfig::default()
+        };
+        let mut a = World::from_config(cfg);
+        let mut b = World::from_config(cfg);
+        for _ in 0..20 {
+            a.update().unwrap();
+            b.update().unwrap();
+        }
+
+        let cids = |w: &World| w.agents().iter().map(|a| a.cid()).collect::<Vec<_>>();
+        assert_eq!(cids(&a), cids(&b));
+    }
+
+    type OutcomeLog = std::sync::Arc<std::sync::Mutex<Vec<(usize, MoveOutcome)>>>;
+
+    /// Greedy policy that records every reported outcome.
+    struct RecordingPolicy(OutcomeLog);
+
+    impl MovementPolicy for RecordingPolicy {
+        fn name(&self) -> &'static str {
+            "recording"
+        }
+
+        fn decide(&self, world: &World, agent: &Agent) -> Option<usize> {
+            GreedyPolicy.decide(world, agent)
+        }
+
+        fn on_outcome(&mut self, agent: &Agent, outcome: MoveOutcome) {
+            self.0.lock().unwrap().push((agent.id(), outcome));
+        }
+    }
+
+    /// Two hungry agents on the outer cells of a 3×1 grid both want the rich
+    /// middle cell, which only has room for one of them.
+    fn contested_world(order: UpdateOrder, rule: ConflictRule) -> (World, OutcomeLog) {
+        let cells = vec![
+            Cell::new(0, 0, 100, 0, 10),
+            Cell::new(1, 50, 100, 0, 10),
+            Cell::new(2, 0, 100, 0, 10),
+        ];
+        let agents = vec![
+            Agent::new(0, 0, 3, 0, 4, true),
+            Agent::new(1, 2, 3, 0, 9, true),
+        ];
+        let mut world = World::new((3, 1), cells, agents);
+        let outcomes = std::sync::Arc::default();
+        world.set_policy(Box::new(RecordingPolicy(std::sync::Arc::clone(&outcomes))));
+        world.set_update_order(order);
+        world.set_cell_capacity(Some(1));
+        world.set_conflict_rule(rule);
+        (world, outcomes)
+    }
+
+    #[test]
+    fn full_cells_block_later_movers_in_sequential_order() {
+        let (mut world, outcomes) =
+            contested_world(UpdateOrder::Sequential, ConflictRule::Stronger);
+        world.step_all_agents().unwrap();
+
+        assert_eq!(cell_of(&world, 0), 1);
+        assert_eq!(cell_of(&world, 1), 2);
+        assert_eq!(world.living_agents_on(1), 1);
+        assert_eq!(
+            *outcomes.lock().unwrap(),
+            vec![
+                (0, MoveOutcome::Moved { from: 0, to: 1 }),
+                (1, MoveOutcome::Blocked { target: 1 }),
+            ]
+        );
+        // The blocked agent paid no movement cost, only metabolism.
+        assert_eq!(world.agents()[1].health_point(), 8);
+    }
+
+    #[test]
+    fn synchronous_conflicts_follow_the_conflict_rule() {
+        let (mut world, _) = contested_world(UpdateOrder::Synchronous, ConflictRule::FirstCome);
+        world.step_all_agents().unwrap();
+        assert_eq!(cell_of(&world, 0), 1);
+        assert_eq!(cell_of(&world, 1), 2);
+
+        let (mut world, outcomes) =
+            contested_world(UpdateOrder::Synchronous, ConflictRule::Stronger);
+        world.step_all_agents().unwrap();
+        assert_eq!(cell_of(&world, 0), 0);
+        assert_eq!(cell_of(&world, 1), 1);
+        assert!(
+            outcomes
+                .lock()
+                .unwrap()
+                .contains(&(0, MoveOutcome::Blocked { target: 1 }))
+        );
+    }
+
+    #[test]
+    fn from_config_respects_cell_capacity() {
+        let cfg = WorldConfig {
+            width: 4,
+            height: 4,
+            min_agents: 16,
+            max_agents: 16,
+            cell_capacity: Some(1),
+            seed: Some(5),
+            ..WorldConfig::default()
+        };
+        let mut world = World::from_config(cfg);
+        for _ in 0..20 {
+            world.update().unwrap();
+            for cid in 0..16 {
+                assert!(world.living_agents_on(cid) <= 1);
+            }
+        }
+
+        let too_crowded = WorldConfig {
+            max_agents: 17,
+            ..cfg
+        };
+        assert!(matches!(
+            too_crowded.validate(),
+            Err(SimulationError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn perceive_lists_cells_within_radius() {
+        let cells = (0..25)
+            .map(|id| Cell::new(id, id as u32, 100, 0, 5))
+            .collect();
+        let agents = vec![
+            Agent::new(0, 12, 1, 0, 5, true),
+            Agent::new(1, 0, 1, 0, 5, true),
+            Agent::new(2, 14, 1, 0, 5, true),
+        ];
+        let mut world = World::new((5, 5), cells, agents);
+        world.set_perception(PerceptionConfig {
+            radius: 2,
+            see_agents: true,
+            cost_per_radius: 1,
+        });
+
+        // Centre of a 5×5 grid sees a diamond of 13 cells.
+        let view = world.perceive(12);
+        assert_eq!(view.cells.len(), 13);
+        assert_eq!(view.cells[0].cid, 12);
+        assert!(view.cells.iter().all(|c| c.distance <= 2));
+        assert_eq!(view.best_cell().unwrap().cid, 22);
+
+        // Agent 1 in the corner is 4 moves away and therefore not visible.
+        let seen: Vec<usize> = view.agents.iter().map(|a| a.id).collect();
+        assert_eq!(seen, vec![0, 2]);
+
+        // Corner view is clipped to the grid.
+        assert_eq!(world.perceive(0).cells.len(), 6);
+
+        // Radius 2 costs one extra resource per step.
+        assert_eq!(world.agents()[0].need(), 2);
+    }
+
+    #[test]
+    fn step_toward_reduces_distance() {
+        let cells = (0..9).map(|id| Cell::new(id, 0, 100, 0, 5)).collect();
+        let mut world = World::new((3, 3), cells, Vec::new());
+        assert_eq!(world.step_toward(0, 8), Some(3));
+        world.cells[1].add_resource(5);
+        assert_eq!(world.step_toward(0, 8), Some(1));
+        assert_eq!(world.step_toward(4, 4), None);
+    }
+
+    #[test]
+    fn fed_agents_leave_a_pheromone_trail() {
+        let cells = vec![
+            Cell::new(0, 10, 100, 0, 5),
+            Cell::new(1, 0, 100, 0, 5),
+            Cell::new(2, 0, 100, 0, 5),
+        ];
+        let agents = vec![Agent::new(0, 0, 2, 0, 5, true)];
+        let mut world = World::new((3, 1), cells, agents);
+        world.update().unwrap();
+        assert_eq!(world.pheromones().max(), 0.0);
+
+        world.set_pheromone(PheromoneConfig {
+            enabled: true,
+            ..PheromoneConfig::default()
+        });
+        world.update().unwrap();
+        let field = world.pheromones();
+        assert!(field.get(0) > field.get(1));
+        assert!(field.get(1) > 0.0);
+        assert_eq!(field.get(2), 0.0);
+
+        world.set_pheromone(PheromoneConfig {
+            enabled: true,
+            deposit: 0.0,
+            evaporation: 1.0,
+            diffusion: 0.0,
+        });
+        world.update().unwrap();
+        assert_eq!(world.pheromones().max(), 0.0);
+    }
+
+    #[test]
+    fn old_age_deaths_are_reported_as_events_and_statistics() {
+        let cells = vec![Cell::new(0, 100, 100, 10, 10)];
+        let agents = vec![Agent::new(0, 0, 1, 0, 50, true)];
+        let mut world = World::new((1, 1), cells, agents);
+        world.set_aging(AgingConfig {
+            enabled: true,
+            min_lifespan: 3,
+            max_lifespan: 3,
+            senescence: 0,
+        });
+
+        world.update().unwrap();
+        world.update().unwrap();
+        assert!(world.events().is_empty());
+        world.update().unwrap();
+
+        assert_eq!(
+            world.events(),
+            &[WorldEvent::Death {
+                tick: 2,
+                agent: 0,
+                cid: 0,
+                age: 3,
+                cause: DeathCause::OldAge,
+            }]
+        );
+        assert_eq!(world.statistics().deaths(DeathCause::OldAge), 1);
+        assert_eq!(world.statistics().mean_age_at_death(), Some(3.0));
+
+        world.update().unwrap();
+        assert!(world.events().is_empty());
+        assert_eq!(world.statistics().total_deaths(), 1);
+    }
+
+    #[test]
+    fn disease_spreads_between_co_located_agents() {
+        let cells = (0..3).map(|id| Cell::new(id, 100, 100, 10, 10)).collect();
+        let mut sick = Agent::new(0, 0, 1, 0, 50, true);
+        sick.infect(2, 1);
+        let agents = vec![
+            sick,
+            Agent::new(1, 0, 1, 0, 50, true),
+            Agent::new(2, 2, 1, 0, 50, true),
+        ];
+        let mut world = World::new((3, 1), cells, agents);
+        world.set_disease(DiseaseConfig {
+            enabled: true,
+            transmission: 1.0,
+            adjacent_transmission: 0.0,
+            duration: 2,
+            ..DiseaseConfig::default()
+        });
+
+        world.update().unwrap();
+        assert_eq!(
+            world.events(),
+            &[WorldEvent::Infection {
+                tick: 0,
+                agent: 1,
+                cid: 0,
+            }]
+        );
+        assert_eq!(
+            world.agents()[0].health_state(),
+            HealthState::Infected { remaining: 1 }
+        );
+        assert_eq!(world.agents()[2].health_state(), HealthState::Susceptible);
+
+        world.update().unwrap();
+        world.update().unwrap();
+        assert_eq!(world.agents()[0].health_state(), HealthState::Recovered);
+        assert_eq!(world.agents()[1].health_state(), HealthState::Recovered);
+        assert_eq!(world.statistics().infections, 1);
+    }
+
+    #[test]
+    fn owners_are_fed_first_and_intruders_pay_extra() {
+        let cells = vec![Cell::new(0, 4, 100, 0, 5), Cell::new(1, 0, 100, 0, 5)];
+        let agents = vec![
+            Agent::new(0, 0, 3, 0, 10, true),
+            Agent::new(1, 0, 3, 0, 10, true),
+            Agent::new(2, 1, 3, 0, 10, true),
+        ];
+        let mut world = World::new((2, 1), cells, agents);
+        world.set_territory(TerritoryConfig {
+            enabled: true,
+            claim_after: 1,
+            intrusion_cost: 2,
+            ..TerritoryConfig::default()
+        });
+        world.claim_territory();
+        assert_eq!(world.territories().owner(0), Some(0));
+        assert_eq!(world.territories().owner(1), Some(2));
+        assert_eq!(
+            world.events(),
+            &[
+                WorldEvent::Claim {
+                    tick: 0,
+                    agent: 0,
+                    cid: 0,
+                },
+                WorldEvent::Claim {
+                    tick: 0,
+                    agent: 2,
+                    cid: 1,
+                },
+            ]
+        );
+
+        world.allocate_resources().unwrap();
+        assert_eq!(world.agents()[0].allocated_resource(), 3);
+        assert_eq!(world.agents()[1].allocated_resource(), 1);
+
+        // Entering agent 2's cell costs the terrain cost plus the intrusion cost.
+        world.apply_agent_step(1, Some(1)).unwrap();
+        assert_eq!(world.agents()[1].health_point(), 10 - 1 - 2 - 1);
+
+        world.set_territory(TerritoryConfig::default());
+        assert_eq!(world.territories().owner(0), None);
+    }
+
+    #[test]
+    fn aggressive_agents_fight_over_scarce_cells() {
+        let make_world = |aggression: f32, fight_cost: u32| {
+            let cells = vec![Cell::new(0, 2, 100, 0, 5)];
+            let agents = vec![
+                Agent::new(0, 0, 3, 0, 5, true),
+                Agent::new(1, 0, 3, 0, 5, true),
+            ];
+            let mut world = World::new((1, 1), cells, agents);
+            world.set_seed(3);
+            world.set_combat(CombatConfig {
+                enabled: true,
+                fight_cost,
+                min_aggression: aggression,
+                max_aggression: aggression,
+            });
+            world
+        };
+
+        // Doves share.
+        let mut world = make_world(0.0, 1);
+        world.allocate_resources().unwrap();
+        assert!(world.events().is_empty());
+        assert_eq!(world.agents()[0].allocated_resource(), 1);
+        assert_eq!(world.agents()[1].allocated_resource(), 1);
+
+        // Hawks fight; the winner takes the whole cell.
+        let mut world = make_world(1.0, 1);
+        world.allocate_resources().unwrap();
+        let [WorldEvent::Fight { winner, loser, .. }] = world.events() else {
+            panic!("expected one fight, got {:?}", world.events());
+        };
+        assert_eq!(world.agents()[*winner].allocated_resource(), 2);
+        assert_eq!(world.agents()[*loser].allocated_resource(), 0);
+        assert!(world.agents().iter().all(|a| a.health_point() == 4));
+        assert_eq!(world.statistics().fights, 1);
+
+        // A deadly fight leaves nobody to eat.
+        let mut world = make_world(1.0, 5);
+        world.allocate_resources().unwrap();
+        assert_eq!(world.statistics().deaths(DeathCause::Combat), 2);
+        assert_eq!(world.cells()[0].cur_resource(), 2 + 2 * 5);
+    }
+
+    #[test]
+    fn combat_settings_keep_aggressions_while_enabled() {
+        let cells = vec![Cell::new(0, 0, 100, 0, 5)];
+        let agents = (0..4).map(|id| Agent::new(id, 0, 3, 0, 5, true)).collect();
+        let mut world = World::new((1, 1), cells, agents);
+        world.set_seed(5);
+        let mut combat = CombatConfig {
+            enabled: true,
+            ..CombatConfig::default()
+        };
+        world.set_combat(combat);
+        let drawn: Vec<f32> = world.agents().iter().map(Agent::aggression).collect();
+
+        combat.fight_cost += 1;
+        world.set_combat(combat);
+        let kept: Vec<f32> = world.agents().iter().map(Agent::aggression).collect();
+        assert_eq!(kept, drawn);
+
+        combat.max_aggression = 0.1;
+        world.set_combat(combat);
+        assert!(world.agents().iter().all(|a| a.aggression() <= 0.1));
+
+        world.set_combat(CombatConfig::default());
+        assert!(world.agents().iter().all(|a| a.aggression() == 0.0));
+    }
+
+    #[test]
+    fn group_settings_keep_memberships_while_enabled() {
+        let cells = vec![Cell::new(0, 0, 100, 0, 5)];
+        let agents = (0..8).map(|id| Agent::new(id, 0, 3, 0, 5, true)).collect();
+        let mut world = World::new((1, 1), cells, agents);
+        world.set_seed(5);
+        let mut groups = GroupConfig {
+            enabled: true,
+            groups: 4,
+            ..GroupConfig::default()
+        };
+        world.set_groups(groups);
+        let drawn: Vec<Option<usize>> = world.agents().iter().map(Agent::group).collect();
+
+        groups.max_gift += 1;
+        world.set_groups(groups);
+        let kept: Vec<Option<usize>> = world.agents().iter().map(Agent::group).collect();
+        assert_eq!(kept, drawn);
+
+        // Members of the removed groups move; the others stay.
+        groups.groups = 2;
+        world.set_groups(groups);
+        for (agent, before) in world.agents().iter().zip(drawn) {
+            assert!(agent.group().is_some_and(|g| g < 2));
+            if before.is_some_and(|g| g < 2) {
+                assert_eq!(agent.group(), before);
+            }
+        }
+
+        world.set_groups(GroupConfig::default());
+        assert!(world.agents().iter().all(|a| a.group().is_none()));
+    }
+
+    #[test]
+    fn altruistic_groups_feed_hungry_members() {
+        let cells = vec![Cell::new(0, 0, 100, 0, 5), Cell::new(1, 0, 100, 0, 5)];
+        let mut agents = vec![
+            Agent::new(0, 0, 2, 0, 5, true),
+            Agent::new(1, 1, 1, 0, 5, true),
+            Agent::new(2, 0, 2, 0, 5, true),
+            Agent::new(3, 0, 1, 0, 5, true),
+        ];
+        for (agent, group) in agents.iter_mut().zip([0, 0, 1, 1]) {
+            agent.receive(5);
+            agent.update().unwrap();
+            agent.set_group(Some(group));
+        }
+        let mut world = World::new((2, 1), cells, agents);
+        world.groups = GroupConfig {
+            enabled: true,
+            reserve: 1,
+            max_gift: 1,
+            ..GroupConfig::default()
+        };
+        world.agents[1].receive(1);
+
+        world.share_food();
+        // Agent 0 gets one unit from its neighbor; group 1 is selfish.
+        assert_eq!(world.agents()[0].allocated_resource(), 1);
+        assert_eq!(world.agents()[2].allocated_resource(), 0);
+        assert_eq!(
+            world.events(),
+            &[WorldEvent::Share {
+                tick: 0,
+                donor: 1,
+                recipient: 0,
+                amount: 1,
+            }]
+        );
+
+        let stats = world.group_statistics();
+        assert_eq!(stats.len(), 2);
+        assert_eq!((stats[0].living, stats[0].shared), (2, 1));
+        assert_eq!((stats[1].living, stats[1].shared), (2, 0));
+        assert_eq!(world.statistics().shared, 1);
+    }
+
+    #[test]
+    fn agent_mut_keeps_the_index_and_ids_in_sync() {
+        let cells = (0..3).map(|id| Cell::new(id, 0, 100, 0, 5)).collect();
+        let agents = vec![
+            Agent::new(0, 0, 1, 0, 5, true),
+            Agent::new(1, 0, 1, 0, 5, true),
+        ];
+        let mut world = World::new((3, 1), cells, agents);
+
+        world.agent_mut(0).unwrap().move_to(2).unwrap();
+        assert_eq!(
+            world.agents_in_cell(0).map(Agent::id).collect::<Vec<_>>(),
+            [1]
+        );
+        assert_eq!(
+            world.agents_in_cell(2).map(Agent::id).collect::<Vec<_>>(),
+            [0]
+        );
+
+        *world.agent_mut(1).unwrap() = Agent::new(7, 1, 1, 0, 5, true);
+        assert_eq!(world.agents()[1].id(), 1);
+        assert_eq!(
+            world.agents_in_cell(1).map(Agent::id).collect::<Vec<_>>(),
+            [1]
+        );
+
+        world.agent_mut(1).unwrap().wound(5);
+        assert_eq!(world.agents_in_cell(1).count(), 0);
+        assert_eq!(world.cell(1).cur_resource(), CORPSE_RESOURCE);
+        assert!(world.agent_mut(9).is_none());
+    }
+
+    #[test]
+    fn regenerate_resources_runs_only_the_regeneration_phase() {
+        let cells = vec![Cell::new(0, 1, 10, 2, 5), Cell::new(1, 9, 10, 3, 5)];
+        let agents = vec![Agent::new(0, 0, 5, 0, 5, true)];
+        let mut world = World::new((2, 1), cells, agents);
+
+        world.regenerate_resources().unwrap();
+        assert_eq!(world.cell(0).cur_resource(), 3);
+        assert_eq!(world.cell(1).cur_resource(), 10);
+        assert_eq!(world.tick(), 0);
+        assert_eq!(world.agents()[0].cid(), 0);
+    }
+
+    #[test]
+    fn far_away_agent_ids_are_replaced() {
+        let cells = (0..2).map(|id| Cell::new(id, 0, 100, 0, 5)).collect();
+        let agents = vec![
+            Agent::new(usize::MAX >> 32, 0, 1, 0, 5, true),
+            Agent::new(1, 1, 1, 0, 5, true),
+        ];
+        let world = World::new((2, 1), cells, agents);
+
+        assert_eq!(world.agents()[0].id(), 0);
+        assert_eq!(world.agent(0).unwrap().cid(), 0);
+        assert_eq!(world.agent(1).unwrap().cid(), 1);
+        assert_eq!(world.agent_ids().len(), 2);
+    }
+
+    #[test]
+    fn compaction_archives_the_dead_and_keeps_ids_stable() {
+        let cells = vec![Cell::new(0, 100, 100, 10, 10)];
+        let mut agents: Vec<Agent> = (0..3).map(|id| Agent::new(id, 0, 1, 0, 50, true)).collect();
+        agents[1].set_lifespan(Some(2));
+        agents[1].set_genome(Some(Genome::random(2, &mut StdRng::seed_from_u64(1))));
+        let mut world = World::new((1, 1), cells, agents);
+        world.set_compaction_interval(Some(2));
+
+        world.update().unwrap();
+        assert_eq!(world.agents().len(), 3);
+        world.update().unwrap();
+
+        assert_eq!(world.agents().len(), 2);
+        assert_eq!(world.agent(2).map(|a| a.id()), Some(2));
+        assert_eq!(world.agent_ids().index_of(2), Some(1));
+        assert!(world.agent(1).is_none());
+        assert_eq!(world.graveyard().len(), 1);
+        let record = &world.graveyard()[0];
+        assert_eq!(record.id, 1);
+        assert_eq!(world.grave(1), Some(record));
+        assert!(world.grave(2).is_none());
+        assert_eq!(record.cause, Some(DeathCause::OldAge));
+        assert_eq!(record.death_tick, Some(1));
+        assert!(world.best_genome().is_some());
+        assert_eq!(world.compact(), 0);
+    }
+
+    #[test]
+    fn spatial_index_follows_moves_births_deaths_and_compaction() {
+        let cfg = WorldConfig {
+            width: 15,
+            height: 15,
+            min_agents: 150,
+            max_agents: 150,
+            seed: Some(5),
+            cell_capacity: Some(3),
+            compaction_interval: Some(7),
+            evolution: EvolutionConfig {
+                enabled: true,
+                ..EvolutionConfig::default()
+            },
+            combat: CombatConfig {
+                enabled: true,
+                ..CombatConfig::default()
+            },
+            ..WorldConfig::default()
+        };
+        let mut world = World::from_config(cfg);
+        for _ in 0..40 {
+            world.update().unwrap();
+            assert_eq!(world.spatial, SpatialIndex::new(world.size, &world.agents));
+        }
+
+        let living = world.agents().iter().filter(|a| a.is_alive()).count();
+        assert_eq!(world.agents_in_rect((0, 0), (99, 99)).count(), living);
+        assert_eq!(world.agents_within(112, 30).count(), living);
+        let cid = world.agents().iter().find(|a| a.is_alive()).unwrap().cid();
+        assert!(world.agents_in_cell(cid).all(|a| a.cid() == cid));
+        assert_eq!(
+            world.agents_in_cell(cid).count(),
+            world.living_agents_on(cid)
+        );
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_and_sequential_paths_agree() {
+        let cfg = WorldConfig {
+            width: 30,
+            height: 30,
+            min_agents: 400,
+            max_agents: 400,
+            seed: Some(11),
+            evolution: EvolutionConfig {
+                enabled: true,
+                ..EvolutionConfig::default()
+            },
+            disease: DiseaseConfig {
+                enabled: true,
+                ..DiseaseConfig::default()
+            },
+            territory: TerritoryConfig {
+                enabled: true,
+                ..TerritoryConfig::default()
+            },
+            combat: CombatConfig {
+                enabled: true,
+                ..CombatConfig::default()
+            },
+            groups: GroupConfig {
+                enabled: true,
+                ..GroupConfig::default()
+            },
+            ..WorldConfig::default()
+        };
+        let state = |world: &World| {
+            let agents: Vec<_> = world
+                .agents()
+                .iter()
+                .map(|a| (a.id(), a.cid(), a.health_point(), a.energy(), a.is_alive()))
+                .collect();
+            let cells: Vec<_> = world.cells().iter().map(|c| c.cur_resource()).collect();
+            (agents, cells)
+        };
+
+        for update_order in UpdateOrder::ALL {
+            let cfg = WorldConfig {
+                update_order,
+                ..cfg
+            };
+            let mut parallel = World::from_config(cfg);
+            let mut sequential = World::from_config(cfg);
+            sequential.set_parallel(false);
+            assert!(parallel.parallel());
+            for _ in 0..30 {
+                assert_eq!(parallel.update().is_ok(), sequential.update().is_ok());
+                assert_eq!(state(&parallel), state(&sequential), "{update_order:?}");
+                assert_eq!(parallel.events(), sequential.events());
+            }
+        }
+    }
 }
@@ -0,0 +1,86 @@
+//! Generates the C header `rcs.h` from the `extern "C"` API.
+//!
+//! The header is written to `OUT_DIR`; a test checks that the committed
+//! `include/rcs.h` matches it. Set `RCS_FFI_UPDATE_HEADER=1` to overwrite
+//! the committed copy as well.
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+/// Turns a rustdoc link target into the name C callers know it by.
+///
+/// ### Parameters
+/// - `target`: The text between the backticks, e.g. `RcsStatus::Ok`.
+///
+/// ### Returns
+/// `RCS_STATUS_OK` for a status variant, the target unchanged otherwise.
+fn c_name(target: &str) -> String {
+    let Some(variant) = target.strip_prefix("RcsStatus::") else {
+        return target.to_string();
+    };
+    let mut name = String::from("RCS_STATUS");
+    for c in variant.chars() {
+        if c.is_ascii_uppercase() {
+            name.push('_');
+        }
+        name.push(c.to_ascii_uppercase());
+    }
+    name
+}
+
+/// Replaces the rustdoc links copied from the doc comments with plain code
+/// spans, so that ``[`RcsStatus::Ok`]`` reads `` `RCS_STATUS_OK` ``.
+///
+/// ### Parameters
+/// - `header`: The header as generated by cbindgen.
+///
+/// ### Returns
+/// The header without link brackets.
+fn plain_links(header: &str) -> String {
+    let mut out = String::with_capacity(header.len());
+    let mut rest = header;
+    while let Some(start) = rest.find("[`") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match after.find("`]") {
+            Some(end) if !after[..end].contains('\n') => {
+                out.push('`');
+                out.push_str(&c_name(&after[..end]));
+                out.push('`');
+                rest = &after[end + 2..];
+            }
+            _ => {
+                out.push_str("[`");
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn main() {
+    let crate_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-env-changed=RCS_FFI_UPDATE_HEADER");
+
+    let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml"))
+        .expect("cbindgen.toml is readable");
+    let mut header = Vec::new();
+    cbindgen::Builder::new()
+        .with_crate(&crate_dir)
+        .with_config(config)
+        .generate()
+        .expect("the C API can be described in a header")
+        .write(&mut header);
+    let header = plain_links(&String::from_utf8(header).expect("the header is UTF-8"));
+
+    fs::write(out_dir.join("rcs.h"), &header).expect("OUT_DIR is writable");
+    if env::var_os("RCS_FFI_UPDATE_HEADER").is_some_and(|v| v != "0") {
+        fs::write(crate_dir.join("include").join("rcs.h"), &header)
+            .expect("include/rcs.h is writable");
+    }
+}
@@ -0,0 +1,207 @@
+/* C API of the 2-D resource-competition simulation. */
+
+#ifndef RCS_H
+#define RCS_H
+
+/* Generated by cbindgen from rcs_ffi; do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Version of the C API, bumped on every incompatible change.
+#define RCS_ABI_VERSION 1
+
+// Result of a C API call.
+typedef enum RcsStatus {
+  // The call succeeded.
+  RCS_STATUS_OK = 0,
+  // A required pointer argument was null.
+  RCS_STATUS_NULL_POINTER = 1,
+  // The world configuration is invalid.
+  RCS_STATUS_INVALID_CONFIG = 2,
+  // The simulation reported an error while updating.
+  RCS_STATUS_SIMULATION_FAILED = 3,
+  // A caller buffer is too small; nothing was written to it.
+  RCS_STATUS_BUFFER_TOO_SMALL = 4,
+  // The library panicked; the world must not be used any more.
+  RCS_STATUS_PANIC = 5,
+} RcsStatus;
+
+// Opaque handle to a running simulation.
+//
+// Created by `rcs_world_new` and released by `rcs_world_free`. A
+// handle may be moved between threads but must not be used by two threads
+// at once.
+typedef struct RcsWorld RcsWorld;
+
+// Settings for a randomized world, mirroring the base fields of
+// `WorldConfig`.
+//
+// Start from `rcs_config_default` and change what you need; settings
+// not listed here keep their defaults.
+typedef struct RcsConfig {
+  size_t width;
+  size_t height;
+  uint32_t min_resource;
+  uint32_t max_resource;
+  uint32_t min_regen_rate;
+  uint32_t max_regen_rate;
+  uint32_t min_terrain_cost;
+  uint32_t max_terrain_cost;
+  size_t min_agents;
+  size_t max_agents;
+  uint32_t min_consumption_rate;
+  uint32_t max_consumption_rate;
+  uint32_t agent_hp;
+  // Whether `seed` is used; without it every world is different.
+  bool has_seed;
+  uint64_t seed;
+} RcsConfig;
+
+// A living agent, as copied into caller buffers.
+typedef struct RcsAgent {
+  // Stable agent id.
+  uint64_t id;
+  // Column of the agent's cell.
+  size_t x;
+  // Row of the agent's cell.
+  size_t y;
+  uint32_t health_point;
+  uint32_t energy;
+} RcsAgent;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Returns the version of the C API the library implements.
+//
+// ### Returns
+// `RCS_ABI_VERSION`.
+uint32_t rcs_abi_version(void);
+
+// Returns the message of the last failed call on the calling thread.
+//
+// The string belongs to the library and stays valid until the next
+// failing call on the same thread.
+//
+// ### Returns
+// A NUL-terminated message, or null if no call has failed yet.
+const char *rcs_last_error(void);
+
+// Returns the default world configuration.
+//
+// ### Returns
+// The same settings as `WorldConfig::default`.
+struct RcsConfig rcs_config_default(void);
+
+// Builds a randomized world.
+//
+// ### Parameters
+// - `config`: The configuration, e.g. from `rcs_config_default`.
+// - `out`: Receives the new handle; left untouched on failure.
+//
+// ### Returns
+// - `RCS_STATUS_OK` on success.
+// - `RCS_STATUS_NULL_POINTER` or `RCS_STATUS_INVALID_CONFIG` otherwise.
+//
+// ### Safety
+// `config` must point to a valid `RcsConfig` and `out` to writable
+// memory for a pointer.
+enum RcsStatus rcs_world_new(const struct RcsConfig *config, struct RcsWorld **out);
+
+// Releases a world.
+//
+// ### Parameters
+// - `world`: The handle; null is ignored.
+//
+// ### Safety
+// `world` must be null or a handle from `rcs_world_new` that has not
+// been freed yet.
+void rcs_world_free(struct RcsWorld *world);
+
+// Advances a world by several ticks.
+//
+// ### Parameters
+// - `world`: The handle.
+// - `ticks`: Number of ticks to run.
+//
+// ### Returns
+// - `RCS_STATUS_OK` on success.
+// - `RCS_STATUS_SIMULATION_FAILED` from the first failing tick; the ticks
+//   before it are kept.
+//
+// ### Safety
+// `world` must be a live handle from `rcs_world_new`.
+enum RcsStatus rcs_world_step(struct RcsWorld *world, uint64_t ticks);
+
+// Reads the tick counter and grid size of a world.
+//
+// ### Parameters
+// - `world`: The handle.
+// - `tick`: Receives the number of completed ticks.
+// - `width`: Receives the grid width.
+// - `height`: Receives the grid height.
+//
+// ### Returns
+// `RCS_STATUS_OK`, or `RCS_STATUS_NULL_POINTER` if any argument is null.
+//
+// ### Safety
+// `world` must be a live handle and the other pointers writable.
+enum RcsStatus rcs_world_info(const struct RcsWorld *world,
+                              uint64_t *tick,
+                              size_t *width,
+                              size_t *height);
+
+// Copies the current resource of every cell into a caller buffer.
+//
+// Cells are written in row-major order, so entry `y * width + x` belongs
+// to column `x` of row `y`.
+//
+// ### Parameters
+// - `world`: The handle.
+// - `buffer`: Receives `width * height` values.
+// - `len`: Length of `buffer`.
+//
+// ### Returns
+// - `RCS_STATUS_OK` on success.
+// - `RCS_STATUS_BUFFER_TOO_SMALL` if `len < width * height`; nothing is
+//   written.
+// - `RCS_STATUS_NULL_POINTER` if `buffer` is null.
+//
+// ### Safety
+// `world` must be a live handle and `buffer` writable for `len` values.
+enum RcsStatus rcs_world_cell_resources(const struct RcsWorld *world, uint32_t *buffer, size_t len);
+
+// Copies the living agents into a caller buffer.
+//
+// Call it with a null `buffer` to learn how many agents there are.
+//
+// ### Parameters
+// - `world`: The handle.
+// - `buffer`: Receives one `RcsAgent` per living agent, in agent order;
+//   may be null.
+// - `capacity`: Length of `buffer`.
+// - `count`: Receives the number of living agents.
+//
+// ### Returns
+// - `RCS_STATUS_OK` when the agents were written or there are none.
+// - `RCS_STATUS_BUFFER_TOO_SMALL` if `buffer` is null or shorter than
+//   `count`; nothing is written to it.
+//
+// ### Safety
+// `world` must be a live handle, `count` writable and `buffer` null or
+// writable for `capacity` agents.
+enum RcsStatus rcs_world_agents(const struct RcsWorld *world,
+                                struct RcsAgent *buffer,
+                                size_t capacity,
+                                size_t *count);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* RCS_H */
@@ -0,0 +1,117 @@
+use crate::RcsStatus;
+use rcs_core::SimulationError;
+use std::cell::RefCell;
+use std::ffi::{CString, c_char};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+thread_local! {
+    /// Message of the last failed call on this thread.
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// A failed call: the status to return and the message to keep.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FfiError {
+    pub status: RcsStatus,
+    pub message: String,
+}
+
+impl FfiError {
+    /// Creates an error.
+    ///
+    /// ### Parameters
+    /// - `status`: The status returned to the caller.
+    /// - `message`: What went wrong.
+    ///
+    /// ### Returns
+    /// A new [`FfiError`].
+    pub fn new(status: RcsStatus, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+
+    /// Error for a null pointer argument.
+    ///
+    /// ### Parameters
+    /// - `name`: Name of the argument.
+    ///
+    /// ### Returns
+    /// An [`RcsStatus::NullPointer`] error.
+    pub fn null(name: &str) -> Self {
+        Self::new(RcsStatus::NullPointer, format!("`{name}` is null"))
+    }
+}
+
+impl From<SimulationError> for FfiError {
+    fn from(err: SimulationError) -> Self {
+        let status = match err {
+            SimulationError::InvalidConfig(_) => RcsStatus::InvalidConfig,
+            _ => RcsStatus::SimulationFailed,
+        };
+        Self::new(status, err.to_string())
+    }
+}
+
+/// Runs the body of a C API call.
+///
+/// Records the message of a failure for [`rcs_last_error`] and turns a
+/// panic into [`RcsStatus::Panic`], so that unwinding never crosses the
+/// boundary.
+///
+/// ### Parameters
+/// - `body`: The call's work.
+///
+/// ### Returns
+/// [`RcsStatus::Ok`] or the failure's status.
+pub(crate) fn guard(body: impl FnOnce() -> Result<(), FfiError>) -> RcsStatus {
+    let result = panic::catch_unwind(AssertUnwindSafe(body)).unwrap_or_else(|_| {
+        Err(FfiError::new(
+            RcsStatus::Panic,
+            "the simulation panicked; the world is no longer usable",
+        ))
+    });
+    match result {
+        Ok(()) => RcsStatus::Ok,
+        Err(err) => {
+            // Interior NUL bytes cannot appear in a C string.
+            let message = CString::new(err.message.replace('\0', " ")).unwrap_or_default();
+            LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+            err.status
+        }
+    }
+}
+
+/// Returns the message of the last failed call on the calling thread.
+///
+/// The string belongs to the library and stays valid until the next
+/// failing call on the same thread.
+///
+/// ### Returns
+/// A NUL-terminated message, or null if no call has failed yet.
+#[unsafe(no_mangle)]
+pub extern "C" fn rcs_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(ptr::null(), |m| m.as_ptr()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+
+    #[test]
+    fn failures_and_panics_leave_a_message() {
+        assert_eq!(guard(|| Ok(())), RcsStatus::Ok);
+
+        let status = guard(|| Err(SimulationError::InvalidConfig("width".into()).into()));
+        assert_eq!(status, RcsStatus::InvalidConfig);
+        let message = unsafe { CStr::from_ptr(rcs_last_error()) };
+        assert!(message.to_str().unwrap().contains("width"));
+
+        assert_eq!(guard(|| panic!("boom")), RcsStatus::Panic);
+        let message = unsafe { CStr::from_ptr(rcs_last_error()) };
+        assert!(message.to_str().unwrap().contains("panicked"));
+    }
+}
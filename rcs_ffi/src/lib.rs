@@ -0,0 +1,50 @@
+//! C ABI for embedding the 2-D resource-competition simulation.
+//!
+//! The crate builds a shared and a static library exposing a small
+//! `extern "C"` API around [`rcs_core::World`]. The C header
+//! `include/rcs.h` is generated from it by cbindgen; a test fails when the
+//! committed copy is stale, and `RCS_FFI_UPDATE_HEADER=1 cargo build -p
+//! rcs_ffi` rewrites it.
+//!
+//! - [`types`]: the `#[repr(C)]` values crossing the boundary
+//!   ([`RcsConfig`], [`RcsAgent`], [`RcsStatus`]).
+//! - [`world`]: the opaque [`RcsWorld`] handle and the functions that
+//!   create, step, query and free it.
+//! - [`error`]: the per-thread message behind [`rcs_last_error`].
+//!
+//! Every fallible function returns an [`RcsStatus`]; on failure a
+//! description of the error can be read with [`rcs_last_error`]. Panics
+//! never cross the boundary and are reported as [`RcsStatus::Panic`].
+
+pub mod error;
+pub mod types;
+pub mod world;
+
+pub use error::rcs_last_error;
+pub use types::{RcsAgent, RcsConfig, RcsStatus};
+pub use world::RcsWorld;
+
+/// Version of the C API, bumped on every incompatible change.
+pub const RCS_ABI_VERSION: u32 = 1;
+
+/// Returns the version of the C API the library implements.
+///
+/// ### Returns
+/// [`RCS_ABI_VERSION`].
+#[unsafe(no_mangle)]
+pub extern "C" fn rcs_abi_version() -> u32 {
+    RCS_ABI_VERSION
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn committed_header_is_current() {
+        let generated = include_str!(concat!(env!("OUT_DIR"), "/rcs.h"));
+        let committed = include_str!("../include/rcs.h");
+        assert!(
+            generated == committed,
+            "include/rcs.h is stale; rebuild with RCS_FFI_UPDATE_HEADER=1"
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use rcs_core::{Agent, WorldConfig};
+
+/// Result of a C API call.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RcsStatus {
+    /// The call succeeded.
+    Ok = 0,
+    /// A required pointer argument was null.
+    NullPointer = 1,
+    /// The world configuration is invalid.
+    InvalidConfig = 2,
+    /// The simulation reported an error while updating.
+    SimulationFailed = 3,
+    /// A caller buffer is too small; nothing was written to it.
+    BufferTooSmall = 4,
+    /// The library panicked; the world must not be used any more.
+    Panic = 5,
+}
+
+/// Settings for a randomized world, mirroring the base fields of
+/// [`WorldConfig`].
+///
+/// Start from [`rcs_config_default`] and change what you need; settings
+/// not listed here keep their defaults.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RcsConfig {
+    pub width: usize,
+    pub height: usize,
+    pub min_resource: u32,
+    pub max_resource: u32,
+    pub min_regen_rate: u32,
+    pub max_regen_rate: u32,
+    pub min_terrain_cost: u32,
+    pub max_terrain_cost: u32,
+    pub min_agents: usize,
+    pub max_agents: usize,
+    pub min_consumption_rate: u32,
+    pub max_consumption_rate: u32,
+    pub agent_hp: u32,
+    /// Whether `seed` is used; without it every world is different.
+    pub has_seed: bool,
+    pub seed: u64,
+}
+
+impl From<&WorldConfig> for RcsConfig {
+    fn from(cfg: &WorldConfig) -> Self {
+        Self {
+            width: cfg.width,
+            height: cfg.height,
+            min_resource: cfg.min_resource,
+            max_resource: cfg.max_resource,
+            min_regen_rate: cfg.min_regen_rate,
+            max_regen_rate: cfg.max_regen_rate,
+            min_terrain_cost: cfg.min_terrain_cost,
+            max_terrain_cost: cfg.max_terrain_cost,
+            min_agents: cfg.min_agents,
+            max_agents: cfg.max_agents,
+            min_consumption_rate: cfg.min_consumption_rate,
+            max_consumption_rate: cfg.max_consumption_rate,
+            agent_hp: cfg.agent_hp,
+            has_seed: cfg.seed.is_some(),
+            seed: cfg.seed.unwrap_or(0),
+        }
+    }
+}
+
+impl From<&RcsConfig> for WorldConfig {
+    fn from(cfg: &RcsConfig) -> Self {
+        Self {
+            width: cfg.width,
+            height: cfg.height,
+            min_resource: cfg.min_resource,
+            max_resource: cfg.max_resource,
+            min_regen_rate: cfg.min_regen_rate,
+            max_regen_rate: cfg.max_regen_rate,
+            min_terrain_cost: cfg.min_terrain_cost,
+            max_terrain_cost: cfg.max_terrain_cost,
+            min_agents: cfg.min_agents,
+            max_agents: cfg.max_agents,
+            min_consumption_rate: cfg.min_consumption_rate,
+            max_consumption_rate: cfg.max_consumption_rate,
+            agent_hp: cfg.agent_hp,
+            seed: cfg.has_seed.then_some(cfg.seed),
+            ..WorldConfig::default()
+        }
+    }
+}
+
+/// Returns the default world configuration.
+///
+/// ### Returns
+/// The same settings as [`WorldConfig::default`].
+#[unsafe(no_mangle)]
+pub extern "C" fn rcs_config_default() -> RcsConfig {
+    RcsConfig::from(&WorldConfig::default())
+}
+
+/// A living agent, as copied into caller buffers.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RcsAgent {
+    /// Stable agent id.
+    pub id: u64,
+    /// Column of the agent's cell.
+    pub x: usize,
+    /// Row of the agent's cell.
+    pub y: usize,
+    pub health_point: u32,
+    pub energy: u32,
+}
+
+impl RcsAgent {
+    /// Describes an agent.
+    ///
+    /// ### Parameters
+    /// - `agent`: The agent.
+    /// - `width`: Grid width, to turn the cell id into coordinates.
+    ///
+    /// ### Returns
+    /// The [`RcsAgent`] for `agent`.
+    pub fn new(agent: &Agent, width: usize) -> Self {
+        Self {
+            id: agent.id() as u64,
+            x: agent.cid() % width,
+            y: agent.cid() / width,
+            health_point: agent.health_point(),
+            energy: agent.energy(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn configs_round_trip_through_the_c_struct() {
+        let mut c = rcs_config_default();
+        c.width = 7;
+        c.has_seed = true;
+        c.seed = 42;
+        let cfg = WorldConfig::from(&c);
+        assert_eq!((cfg.width, cfg.seed), (7, Some(42)));
+        assert_eq!(RcsConfig::from(&cfg), c);
+
+        c.has_seed = false;
+        assert_eq!(WorldConfig::from(&c).seed, None);
+    }
+}
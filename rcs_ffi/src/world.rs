@@ -0,0 +1,308 @@
+use crate::error::{FfiError, guard};
+use crate::{RcsAgent, RcsConfig, RcsStatus};
+use rcs_core::{Updatable, World, WorldConfig};
+use std::slice;
+
+/// Opaque handle to a running simulation.
+///
+/// Created by [`rcs_world_new`] and released by [`rcs_world_free`]. A
+/// handle may be moved between threads but must not be used by two threads
+/// at once.
+pub struct RcsWorld {
+    world: World,
+}
+
+/// Reads a pointer argument.
+///
+/// ### Parameters
+/// - `ptr`: The argument.
+/// - `name`: Its name, for the error message.
+///
+/// ### Returns
+/// - `Ok(reference)` if `ptr` is not null.
+/// - `Err(FfiError)` with [`RcsStatus::NullPointer`] otherwise.
+///
+/// ### Safety
+/// A non-null `ptr` must point to a valid `T` for the lifetime `'a`.
+unsafe fn arg<'a, T>(ptr: *const T, name: &str) -> Result<&'a T, FfiError> {
+    unsafe { ptr.as_ref() }.ok_or_else(|| FfiError::null(name))
+}
+
+/// Reads a mutable pointer argument.
+///
+/// ### Parameters
+/// - `ptr`: The argument.
+/// - `name`: Its name, for the error message.
+///
+/// ### Returns
+/// - `Ok(reference)` if `ptr` is not null.
+/// - `Err(FfiError)` with [`RcsStatus::NullPointer`] otherwise.
+///
+/// ### Safety
+/// A non-null `ptr` must point to a valid `T` that nothing else accesses
+/// for the lifetime `'a`.
+unsafe fn arg_mut<'a, T>(ptr: *mut T, name: &str) -> Result<&'a mut T, FfiError> {
+    unsafe { ptr.as_mut() }.ok_or_else(|| FfiError::null(name))
+}
+
+/// Builds a randomized world.
+///
+/// ### Parameters
+/// - `config`: The configuration, e.g. from `rcs_config_default`.
+/// - `out`: Receives the new handle; left untouched on failure.
+///
+/// ### Returns
+/// - [`RcsStatus::Ok`] on success.
+/// - [`RcsStatus::NullPointer`] or [`RcsStatus::InvalidConfig`] otherwise.
+///
+/// ### Safety
+/// `config` must point to a valid [`RcsConfig`] and `out` to writable
+/// memory for a pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rcs_world_new(
+    config: *const RcsConfig,
+    out: *mut *mut RcsWorld,
+) -> RcsStatus {
+    guard(|| {
+        let config = WorldConfig::from(unsafe { arg(config, "config") }?);
+        let out = unsafe { arg_mut(out, "out") }?;
+        let world = World::try_from_config(config)?;
+        *out = Box::into_raw(Box::new(RcsWorld { world }));
+        Ok(())
+    })
+}
+
+/// Releases a world.
+///
+/// ### Parameters
+/// - `world`: The handle; null is ignored.
+///
+/// ### Safety
+/// `world` must be null or a handle from [`rcs_world_new`] that has not
+/// been freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rcs_world_free(world: *mut RcsWorld) {
+    if !world.is_null() {
+        drop(unsafe { Box::from_raw(world) });
+    }
+}
+
+/// Advances a world by several ticks.
+///
+/// ### Parameters
+/// - `world`: The handle.
+/// - `ticks`: Number of ticks to run.
+///
+/// ### Returns
+/// - [`RcsStatus::Ok`] on success.
+/// - [`RcsStatus::SimulationFailed`] from the first failing tick; the ticks
+///   before it are kept.
+///
+/// ### Safety
+/// `world` must be a live handle from [`rcs_world_new`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rcs_world_step(world: *mut RcsWorld, ticks: u64) -> RcsStatus {
+    guard(|| {
+        let world = &mut unsafe { arg_mut(world, "world") }?.world;
+        for _ in 0..ticks {
+            world.update()?;
+        }
+        Ok(())
+    })
+}
+
+/// Reads the tick counter and grid size of a world.
+///
+/// ### Parameters
+/// - `world`: The handle.
+/// - `tick`: Receives the number of completed ticks.
+/// - `width`: Receives the grid width.
+/// - `height`: Receives the grid height.
+///
+/// ### Returns
+/// [`RcsStatus::Ok`], or [`RcsStatus::NullPointer`] if any argument is null.
+///
+/// ### Safety
+/// `world` must be a live handle and the other pointers writable.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rcs_world_info(
+    world: *const RcsWorld,
+    tick: *mut u64,
+    width: *mut usize,
+    height: *mut usize,
+) -> RcsStatus {
+    guard(|| {
+        let world = &unsafe { arg(world, "world") }?.world;
+        let (w, h) = world.size();
+        *unsafe { arg_mut(tick, "tick") }? = world.tick();
+        *unsafe { arg_mut(width, "width") }? = w;
+        *unsafe { arg_mut(height, "height") }? = h;
+        Ok(())
+    })
+}
+
+/// Copies the current resource of every cell into a caller buffer.
+///
+/// Cells are written in row-major order, so entry `y * width + x` belongs
+/// to column `x` of row `y`.
+///
+/// ### Parameters
+/// - `world`: The handle.
+/// - `buffer`: Receives `width * height` values.
+/// - `len`: Length of `buffer`.
+///
+/// ### Returns
+/// - [`RcsStatus::Ok`] on success.
+/// - [`RcsStatus::BufferTooSmall`] if `len < width * height`; nothing is
+///   written.
+/// - [`RcsStatus::NullPointer`] if `buffer` is null.
+///
+/// ### Safety
+/// `world` must be a live handle and `buffer` writable for `len` values.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rcs_world_cell_resources(
+    world: *const RcsWorld,
+    buffer: *mut u32,
+    len: usize,
+) -> RcsStatus {
+    guard(|| {
+        let cells = unsafe { arg(world, "world") }?.world.cells();
+        if len < cells.len() {
+            return Err(FfiError::new(
+                RcsStatus::BufferTooSmall,
+                format!("{} cells do not fit in {len} values", cells.len()),
+            ));
+        }
+        if buffer.is_null() {
+            return Err(FfiError::null("buffer"));
+        }
+        let buffer = unsafe { slice::from_raw_parts_mut(buffer, len) };
+        for (slot, cell) in buffer.iter_mut().zip(cells) {
+            *slot = cell.cur_resource();
+        }
+        Ok(())
+    })
+}
+
+/// Copies the living agents into a caller buffer.
+///
+/// Call it with a null `buffer` to learn how many agents there are.
+///
+/// ### Parameters
+/// - `world`: The handle.
+/// - `buffer`: Receives one [`RcsAgent`] per living agent, in agent order;
+///   may be null.
+/// - `capacity`: Length of `buffer`.
+/// - `count`: Receives the number of living agents.
+///
+/// ### Returns
+/// - [`RcsStatus::Ok`] when the agents were written or there are none.
+/// - [`RcsStatus::BufferTooSmall`] if `buffer` is null or shorter than
+///   `count`; nothing is written to it.
+///
+/// ### Safety
+/// `world` must be a live handle, `count` writable and `buffer` null or
+/// writable for `capacity` agents.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rcs_world_agents(
+    world: *const RcsWorld,
+    buffer: *mut RcsAgent,
+    capacity: usize,
+    count: *mut usize,
+) -> RcsStatus {
+    guard(|| {
+        let world = &unsafe { arg(world, "world") }?.world;
+        let count = unsafe { arg_mut(count, "count") }?;
+        let width = world.size().0;
+        let living = world.agents().iter().filter(|a| a.is_alive());
+        *count = living.clone().count();
+        if *count == 0 {
+            return Ok(());
+        }
+        if buffer.is_null() || capacity < *count {
+            return Err(FfiError::new(
+                RcsStatus::BufferTooSmall,
+                format!("{count} agents do not fit in {capacity} slots"),
+            ));
+        }
+        let buffer = unsafe { slice::from_raw_parts_mut(buffer, capacity) };
+        for (slot, agent) in buffer.iter_mut().zip(living) {
+            *slot = RcsAgent::new(agent, width);
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::rcs_config_default;
+    use std::ptr;
+
+    fn new_world(config: &RcsConfig) -> *mut RcsWorld {
+        let mut world = ptr::null_mut();
+        assert_eq!(unsafe { rcs_world_new(config, &mut world) }, RcsStatus::Ok);
+        world
+    }
+
+    #[test]
+    fn worlds_are_created_stepped_queried_and_freed() {
+        let mut config = rcs_config_default();
+        config.width = 6;
+        config.height = 4;
+        config.has_seed = true;
+        config.seed = 11;
+        let world = new_world(&config);
+
+        assert_eq!(unsafe { rcs_world_step(world, 5) }, RcsStatus::Ok);
+        let (mut tick, mut width, mut height) = (0, 0, 0);
+        let status = unsafe { rcs_world_info(world, &mut tick, &mut width, &mut height) };
+        assert_eq!(status, RcsStatus::Ok);
+        assert_eq!((tick, width, height), (5, 6, 4));
+
+        let mut grid = vec![u32::MAX; 24];
+        let status = unsafe { rcs_world_cell_resources(world, grid.as_mut_ptr(), grid.len()) };
+        assert_eq!(status, RcsStatus::Ok);
+        let cells = unsafe { &(*world).world }.cells();
+        assert!(grid.iter().zip(cells).all(|(&r, c)| r == c.cur_resource()));
+
+        let mut count = 0;
+        let status = unsafe { rcs_world_agents(world, ptr::null_mut(), 0, &mut count) };
+        assert_eq!(status, RcsStatus::BufferTooSmall);
+        let mut agents = vec![RcsAgent::default(); count];
+        let status = unsafe { rcs_world_agents(world, agents.as_mut_ptr(), count, &mut count) };
+        assert_eq!(status, RcsStatus::Ok);
+        assert!(
+            agents
+                .iter()
+                .all(|a| a.x < 6 && a.y < 4 && a.health_point > 0)
+        );
+
+        unsafe { rcs_world_free(world) };
+    }
+
+    #[test]
+    fn bad_arguments_are_reported() {
+        let mut config = rcs_config_default();
+        config.width = 0;
+        let mut world = ptr::null_mut();
+        let status = unsafe { rcs_world_new(&config, &mut world) };
+        assert_eq!(status, RcsStatus::InvalidConfig);
+        assert!(world.is_null());
+        assert_eq!(
+            unsafe { rcs_world_new(ptr::null(), &mut world) },
+            RcsStatus::NullPointer
+        );
+        assert_eq!(
+            unsafe { rcs_world_step(ptr::null_mut(), 1) },
+            RcsStatus::NullPointer
+        );
+
+        let world = new_world(&rcs_config_default());
+        let mut grid = [0; 3];
+        let status = unsafe { rcs_world_cell_resources(world, grid.as_mut_ptr(), grid.len()) };
+        assert_eq!(status, RcsStatus::BufferTooSmall);
+        unsafe { rcs_world_free(world) };
+        unsafe { rcs_world_free(ptr::null_mut()) };
+    }
+}
@@ -0,0 +1,109 @@
+use numpy::ndarray::{Array1, Array2};
+use rcs_core::World;
+
+/// Current resource of every cell, laid out like the grid.
+///
+/// ### Parameters
+/// - `world`: The world to read.
+///
+/// ### Returns
+/// A `(height, width)` array; entry `[y, x]` belongs to cell
+/// `y * width + x`.
+pub fn cell_resources(world: &World) -> Array2<u32> {
+    let (width, height) = world.size();
+    Array2::from_shape_fn((height, width), |(y, x)| {
+        world.cell(y * width + x).cur_resource()
+    })
+}
+
+/// Ids of the living agents.
+///
+/// Rows of [`agent_positions`] and [`agent_hp`] follow the same order.
+///
+/// ### Parameters
+/// - `world`: The world to read.
+///
+/// ### Returns
+/// One stable id (see [`World::agent`]) per living agent.
+pub fn agent_ids(world: &World) -> Array1<u64> {
+    living(world).map(|(id, _, _)| id).collect()
+}
+
+/// Grid coordinates of the living agents.
+///
+/// ### Parameters
+/// - `world`: The world to read.
+///
+/// ### Returns
+/// An `(n, 2)` array of `(x, y)` rows.
+pub fn agent_positions(world: &World) -> Array2<u64> {
+    let positions: Vec<u64> = living(world).flat_map(|(_, (x, y), _)| [x, y]).collect();
+    Array2::from_shape_vec((positions.len() / 2, 2), positions).expect("two coordinates per agent")
+}
+
+/// Health points of the living agents.
+///
+/// ### Parameters
+/// - `world`: The world to read.
+///
+/// ### Returns
+/// One entry per living agent.
+pub fn agent_hp(world: &World) -> Array1<u32> {
+    living(world).map(|(_, _, hp)| hp).collect()
+}
+
+/// Iterates over the living agents in agent order.
+///
+/// ### Parameters
+/// - `world`: The world to read.
+///
+/// ### Returns
+/// `(id, (x, y), hp)` for each living agent.
+fn living(world: &World) -> impl Iterator<Item = (u64, (u64, u64), u32)> + '_ {
+    let width = world.size().0;
+    world
+        .agents()
+        .iter()
+        .filter(|agent| agent.is_alive())
+        .map(move |agent| {
+            let (x, y) = (agent.cid() % width, agent.cid() / width);
+            (
+                agent.id() as u64,
+                (x as u64, y as u64),
+                agent.health_point(),
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use numpy::ndarray::array;
+    use rcs_core::{Agent, Cell};
+
+    #[test]
+    fn arrays_follow_the_grid_and_skip_dead_agents() {
+        // 3×2 grid with resource equal to the cell id.
+        let cells = (0..6)
+            .map(|id| Cell::new(id, id as u32, 10, 0, 1))
+            .collect();
+        let agents = vec![
+            Agent::new(0, 5, 1, 0, 4, true),
+            Agent::new(1, 0, 1, 0, 0, false),
+            Agent::new(2, 1, 1, 0, 2, true),
+        ];
+        let world = World::new((3, 2), cells, agents);
+
+        assert_eq!(cell_resources(&world), array![[0, 1, 2], [3, 4, 5]]);
+        assert_eq!(agent_ids(&world), array![0, 2]);
+        assert_eq!(agent_positions(&world), array![[2, 1], [1, 0]]);
+        assert_eq!(agent_hp(&world), array![4, 2]);
+    }
+
+    #[test]
+    fn empty_worlds_give_empty_agent_arrays() {
+        let world = World::new((1, 1), vec![Cell::new(0, 0, 1, 0, 0)], Vec::new());
+        assert_eq!(agent_positions(&world).dim(), (0, 2));
+        assert!(agent_hp(&world).is_empty());
+    }
+}
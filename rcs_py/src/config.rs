@@ -0,0 +1,484 @@
+use crate::{layers, to_py_err};
+use pyo3::exceptions::{PyAttributeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use rcs_core::{ConflictRule, PolicyKind, UpdateOrder, WorldConfig};
+
+/// `rcs.WorldConfig`: settings for a randomized world.
+///
+/// Every setting is a property with the name of the [`WorldConfig`] field,
+/// and any of them can be passed as a keyword argument:
+/// `WorldConfig(width=40, height=30, seed=7, policy="vision")`. The
+/// policy, update order and conflict rule are set by their labels, e.g.
+/// `"greedy"`, `"sequential"` or `"first-come"`.
+///
+/// The nested settings (`perception`, `pheromone`, `evolution`, `aging`,
+/// `disease`, `territory`, `combat` and `groups`) read as dicts keyed by
+/// field name. Reading returns a copy; assigning a dict changes only the
+/// fields it names, e.g. `config.disease = {"enabled": True}` or
+/// `WorldConfig(combat={"enabled": True, "fight_cost": 2})`.
+#[pyclass(name = "WorldConfig", module = "rcs")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PyWorldConfig {
+    pub inner: WorldConfig,
+}
+
+/// Looks up an option by its label.
+///
+/// ### Parameters
+/// - `all`: Every option.
+/// - `label`: Returns an option's label.
+/// - `name`: The label to find.
+/// - `what`: What the options are, for the error message.
+///
+/// ### Returns
+/// - `Ok(option)` with the matching label.
+/// - `Err(ValueError)` listing the valid labels otherwise.
+fn parse_label<T: Copy>(
+    all: &[T],
+    label: fn(&T) -> &'static str,
+    name: &str,
+    what: &str,
+) -> PyResult<T> {
+    all.iter()
+        .find(|option| label(option) == name)
+        .copied()
+        .ok_or_else(|| {
+            let valid: Vec<_> = all.iter().map(label).collect();
+            PyValueError::new_err(format!(
+                "unknown {what} {name:?}, expected one of {}",
+                valid.join(", ")
+            ))
+        })
+}
+
+#[pymethods]
+impl PyWorldConfig {
+    /// Creates the default configuration with some settings overridden.
+    ///
+    /// ### Parameters
+    /// - `overrides`: Keyword arguments named after the properties.
+    ///
+    /// ### Returns
+    /// - `Ok(config)` on success.
+    /// - `Err(AttributeError)` for an unknown setting, `Err(TypeError)` or
+    ///   `Err(ValueError)` for a bad value.
+    #[new]
+    #[pyo3(signature = (**overrides))]
+    fn new(overrides: Option<&Bound<'_, PyDict>>) -> PyResult<Self> {
+        let mut config = Self::default();
+        for (key, value) in overrides.into_iter().flatten() {
+            config.set(&key.extract::<String>()?, &value)?;
+        }
+        Ok(config)
+    }
+
+    /// Checks that the configuration describes a valid world.
+    ///
+    /// ### Returns
+    /// - `Ok(())` if it does.
+    /// - `Err(ValueError)` naming the first broken rule.
+    fn validate(&self) -> PyResult<()> {
+        self.inner.validate().map_err(to_py_err)
+    }
+
+    fn __repr__(&self) -> String {
+        let c = &self.inner;
+        format!(
+            "WorldConfig(width={}, height={}, agents={}..={}, policy={:?}, seed={:?})",
+            c.width,
+            c.height,
+            c.min_agents,
+            c.max_agents,
+            c.policy.label(),
+            c.seed
+        )
+    }
+
+    #[getter]
+    fn width(&self) -> usize {
+        self.inner.width
+    }
+
+    #[setter]
+    fn set_width(&mut self, width: usize) {
+        self.inner.width = width;
+    }
+
+    #[getter]
+    fn height(&self) -> usize {
+        self.inner.height
+    }
+
+    #[setter]
+    fn set_height(&mut self, height: usize) {
+        self.inner.height = height;
+    }
+
+    #[getter]
+    fn min_resource(&self) -> u32 {
+        self.inner.min_resource
+    }
+
+    #[setter]
+    fn set_min_resource(&mut self, value: u32) {
+        self.inner.min_resource = value;
+    }
+
+    #[getter]
+    fn max_resource(&self) -> u32 {
+        self.inner.max_resource
+    }
+
+    #[setter]
+    fn set_max_resource(&mut self, value: u32) {
+        self.inner.max_resource = value;
+    }
+
+    #[getter]
+    fn min_regen_rate(&self) -> u32 {
+        self.inner.min_regen_rate
+    }
+
+    #[setter]
+    fn set_min_regen_rate(&mut self, value: u32) {
+        self.inner.min_regen_rate = value;
+    }
+
+    #[getter]
+    fn max_regen_rate(&self) -> u32 {
+        self.inner.max_regen_rate
+    }
+
+    #[setter]
+    fn set_max_regen_rate(&mut self, value: u32) {
+        self.inner.max_regen_rate = value;
+    }
+
+    #[getter]
+    fn min_terrain_cost(&self) -> u32 {
+        self.inner.min_terrain_cost
+    }
+
+    #[setter]
+    fn set_min_terrain_cost(&mut self, value: u32) {
+        self.inner.min_terrain_cost = value;
+    }
+
+    #[getter]
+    fn max_terrain_cost(&self) -> u32 {
+        self.inner.max_terrain_cost
+    }
+
+    #[setter]
+    fn set_max_terrain_cost(&mut self, value: u32) {
+        self.inner.max_terrain_cost = value;
+    }
+
+    #[getter]
+    fn min_agents(&self) -> usize {
+        self.inner.min_agents
+    }
+
+    #[setter]
+    fn set_min_agents(&mut self, value: usize) {
+        self.inner.min_agents = value;
+    }
+
+    #[getter]
+    fn max_agents(&self) -> usize {
+        self.inner.max_agents
+    }
+
+    #[setter]
+    fn set_max_agents(&mut self, value: usize) {
+        self.inner.max_agents = value;
+    }
+
+    #[getter]
+    fn min_consumption_rate(&self) -> u32 {
+        self.inner.min_consumption_rate
+    }
+
+    #[setter]
+    fn set_min_consumption_rate(&mut self, value: u32) {
+        self.inner.min_consumption_rate = value;
+    }
+
+    #[getter]
+    fn max_consumption_rate(&self) -> u32 {
+        self.inner.max_consumption_rate
+    }
+
+    #[setter]
+    fn set_max_consumption_rate(&mut self, value: u32) {
+        self.inner.max_consumption_rate = value;
+    }
+
+    #[getter]
+    fn agent_hp(&self) -> u32 {
+        self.inner.agent_hp
+    }
+
+    #[setter]
+    fn set_agent_hp(&mut self, value: u32) {
+        self.inner.agent_hp = value;
+    }
+
+    #[getter]
+    fn seed(&self) -> Option<u64> {
+        self.inner.seed
+    }
+
+    #[setter]
+    fn set_seed(&mut self, seed: Option<u64>) {
+        self.inner.seed = seed;
+    }
+
+    #[getter]
+    fn cell_capacity(&self) -> Option<usize> {
+        self.inner.cell_capacity
+    }
+
+    #[setter]
+    fn set_cell_capacity(&mut self, capacity: Option<usize>) {
+        self.inner.cell_capacity = capacity;
+    }
+
+    #[getter]
+    fn compaction_interval(&self) -> Option<u64> {
+        self.inner.compaction_interval
+    }
+
+    #[setter]
+    fn set_compaction_interval(&mut self, interval: Option<u64>) {
+        self.inner.compaction_interval = interval;
+    }
+
+    #[getter]
+    fn policy(&self) -> &'static str {
+        self.inner.policy.label()
+    }
+
+    #[setter]
+    fn set_policy(&mut self, name: &str) -> PyResult<()> {
+        self.inner.policy = parse_label(&PolicyKind::ALL, PolicyKind::label, name, "policy")?;
+        Ok(())
+    }
+
+    #[getter]
+    fn update_order(&self) -> &'static str {
+        self.inner.update_order.label()
+    }
+
+    #[setter]
+    fn set_update_order(&mut self, name: &str) -> PyResult<()> {
+        self.inner.update_order =
+            parse_label(&UpdateOrder::ALL, UpdateOrder::label, name, "update order")?;
+        Ok(())
+    }
+
+    #[getter]
+    fn conflict_rule(&self) -> &'static str {
+        self.inner.conflict_rule.label()
+    }
+
+    #[setter]
+    fn set_conflict_rule(&mut self, name: &str) -> PyResult<()> {
+        self.inner.conflict_rule = parse_label(
+            &ConflictRule::ALL,
+            ConflictRule::label,
+            name,
+            "conflict rule",
+        )?;
+        Ok(())
+    }
+
+    #[getter]
+    fn agent_memory(&self) -> usize {
+        self.inner.agent_memory
+    }
+
+    #[setter]
+    fn set_agent_memory(&mut self, cells: usize) {
+        self.inner.agent_memory = cells;
+    }
+
+    #[getter]
+    fn perception<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        layers::perception(py, &self.inner.perception)
+    }
+
+    #[setter]
+    fn set_perception(&mut self, values: &Bound<'_, PyDict>) -> PyResult<()> {
+        self.inner.perception = layers::update(
+            self.inner.perception,
+            values,
+            "perception",
+            layers::set_perception,
+        )?;
+        Ok(())
+    }
+
+    #[getter]
+    fn pheromone<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        layers::pheromone(py, &self.inner.pheromone)
+    }
+
+    #[setter]
+    fn set_pheromone(&mut self, values: &Bound<'_, PyDict>) -> PyResult<()> {
+        self.inner.pheromone = layers::update(
+            self.inner.pheromone,
+            values,
+            "pheromone",
+            layers::set_pheromone,
+        )?;
+        Ok(())
+    }
+
+    #[getter]
+    fn evolution<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        layers::evolution(py, &self.inner.evolution)
+    }
+
+    #[setter]
+    fn set_evolution(&mut self, values: &Bound<'_, PyDict>) -> PyResult<()> {
+        self.inner.evolution = layers::update(
+            self.inner.evolution,
+            values,
+            "evolution",
+            layers::set_evolution,
+        )?;
+        Ok(())
+    }
+
+    #[getter]
+    fn aging<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        layers::aging(py, &self.inner.aging)
+    }
+
+    #[setter]
+    fn set_aging(&mut self, values: &Bound<'_, PyDict>) -> PyResult<()> {
+        self.inner.aging = layers::update(self.inner.aging, values, "aging", layers::set_aging)?;
+        Ok(())
+    }
+
+    #[getter]
+    fn disease<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        layers::disease(py, &self.inner.disease)
+    }
+
+    #[setter]
+    fn set_disease(&mut self, values: &Bound<'_, PyDict>) -> PyResult<()> {
+        self.inner.disease =
+            layers::update(self.inner.disease, values, "disease", layers::set_disease)?;
+        Ok(())
+    }
+
+    #[getter]
+    fn territory<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        layers::territory(py, &self.inner.territory)
+    }
+
+    #[setter]
+    fn set_territory(&mut self, values: &Bound<'_, PyDict>) -> PyResult<()> {
+        self.inner.territory = layers::update(
+            self.inner.territory,
+            values,
+            "territory",
+            layers::set_territory,
+        )?;
+        Ok(())
+    }
+
+    #[getter]
+    fn combat<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        layers::combat(py, &self.inner.combat)
+    }
+
+    #[setter]
+    fn set_combat(&mut self, values: &Bound<'_, PyDict>) -> PyResult<()> {
+        self.inner.combat =
+            layers::update(self.inner.combat, values, "combat", layers::set_combat)?;
+        Ok(())
+    }
+
+    #[getter]
+    fn groups<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        layers::groups(py, &self.inner.groups)
+    }
+
+    #[setter]
+    fn set_groups(&mut self, values: &Bound<'_, PyDict>) -> PyResult<()> {
+        self.inner.groups =
+            layers::update(self.inner.groups, values, "groups", layers::set_groups)?;
+        Ok(())
+    }
+}
+
+impl PyWorldConfig {
+    /// Sets a property by name, for the keyword arguments of `new`.
+    ///
+    /// ### Parameters
+    /// - `key`: Property name.
+    /// - `value`: The Python value.
+    ///
+    /// ### Returns
+    /// - `Ok(())` on success.
+    /// - `Err(AttributeError)` for an unknown name, or the conversion error.
+    fn set(&mut self, key: &str, value: &Bound<'_, PyAny>) -> PyResult<()> {
+        match key {
+            "width" => self.set_width(value.extract()?),
+            "height" => self.set_height(value.extract()?),
+            "min_resource" => self.set_min_resource(value.extract()?),
+            "max_resource" => self.set_max_resource(value.extract()?),
+            "min_regen_rate" => self.set_min_regen_rate(value.extract()?),
+            "max_regen_rate" => self.set_max_regen_rate(value.extract()?),
+            "min_terrain_cost" => self.set_min_terrain_cost(value.extract()?),
+            "max_terrain_cost" => self.set_max_terrain_cost(value.extract()?),
+            "min_agents" => self.set_min_agents(value.extract()?),
+            "max_agents" => self.set_max_agents(value.extract()?),
+            "min_consumption_rate" => self.set_min_consumption_rate(value.extract()?),
+            "max_consumption_rate" => self.set_max_consumption_rate(value.extract()?),
+            "agent_hp" => self.set_agent_hp(value.extract()?),
+            "seed" => self.set_seed(value.extract()?),
+            "cell_capacity" => self.set_cell_capacity(value.extract()?),
+            "compaction_interval" => self.set_compaction_interval(value.extract()?),
+            "policy" => self.set_policy(&value.extract::<String>()?)?,
+            "update_order" => self.set_update_order(&value.extract::<String>()?)?,
+            "conflict_rule" => self.set_conflict_rule(&value.extract::<String>()?)?,
+            "agent_memory" => self.set_agent_memory(value.extract()?),
+            "perception" => self.set_perception(value.cast()?)?,
+            "pheromone" => self.set_pheromone(value.cast()?)?,
+            "evolution" => self.set_evolution(value.cast()?)?,
+            "aging" => self.set_aging(value.cast()?)?,
+            "disease" => self.set_disease(value.cast()?)?,
+            "territory" => self.set_territory(value.cast()?)?,
+            "combat" => self.set_combat(value.cast()?)?,
+            "groups" => self.set_groups(value.cast()?)?,
+            _ => {
+                return Err(PyAttributeError::new_err(format!(
+                    "WorldConfig has no setting {key:?}"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels_round_trip() {
+        let mut config = PyWorldConfig::default();
+        config.set_policy("vision").unwrap();
+        config.set_update_order("shuffled").unwrap();
+        config.set_conflict_rule("stronger wins").unwrap();
+        assert_eq!(config.inner.policy, PolicyKind::Vision);
+        assert_eq!(config.policy(), "vision");
+        assert_eq!(config.update_order(), "shuffled");
+        assert_eq!(config.conflict_rule(), "stronger wins");
+    }
+}
@@ -0,0 +1,342 @@
+//! Dict conversions for the nested settings of [`rcs_core::WorldConfig`].
+//!
+//! Each optional layer is exposed on `rcs.WorldConfig` as a property that
+//! reads as a dict with one entry per field, e.g.
+//! `{"enabled": False, "deposit": 1.0, ...}` for `pheromone`. Assigning a
+//! dict changes only the fields it names.
+
+use pyo3::exceptions::PyAttributeError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use rcs_core::{
+    AgingConfig, CombatConfig, DiseaseConfig, EvolutionConfig, GroupConfig, PerceptionConfig,
+    PheromoneConfig, TerritoryConfig,
+};
+
+/// Sets one field of a settings struct from a Python value.
+///
+/// Returns `Ok(false)` if the struct has no field named `key`.
+type SetField<T> = fn(&mut T, &str, &Bound<'_, PyAny>) -> PyResult<bool>;
+
+/// Applies a dict of changes to a copy of some settings.
+///
+/// ### Parameters
+/// - `settings`: The current settings.
+/// - `values`: Field names mapped to new values.
+/// - `layer`: Name of the property, for the error message.
+/// - `set`: Sets a single field.
+///
+/// ### Returns
+/// - `Ok(settings)` with the named fields replaced.
+/// - `Err(AttributeError)` for an unknown field, or the conversion error;
+///   nothing is changed then.
+pub(crate) fn update<T: Copy>(
+    settings: T,
+    values: &Bound<'_, PyDict>,
+    layer: &str,
+    set: SetField<T>,
+) -> PyResult<T> {
+    let mut updated = settings;
+    for (key, value) in values {
+        let key = key.extract::<String>()?;
+        if !set(&mut updated, &key, &value)? {
+            return Err(PyAttributeError::new_err(format!(
+                "{layer} has no setting {key:?}"
+            )));
+        }
+    }
+    Ok(updated)
+}
+
+/// Describes the perception settings.
+///
+/// ### Parameters
+/// - `py`: The Python interpreter.
+/// - `c`: The settings.
+///
+/// ### Returns
+/// A dict with `radius`, `see_agents` and `cost_per_radius`.
+pub(crate) fn perception<'py>(
+    py: Python<'py>,
+    c: &PerceptionConfig,
+) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("radius", c.radius)?;
+    dict.set_item("see_agents", c.see_agents)?;
+    dict.set_item("cost_per_radius", c.cost_per_radius)?;
+    Ok(dict)
+}
+
+/// Sets one perception setting (see [`SetField`]).
+pub(crate) fn set_perception(
+    c: &mut PerceptionConfig,
+    key: &str,
+    value: &Bound<'_, PyAny>,
+) -> PyResult<bool> {
+    match key {
+        "radius" => c.radius = value.extract()?,
+        "see_agents" => c.see_agents = value.extract()?,
+        "cost_per_radius" => c.cost_per_radius = value.extract()?,
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+/// Describes the pheromone settings.
+///
+/// ### Parameters
+/// - `py`: The Python interpreter.
+/// - `c`: The settings.
+///
+/// ### Returns
+/// A dict with `enabled`, `deposit`, `evaporation` and `diffusion`.
+pub(crate) fn pheromone<'py>(py: Python<'py>, c: &PheromoneConfig) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("enabled", c.enabled)?;
+    dict.set_item("deposit", c.deposit)?;
+    dict.set_item("evaporation", c.evaporation)?;
+    dict.set_item("diffusion", c.diffusion)?;
+    Ok(dict)
+}
+
+/// Sets one pheromone setting (see [`SetField`]).
+pub(crate) fn set_pheromone(
+    c: &mut PheromoneConfig,
+    key: &str,
+    value: &Bound<'_, PyAny>,
+) -> PyResult<bool> {
+    match key {
+        "enabled" => c.enabled = value.extract()?,
+        "deposit" => c.deposit = value.extract()?,
+        "evaporation" => c.evaporation = value.extract()?,
+        "diffusion" => c.diffusion = value.extract()?,
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+/// Describes the evolution settings.
+///
+/// ### Parameters
+/// - `py`: The Python interpreter.
+/// - `c`: The settings.
+///
+/// ### Returns
+/// A dict with `enabled`, `hidden`, `appetite`, `birth_cost`, `child_hp`,
+/// `mutation_rate`, `mutation_size` and `max_agents`.
+pub(crate) fn evolution<'py>(py: Python<'py>, c: &EvolutionConfig) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("enabled", c.enabled)?;
+    dict.set_item("hidden", c.hidden)?;
+    dict.set_item("appetite", c.appetite)?;
+    dict.set_item("birth_cost", c.birth_cost)?;
+    dict.set_item("child_hp", c.child_hp)?;
+    dict.set_item("mutation_rate", c.mutation_rate)?;
+    dict.set_item("mutation_size", c.mutation_size)?;
+    dict.set_item("max_agents", c.max_agents)?;
+    Ok(dict)
+}
+
+/// Sets one evolution setting (see [`SetField`]).
+pub(crate) fn set_evolution(
+    c: &mut EvolutionConfig,
+    key: &str,
+    value: &Bound<'_, PyAny>,
+) -> PyResult<bool> {
+    match key {
+        "enabled" => c.enabled = value.extract()?,
+        "hidden" => c.hidden = value.extract()?,
+        "appetite" => c.appetite = value.extract()?,
+        "birth_cost" => c.birth_cost = value.extract()?,
+        "child_hp" => c.child_hp = value.extract()?,
+        "mutation_rate" => c.mutation_rate = value.extract()?,
+        "mutation_size" => c.mutation_size = value.extract()?,
+        "max_agents" => c.max_agents = value.extract()?,
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+/// Describes the aging settings.
+///
+/// ### Parameters
+/// - `py`: The Python interpreter.
+/// - `c`: The settings.
+///
+/// ### Returns
+/// A dict with `enabled`, `min_lifespan`, `max_lifespan` and `senescence`.
+pub(crate) fn aging<'py>(py: Python<'py>, c: &AgingConfig) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("enabled", c.enabled)?;
+    dict.set_item("min_lifespan", c.min_lifespan)?;
+    dict.set_item("max_lifespan", c.max_lifespan)?;
+    dict.set_item("senescence", c.senescence)?;
+    Ok(dict)
+}
+
+/// Sets one aging setting (see [`SetField`]).
+pub(crate) fn set_aging(
+    c: &mut AgingConfig,
+    key: &str,
+    value: &Bound<'_, PyAny>,
+) -> PyResult<bool> {
+    match key {
+        "enabled" => c.enabled = value.extract()?,
+        "min_lifespan" => c.min_lifespan = value.extract()?,
+        "max_lifespan" => c.max_lifespan = value.extract()?,
+        "senescence" => c.senescence = value.extract()?,
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+/// Describes the disease settings.
+///
+/// ### Parameters
+/// - `py`: The Python interpreter.
+/// - `c`: The settings.
+///
+/// ### Returns
+/// A dict with `enabled`, `initial_infected`, `transmission`,
+/// `adjacent_transmission`, `duration`, `extra_need`, `hp_drain` and
+/// `lasting_immunity`.
+pub(crate) fn disease<'py>(py: Python<'py>, c: &DiseaseConfig) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("enabled", c.enabled)?;
+    dict.set_item("initial_infected", c.initial_infected)?;
+    dict.set_item("transmission", c.transmission)?;
+    dict.set_item("adjacent_transmission", c.adjacent_transmission)?;
+    dict.set_item("duration", c.duration)?;
+    dict.set_item("extra_need", c.extra_need)?;
+    dict.set_item("hp_drain", c.hp_drain)?;
+    dict.set_item("lasting_immunity", c.lasting_immunity)?;
+    Ok(dict)
+}
+
+/// Sets one disease setting (see [`SetField`]).
+pub(crate) fn set_disease(
+    c: &mut DiseaseConfig,
+    key: &str,
+    value: &Bound<'_, PyAny>,
+) -> PyResult<bool> {
+    match key {
+        "enabled" => c.enabled = value.extract()?,
+        "initial_infected" => c.initial_infected = value.extract()?,
+        "transmission" => c.transmission = value.extract()?,
+        "adjacent_transmission" => c.adjacent_transmission = value.extract()?,
+        "duration" => c.duration = value.extract()?,
+        "extra_need" => c.extra_need = value.extract()?,
+        "hp_drain" => c.hp_drain = value.extract()?,
+        "lasting_immunity" => c.lasting_immunity = value.extract()?,
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+/// Describes the territory settings.
+///
+/// ### Parameters
+/// - `py`: The Python interpreter.
+/// - `c`: The settings.
+///
+/// ### Returns
+/// A dict with `enabled`, `claim_after`, `abandon_after`, `owner_priority`
+/// and `intrusion_cost`.
+pub(crate) fn territory<'py>(py: Python<'py>, c: &TerritoryConfig) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("enabled", c.enabled)?;
+    dict.set_item("claim_after", c.claim_after)?;
+    dict.set_item("abandon_after", c.abandon_after)?;
+    dict.set_item("owner_priority", c.owner_priority)?;
+    dict.set_item("intrusion_cost", c.intrusion_cost)?;
+    Ok(dict)
+}
+
+/// Sets one territory setting (see [`SetField`]).
+pub(crate) fn set_territory(
+    c: &mut TerritoryConfig,
+    key: &str,
+    value: &Bound<'_, PyAny>,
+) -> PyResult<bool> {
+    match key {
+        "enabled" => c.enabled = value.extract()?,
+        "claim_after" => c.claim_after = value.extract()?,
+        "abandon_after" => c.abandon_after = value.extract()?,
+        "owner_priority" => c.owner_priority = value.extract()?,
+        "intrusion_cost" => c.intrusion_cost = value.extract()?,
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+/// Describes the combat settings.
+///
+/// ### Parameters
+/// - `py`: The Python interpreter.
+/// - `c`: The settings.
+///
+/// ### Returns
+/// A dict with `enabled`, `fight_cost`, `min_aggression` and
+/// `max_aggression`.
+pub(crate) fn combat<'py>(py: Python<'py>, c: &CombatConfig) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("enabled", c.enabled)?;
+    dict.set_item("fight_cost", c.fight_cost)?;
+    dict.set_item("min_aggression", c.min_aggression)?;
+    dict.set_item("max_aggression", c.max_aggression)?;
+    Ok(dict)
+}
+
+/// Sets one combat setting (see [`SetField`]).
+pub(crate) fn set_combat(
+    c: &mut CombatConfig,
+    key: &str,
+    value: &Bound<'_, PyAny>,
+) -> PyResult<bool> {
+    match key {
+        "enabled" => c.enabled = value.extract()?,
+        "fight_cost" => c.fight_cost = value.extract()?,
+        "min_aggression" => c.min_aggression = value.extract()?,
+        "max_aggression" => c.max_aggression = value.extract()?,
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+/// Describes the group settings.
+///
+/// ### Parameters
+/// - `py`: The Python interpreter.
+/// - `c`: The settings.
+///
+/// ### Returns
+/// A dict with `enabled`, `groups`, `altruistic_groups`, `reserve`,
+/// `max_gift` and `share_adjacent`.
+pub(crate) fn groups<'py>(py: Python<'py>, c: &GroupConfig) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("enabled", c.enabled)?;
+    dict.set_item("groups", c.groups)?;
+    dict.set_item("altruistic_groups", c.altruistic_groups)?;
+    dict.set_item("reserve", c.reserve)?;
+    dict.set_item("max_gift", c.max_gift)?;
+    dict.set_item("share_adjacent", c.share_adjacent)?;
+    Ok(dict)
+}
+
+/// Sets one group setting (see [`SetField`]).
+pub(crate) fn set_groups(
+    c: &mut GroupConfig,
+    key: &str,
+    value: &Bound<'_, PyAny>,
+) -> PyResult<bool> {
+    match key {
+        "enabled" => c.enabled = value.extract()?,
+        "groups" => c.groups = value.extract()?,
+        "altruistic_groups" => c.altruistic_groups = value.extract()?,
+        "reserve" => c.reserve = value.extract()?,
+        "max_gift" => c.max_gift = value.extract()?,
+        "share_adjacent" => c.share_adjacent = value.extract()?,
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
@@ -0,0 +1,52 @@
+//! Python bindings for the 2-D resource-competition simulation.
+//!
+//! The crate builds a Python extension module named `rcs` (for example with
+//! `maturin develop --release` in this directory) so that simulations can be
+//! driven and analysed from Python without exporting files:
+//!
+//! - [`PyWorldConfig`]: `rcs.WorldConfig`, the world configuration with one
+//!   property per setting and a dict per optional layer.
+//! - [`PyWorld`]: `rcs.World`, created with `World.from_config(config)`,
+//!   advanced with `update()` or `step(n)` and read as numpy arrays.
+//! - [`arrays`]: the array layouts returned to Python, usable from Rust too.
+//!
+//! Simulation errors are raised as `ValueError` for invalid configurations
+//! and `RuntimeError` otherwise.
+
+pub mod arrays;
+pub mod config;
+mod layers;
+pub mod world;
+
+pub use config::PyWorldConfig;
+pub use world::PyWorld;
+
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use rcs_core::SimulationError;
+
+/// Converts a simulation error into a Python exception.
+///
+/// ### Parameters
+/// - `err`: The error raised by `rcs_core`.
+///
+/// ### Returns
+/// A `ValueError` for [`SimulationError::InvalidConfig`], a `RuntimeError`
+/// for everything else.
+pub(crate) fn to_py_err(err: SimulationError) -> PyErr {
+    match err {
+        SimulationError::InvalidConfig(_) => PyValueError::new_err(err.to_string()),
+        _ => PyRuntimeError::new_err(err.to_string()),
+    }
+}
+
+/// The `rcs` Python module.
+///
+/// ### Parameters
+/// - `m`: The module being initialised.
+#[pymodule]
+fn rcs(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyWorldConfig>()?;
+    m.add_class::<PyWorld>()?;
+    Ok(())
+}
@@ -0,0 +1,125 @@
+use crate::{PyWorldConfig, arrays, to_py_err};
+use numpy::{IntoPyArray, PyArray1, PyArray2};
+use pyo3::prelude::*;
+use rcs_core::{Updatable, World};
+
+/// `rcs.World`: a running simulation.
+///
+/// Create one with `World.from_config(config)`, advance it with `update()`
+/// or `step(n)` and read its state as numpy arrays. The arrays are copies
+/// taken when the method is called; they do not change as the world keeps
+/// running.
+#[pyclass(name = "World", module = "rcs")]
+pub struct PyWorld {
+    pub inner: World,
+}
+
+#[pymethods]
+impl PyWorld {
+    /// Builds a randomized world.
+    ///
+    /// ### Parameters
+    /// - `config`: The world configuration.
+    ///
+    /// ### Returns
+    /// - `Ok(world)` on success.
+    /// - `Err(ValueError)` if the configuration is invalid.
+    #[staticmethod]
+    fn from_config(config: &PyWorldConfig) -> PyResult<Self> {
+        let inner = World::try_from_config(config.inner).map_err(to_py_err)?;
+        Ok(Self { inner })
+    }
+
+    /// Advances the world by one tick.
+    ///
+    /// ### Returns
+    /// - `Ok(())` on success.
+    /// - `Err(RuntimeError)` describing what went wrong during the tick.
+    fn update(&mut self) -> PyResult<()> {
+        self.inner.update().map_err(to_py_err)
+    }
+
+    /// Advances the world by several ticks.
+    ///
+    /// Python threads keep running while the world is stepped.
+    ///
+    /// ### Parameters
+    /// - `ticks`: Number of ticks to run.
+    ///
+    /// ### Returns
+    /// - `Ok(tick)` with the new tick counter.
+    /// - `Err(RuntimeError)` from the first failing tick; the ticks before
+    ///   it are kept.
+    #[pyo3(signature = (ticks = 1))]
+    fn step(&mut self, py: Python<'_>, ticks: u64) -> PyResult<u64> {
+        let world = &mut self.inner;
+        py.detach(|| (0..ticks).try_for_each(|_| world.update()))
+            .map_err(to_py_err)?;
+        Ok(self.inner.tick())
+    }
+
+    /// Number of completed ticks.
+    #[getter]
+    fn tick(&self) -> u64 {
+        self.inner.tick()
+    }
+
+    /// Grid width in cells.
+    #[getter]
+    fn width(&self) -> usize {
+        self.inner.size().0
+    }
+
+    /// Grid height in cells.
+    #[getter]
+    fn height(&self) -> usize {
+        self.inner.size().1
+    }
+
+    /// Number of living agents.
+    #[getter]
+    fn living(&self) -> usize {
+        self.inner.agents().iter().filter(|a| a.is_alive()).count()
+    }
+
+    /// Current resource of every cell.
+    ///
+    /// ### Returns
+    /// A `(height, width)` `uint32` array (see [`arrays::cell_resources`]).
+    fn cell_resources<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray2<u32>> {
+        arrays::cell_resources(&self.inner).into_pyarray(py)
+    }
+
+    /// Ids of the living agents, in the row order of the other agent arrays.
+    ///
+    /// ### Returns
+    /// A `uint64` array.
+    fn agent_ids<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<u64>> {
+        arrays::agent_ids(&self.inner).into_pyarray(py)
+    }
+
+    /// Grid coordinates of the living agents.
+    ///
+    /// ### Returns
+    /// An `(n, 2)` `uint64` array of `(x, y)` rows.
+    fn agent_positions<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray2<u64>> {
+        arrays::agent_positions(&self.inner).into_pyarray(py)
+    }
+
+    /// Health points of the living agents.
+    ///
+    /// ### Returns
+    /// A `uint32` array.
+    fn agent_hp<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<u32>> {
+        arrays::agent_hp(&self.inner).into_pyarray(py)
+    }
+
+    fn __repr__(&self) -> String {
+        let (width, height) = self.inner.size();
+        format!(
+            "World({width}x{height}, tick={}, living={})",
+            self.inner.tick(),
+            self.living()
+        )
+    }
+}
@@ -0,0 +1,68 @@
+"""Smoke tests for the built `rcs` module.
+
+Build the module into the active virtualenv first (`maturin develop` in
+`rcs_py`), then run `python -m unittest discover rcs_py/tests`. The array
+tests are skipped when numpy is not installed.
+"""
+
+import importlib.util
+import unittest
+
+import rcs
+
+HAS_NUMPY = importlib.util.find_spec("numpy") is not None
+
+
+class WorldConfigTest(unittest.TestCase):
+    def test_keyword_settings(self):
+        config = rcs.WorldConfig(width=12, height=8, seed=3, policy="vision")
+        self.assertEqual((config.width, config.height, config.seed), (12, 8, 3))
+        self.assertEqual(config.policy, "vision")
+        with self.assertRaises(AttributeError):
+            rcs.WorldConfig(no_such_setting=1)
+
+    def test_layers_update_only_the_named_fields(self):
+        config = rcs.WorldConfig(combat={"enabled": True, "fight_cost": 2})
+        combat = config.combat
+        self.assertTrue(combat["enabled"])
+        self.assertEqual(combat["fight_cost"], 2)
+        self.assertIn("max_aggression", combat)
+
+        duration = config.disease["duration"]
+        config.disease = {"enabled": True}
+        self.assertTrue(config.disease["enabled"])
+        self.assertEqual(config.disease["duration"], duration)
+
+        config.agent_memory = 4
+        self.assertEqual(config.agent_memory, 4)
+        with self.assertRaises(AttributeError):
+            config.groups = {"no_such_field": 1}
+        config.validate()
+
+
+class WorldTest(unittest.TestCase):
+    def test_step_counts_ticks(self):
+        world = rcs.World.from_config(rcs.WorldConfig(width=10, height=6, seed=7))
+        self.assertEqual(world.step(5), 5)
+        world.update()
+        self.assertEqual(world.tick, 6)
+        self.assertEqual((world.width, world.height), (10, 6))
+
+
+@unittest.skipUnless(HAS_NUMPY, "numpy is not installed")
+class WorldArraysTest(unittest.TestCase):
+    def test_step_and_array_shapes(self):
+        config = rcs.WorldConfig(width=10, height=6, min_agents=5, max_agents=5, seed=7)
+        world = rcs.World.from_config(config)
+        world.step(20)
+        self.assertEqual(world.tick, 20)
+
+        self.assertEqual(world.cell_resources().shape, (6, 10))
+        n = world.living
+        self.assertEqual(world.agent_ids().shape, (n,))
+        self.assertEqual(world.agent_positions().shape, (n, 2))
+        self.assertEqual(world.agent_hp().shape, (n,))
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,479 @@
+use rcs_core::{Agent, Cell, World};
+use rhai::{Array, Dynamic, Engine, FLOAT, INT, Map};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+/// Operation budget of a single script call; stops runaway loops.
+const MAX_OPERATIONS: u64 = 1_000_000;
+
+/// Deepest call stack a script may build.
+const MAX_CALL_LEVELS: usize = 64;
+
+/// Number of `print` / `debug` lines kept until they are read.
+const MAX_OUTPUT_LINES: usize = 100;
+
+/// Lines printed by a script, oldest first.
+pub(crate) type Output = Arc<Mutex<VecDeque<String>>>;
+
+/// Adds a printed line, forgetting the oldest one if the log is full.
+///
+/// ### Parameters
+/// - `output`: The script's output log.
+/// - `line`: The printed text.
+fn push_line(output: &Output, line: String) {
+    let mut lines = output.lock().unwrap_or_else(|e| e.into_inner());
+    if lines.len() == MAX_OUTPUT_LINES {
+        lines.pop_front();
+    }
+    lines.push_back(line);
+}
+
+/// Builds the sandboxed engine that runs scenario scripts.
+///
+/// Scripts cannot touch the file system or the host; besides the Rhai
+/// standard library they only see the [`WorldView`] type registered here.
+/// Every call is limited to [`MAX_OPERATIONS`] operations. `print` and
+/// `debug` write to `output` instead of the host's standard output; only
+/// the last [`MAX_OUTPUT_LINES`] lines are kept.
+///
+/// ### Parameters
+/// - `output`: Receives the lines the script prints.
+///
+/// ### Returns
+/// A configured [`Engine`].
+pub(crate) fn build_engine(output: Output) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.set_max_call_levels(MAX_CALL_LEVELS);
+    let printed = Arc::clone(&output);
+    engine.on_print(move |text| push_line(&printed, text.to_string()));
+    engine.on_debug(move |text, _source, pos| {
+        let line = if pos.is_none() {
+            text.to_string()
+        } else {
+            format!("{pos} | {text}")
+        };
+        push_line(&output, line);
+    });
+    engine
+        .register_type_with_name::<WorldView>("World")
+        .register_get("tick", |w: &mut WorldView| w.snapshot.tick as INT)
+        .register_get("width", |w: &mut WorldView| w.snapshot.size.0 as INT)
+        .register_get("height", |w: &mut WorldView| w.snapshot.size.1 as INT)
+        .register_get("living", |w: &mut WorldView| w.snapshot.agents.len() as INT)
+        .register_fn("cell", WorldView::cell)
+        .register_fn("agents", WorldView::agents)
+        .register_fn("agents_on", WorldView::agents_on)
+        .register_fn("add_resource", WorldView::add_resource)
+        .register_fn("remove_resource", WorldView::remove_resource)
+        .register_fn("deposit_pheromone", WorldView::deposit_pheromone);
+    engine
+}
+
+/// Converts a script integer to a cell id or amount.
+///
+/// ### Parameters
+/// - `value`: Integer passed by the script.
+///
+/// ### Returns
+/// - `Some(value)` if it is not negative.
+/// - `None` otherwise.
+pub(crate) fn to_usize(value: INT) -> Option<usize> {
+    usize::try_from(value).ok()
+}
+
+/// Describes a cell to a script.
+///
+/// ### Parameters
+/// - `cell`: The cell to describe.
+///
+/// ### Returns
+/// A map with `id`, `resource`, `max_resource`, `regen_rate`,
+/// `max_regen_rate` and `terrain_cost`.
+pub fn cell_map(cell: &Cell) -> Map {
+    CellInfo::from(cell).to_map()
+}
+
+/// The values of a cell that scripts can read.
+#[derive(Debug, Clone, Copy)]
+struct CellInfo {
+    id: usize,
+    resource: u32,
+    max_resource: u32,
+    regen_rate: u32,
+    max_regen_rate: u32,
+    terrain_cost: u32,
+}
+
+impl From<&Cell> for CellInfo {
+    fn from(cell: &Cell) -> Self {
+        Self {
+            id: cell.id(),
+            resource: cell.cur_resource(),
+            max_resource: cell.max_resource(),
+            regen_rate: cell.regen_rate(),
+            max_regen_rate: cell.max_regen_rate(),
+            terrain_cost: cell.terrain_cost(),
+        }
+    }
+}
+
+impl CellInfo {
+    /// Builds the map seen by scripts (see [`cell_map`]).
+    ///
+    /// ### Returns
+    /// A new [`Map`].
+    fn to_map(self) -> Map {
+        let mut map = Map::new();
+        map.insert("id".into(), (self.id as INT).into());
+        map.insert("resource".into(), INT::from(self.resource).into());
+        map.insert("max_resource".into(), INT::from(self.max_resource).into());
+        map.insert("regen_rate".into(), INT::from(self.regen_rate).into());
+        map.insert(
+            "max_regen_rate".into(),
+            INT::from(self.max_regen_rate).into(),
+        );
+        map.insert("terrain_cost".into(), INT::from(self.terrain_cost).into());
+        map
+    }
+}
+
+/// Describes an agent to a script.
+///
+/// ### Parameters
+/// - `agent`: The agent to describe.
+///
+/// ### Returns
+/// A map with `id`, `cid`, `hp`, `energy`, `need`, `hungry`, `alive`,
+/// `age`, `aggression` and `group` (`()` without a group).
+pub fn agent_map(agent: &Agent) -> Map {
+    AgentInfo::from(agent).to_map()
+}
+
+/// The values of an agent that scripts can read.
+#[derive(Debug, Clone, Copy)]
+struct AgentInfo {
+    id: usize,
+    cid: usize,
+    hp: u32,
+    energy: u32,
+    need: u32,
+    hungry: bool,
+    alive: bool,
+    age: u64,
+    aggression: f32,
+    group: Option<usize>,
+}
+
+impl From<&Agent> for AgentInfo {
+    fn from(agent: &Agent) -> Self {
+        Self {
+            id: agent.id(),
+            cid: agent.cid(),
+            hp: agent.health_point(),
+            energy: agent.energy(),
+            need: agent.need(),
+            hungry: agent.is_hungry(),
+            alive: agent.is_alive(),
+            age: agent.age(),
+            aggression: agent.aggression(),
+            group: agent.group(),
+        }
+    }
+}
+
+impl AgentInfo {
+    /// Builds the map seen by scripts (see [`agent_map`]).
+    ///
+    /// ### Returns
+    /// A new [`Map`].
+    fn to_map(self) -> Map {
+        let mut map = Map::new();
+        map.insert("id".into(), (self.id as INT).into());
+        map.insert("cid".into(), (self.cid as INT).into());
+        map.insert("hp".into(), INT::from(self.hp).into());
+        map.insert("energy".into(), INT::from(self.energy).into());
+        map.insert("need".into(), INT::from(self.need).into());
+        map.insert("hungry".into(), self.hungry.into());
+        map.insert("alive".into(), self.alive.into());
+        map.insert("age".into(), (self.age as INT).into());
+        map.insert("aggression".into(), FLOAT::from(self.aggression).into());
+        map.insert(
+            "group".into(),
+            self.group.map_or(Dynamic::UNIT, |g| (g as INT).into()),
+        );
+        map
+    }
+}
+
+/// A change a script asked for from `on_tick`.
+///
+/// Commands are collected while the script runs and applied to the world
+/// afterwards, so the script never holds a reference into the world.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Command {
+    /// Add resource to a cell, up to its maximum.
+    AddResource { cid: usize, amount: u32 },
+    /// Take up to `amount` resource from a cell.
+    RemoveResource { cid: usize, amount: u32 },
+    /// Add pheromone to a cell.
+    DepositPheromone { cid: usize, amount: f32 },
+}
+
+impl Command {
+    /// Applies the command to a world.
+    ///
+    /// Commands naming a cell outside the grid are ignored.
+    ///
+    /// ### Parameters
+    /// - `world`: The world to change.
+    pub fn apply(&self, world: &mut World) {
+        let num_cells = world.cells().len();
+        match *self {
+            Command::AddResource { cid, amount } if cid < num_cells => {
+                world.cell_mut(cid).add_resource(amount);
+            }
+            Command::RemoveResource { cid, amount } if cid < num_cells => {
+                world.cell_mut(cid).take_up_to(amount);
+            }
+            Command::DepositPheromone { cid, amount } if cid < num_cells => {
+                world.deposit_pheromone(cid, amount);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// What `on_tick` knows about the world.
+///
+/// Only plain values are copied; the maps scripts see are built when they
+/// ask for a cell or agent.
+struct Snapshot {
+    tick: u64,
+    size: (usize, usize),
+    cells: Vec<CellInfo>,
+    agents: Vec<AgentInfo>,
+}
+
+/// The `World` object handed to a script's `on_tick`.
+///
+/// It is a read-only snapshot of the world at the end of the tick plus a
+/// queue of [`Command`]s. Scripts read it through the properties `tick`,
+/// `width`, `height` and `living` and the methods `cell(cid)`, `agents()`
+/// and `agents_on(cid)`, and change the world through
+/// `add_resource(cid, amount)`, `remove_resource(cid, amount)` and
+/// `deposit_pheromone(cid, amount)`.
+#[derive(Clone)]
+pub struct WorldView {
+    snapshot: Arc<Snapshot>,
+    commands: Arc<Mutex<Vec<Command>>>,
+}
+
+impl WorldView {
+    /// Takes a snapshot of a world.
+    ///
+    /// ### Parameters
+    /// - `world`: The world to describe.
+    ///
+    /// ### Returns
+    /// A [`WorldView`] with an empty command queue.
+    pub fn new(world: &World) -> Self {
+        let snapshot = Snapshot {
+            tick: world.tick(),
+            size: world.size(),
+            cells: world.cells().iter().map(CellInfo::from).collect(),
+            agents: world
+                .agents()
+                .iter()
+                .filter(|a| a.is_alive())
+                .map(AgentInfo::from)
+                .collect(),
+        };
+        Self {
+            snapshot: Arc::new(snapshot),
+            commands: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Removes and returns the queued commands.
+    ///
+    /// ### Returns
+    /// The commands in the order the script issued them.
+    pub fn take_commands(&self) -> Vec<Command> {
+        std::mem::take(&mut *self.commands.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// Script method `cell(cid)`.
+    ///
+    /// ### Parameters
+    /// - `cid`: Cell id.
+    ///
+    /// ### Returns
+    /// The cell's map (see [`cell_map`]), or `()` outside the grid.
+    fn cell(&mut self, cid: INT) -> Dynamic {
+        to_usize(cid)
+            .and_then(|cid| self.snapshot.cells.get(cid))
+            .map_or(Dynamic::UNIT, |cell| cell.to_map().into())
+    }
+
+    /// Script method `agents()`.
+    ///
+    /// ### Returns
+    /// The maps of all living agents (see [`agent_map`]).
+    fn agents(&mut self) -> Array {
+        self.snapshot
+            .agents
+            .iter()
+            .map(|agent| agent.to_map().into())
+            .collect()
+    }
+
+    /// Script method `agents_on(cid)`.
+    ///
+    /// ### Parameters
+    /// - `cid`: Cell id.
+    ///
+    /// ### Returns
+    /// The maps of the living agents on `cid`.
+    fn agents_on(&mut self, cid: INT) -> Array {
+        let Some(cid) = to_usize(cid) else {
+            return Array::new();
+        };
+        self.snapshot
+            .agents
+            .iter()
+            .filter(|agent| agent.cid == cid)
+            .map(|agent| agent.to_map().into())
+            .collect()
+    }
+
+    /// Queues a command, dropping it if an argument is negative.
+    ///
+    /// ### Parameters
+    /// - `command`: The command, or `None` if it was invalid.
+    fn push(&mut self, command: Option<Command>) {
+        if let Some(command) = command {
+            self.commands
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push(command);
+        }
+    }
+
+    /// Script method `add_resource(cid, amount)`.
+    ///
+    /// Negative arguments are ignored.
+    ///
+    /// ### Parameters
+    /// - `cid`: Cell id.
+    /// - `amount`: Resource to add.
+    fn add_resource(&mut self, cid: INT, amount: INT) {
+        let command = to_usize(cid).zip(u32::try_from(amount).ok());
+        self.push(command.map(|(cid, amount)| Command::AddResource { cid, amount }));
+    }
+
+    /// Script method `remove_resource(cid, amount)`.
+    ///
+    /// Negative arguments are ignored.
+    ///
+    /// ### Parameters
+    /// - `cid`: Cell id.
+    /// - `amount`: Resource to take.
+    fn remove_resource(&mut self, cid: INT, amount: INT) {
+        let command = to_usize(cid).zip(u32::try_from(amount).ok());
+        self.push(command.map(|(cid, amount)| Command::RemoveResource { cid, amount }));
+    }
+
+    /// Script method `deposit_pheromone(cid, amount)`.
+    ///
+    /// Negative arguments are ignored.
+    ///
+    /// ### Parameters
+    /// - `cid`: Cell id.
+    /// - `amount`: Pheromone to add.
+    fn deposit_pheromone(&mut self, cid: INT, amount: FLOAT) {
+        let command = to_usize(cid).filter(|_| amount >= 0.0);
+        self.push(command.map(|cid| Command::DepositPheromone {
+            cid,
+            amount: amount as f32,
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn world() -> World {
+        let cells = vec![Cell::new(0, 3, 10, 1, 2), Cell::new(1, 0, 10, 0, 2)];
+        let agents = vec![Agent::new(0, 1, 2, 0, 4, true)];
+        World::new((2, 1), cells, agents)
+    }
+
+    #[test]
+    fn scripts_read_the_snapshot_and_queue_commands() {
+        let engine = build_engine(Output::default());
+        let view = WorldView::new(&world());
+        let result: Array = engine
+            .call_fn(
+                &mut rhai::Scope::new(),
+                &engine
+                    .compile(
+                        "fn run(w) {
+                            w.add_resource(1, 4);
+                            w.remove_resource(-1, 4);
+                            w.deposit_pheromone(0, 0.5);
+                            [w.width, w.living, w.cell(0).resource, w.agents_on(1)[0].hp, w.cell(9)]
+                        }",
+                    )
+                    .unwrap(),
+                "run",
+                (view.clone(),),
+            )
+            .unwrap();
+
+        let ints: Vec<_> = result[..4].iter().map(|v| v.as_int().unwrap()).collect();
+        assert_eq!(ints, [2, 1, 3, 4]);
+        assert!(result[4].is_unit());
+        assert_eq!(
+            view.take_commands(),
+            [
+                Command::AddResource { cid: 1, amount: 4 },
+                Command::DepositPheromone {
+                    cid: 0,
+                    amount: 0.5
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn runaway_scripts_are_stopped() {
+        let engine = build_engine(Output::default());
+        assert!(engine.eval::<INT>("let x = 0; loop { x += 1; }").is_err());
+    }
+
+    #[test]
+    fn prints_go_to_the_bounded_output() {
+        let output = Output::default();
+        let engine = build_engine(Arc::clone(&output));
+        engine
+            .run("for i in 0..150 { print(i); } debug(\"done\");")
+            .unwrap();
+
+        let lines = output.lock().unwrap();
+        assert_eq!(lines.len(), MAX_OUTPUT_LINES);
+        assert_eq!(lines[0], "51");
+        assert!(lines[MAX_OUTPUT_LINES - 1].ends_with("| \"done\""));
+    }
+
+    #[test]
+    fn commands_ignore_cells_outside_the_grid() {
+        let mut world = world();
+        Command::AddResource { cid: 1, amount: 4 }.apply(&mut world);
+        Command::RemoveResource { cid: 0, amount: 9 }.apply(&mut world);
+        Command::AddResource { cid: 7, amount: 4 }.apply(&mut world);
+        assert_eq!(world.cell(1).cur_resource(), 4);
+        assert_eq!(world.cell(0).cur_resource(), 0);
+    }
+}
@@ -0,0 +1,48 @@
+use std::fmt;
+
+/// Errors raised while loading or running a scenario script.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScriptError {
+    /// The scenario file could not be read.
+    Io(String),
+    /// The script does not compile.
+    Parse(String),
+    /// A script function failed or returned a value of the wrong type.
+    Runtime { function: String, reason: String },
+    /// The configuration returned by `config()` is unusable.
+    InvalidConfig(String),
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScriptError::Io(reason) => write!(f, "cannot read scenario: {reason}"),
+            ScriptError::Parse(reason) => write!(f, "script does not compile: {reason}"),
+            ScriptError::Runtime { function, reason } => {
+                write!(f, "script function `{function}` failed: {reason}")
+            }
+            ScriptError::InvalidConfig(reason) => {
+                write!(f, "invalid scenario configuration: {reason}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScriptError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_names_the_failing_function() {
+        let err = ScriptError::Runtime {
+            function: "choose_move".to_string(),
+            reason: "boom".to_string(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "script function `choose_move` failed: boom"
+        );
+    }
+}
@@ -0,0 +1,88 @@
+use crate::api::{WorldView, cell_map};
+use crate::scenario::{ON_TICK_FN, REGEN_FN, SharedScript};
+use rcs_core::{World, WorldHook};
+use rhai::INT;
+
+/// Runs a scenario's `regen` and `on_tick` functions during updates.
+///
+/// `regen(cell, tick)` is called for every cell after the built-in
+/// regeneration; a positive result is added to the cell (up to its
+/// maximum), a negative one is taken from it. `on_tick(world)` runs at the
+/// end of the tick and its queued commands are applied right after.
+///
+/// A failing call skips the rest of that phase for the tick.
+pub struct ScriptHook {
+    script: SharedScript,
+}
+
+impl ScriptHook {
+    /// Creates a hook that follows a shared script.
+    ///
+    /// ### Parameters
+    /// - `script`: The scenario's script.
+    ///
+    /// ### Returns
+    /// A new [`ScriptHook`].
+    pub(crate) fn new(script: SharedScript) -> Self {
+        Self { script }
+    }
+}
+
+impl WorldHook for ScriptHook {
+    fn after_regen(&mut self, world: &mut World) {
+        let script = self.script.read().unwrap_or_else(|e| e.into_inner());
+        if !script.defines(REGEN_FN, 2) {
+            return;
+        }
+        let tick = world.tick() as INT;
+        for cid in 0..world.cells().len() {
+            let Ok(extra) = script.call_int(REGEN_FN, (cell_map(world.cell(cid)), tick)) else {
+                return;
+            };
+            let cell = world.cell_mut(cid);
+            match extra.unwrap_or(0) {
+                n if n > 0 => cell.add_resource(u32::try_from(n).unwrap_or(u32::MAX)),
+                n => {
+                    cell.take_up_to(u32::try_from(n.unsigned_abs()).unwrap_or(u32::MAX));
+                }
+            }
+        }
+    }
+
+    fn after_tick(&mut self, world: &mut World) {
+        let script = self.script.read().unwrap_or_else(|e| e.into_inner());
+        if !script.defines(ON_TICK_FN, 1) {
+            return;
+        }
+        let view = WorldView::new(world);
+        if script.call(ON_TICK_FN, (view.clone(),)).is_ok() {
+            for command in view.take_commands() {
+                command.apply(world);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Scenario;
+    use rcs_core::{Cell, Updatable, World};
+
+    #[test]
+    fn regen_and_on_tick_change_cells() {
+        let scenario = Scenario::from_source(
+            "fn regen(cell, tick) { if cell.id == 0 { 3 } else { -1 } }
+             fn on_tick(world) { world.add_resource(world.width - 1, world.tick); }",
+        )
+        .unwrap();
+        let cells = vec![Cell::new(0, 0, 100, 0, 0), Cell::new(1, 5, 100, 0, 0)];
+        let mut world = World::new((2, 1), cells, Vec::new());
+        scenario.install(&mut world);
+
+        world.update().unwrap();
+        world.update().unwrap();
+        assert_eq!(world.cell(0).cur_resource(), 6);
+        // 5 - 1 + 0 (tick 0) - 1 + 1 (tick 1)
+        assert_eq!(world.cell(1).cur_resource(), 4);
+    }
+}
@@ -0,0 +1,31 @@
+//! Scenario scripting for the 2-D resource-competition simulation.
+//!
+//! This crate lets researchers try out new rules without recompiling:
+//! a [`Scenario`] is a [Rhai](https://rhai.rs) script that can override
+//! the world configuration, add per-cell regeneration, steer agents and
+//! change the world at the end of every tick. Scenarios are installed into
+//! an [`rcs_core::World`] as a [`ScriptPolicy`] and a [`ScriptHook`] and
+//! can be reloaded while the simulation runs.
+//!
+//! - [`Scenario`]: loading, hot reloading and installing a script.
+//! - [`ScriptPolicy`]: a [`rcs_core::MovementPolicy`] backed by
+//!   `choose_move`.
+//! - [`ScriptHook`]: a [`rcs_core::WorldHook`] backed by `regen` and
+//!   `on_tick`.
+//! - [`WorldView`]: the read-only world snapshot and command queue passed
+//!   to `on_tick`.
+//! - [`ScriptError`]: errors raised while loading or running scripts.
+//!
+//! See `scenarios/oasis.rhai` for an example.
+
+pub mod api;
+pub mod errors;
+pub mod hook;
+pub mod policy;
+pub mod scenario;
+
+pub use api::{Command, WorldView, agent_map, cell_map};
+pub use errors::ScriptError;
+pub use hook::ScriptHook;
+pub use policy::ScriptPolicy;
+pub use scenario::Scenario;
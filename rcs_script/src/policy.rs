@@ -0,0 +1,92 @@
+use crate::api::{agent_map, cell_map, to_usize};
+use crate::scenario::{CHOOSE_MOVE_FN, SharedScript};
+use rcs_core::{Agent, GreedyPolicy, MovementPolicy, World};
+use rhai::Array;
+
+/// A movement policy implemented by a scenario's `choose_move`.
+///
+/// The function gets the agent and its neighboring cells as maps (see
+/// [`agent_map`] and [`cell_map`]) and returns the id of the cell to move
+/// to, or `()` to stay. If the call fails, or if a reload removed the
+/// function, the agent falls back to the [`GreedyPolicy`].
+pub struct ScriptPolicy {
+    script: SharedScript,
+}
+
+impl ScriptPolicy {
+    /// Creates a policy that follows a shared script.
+    ///
+    /// ### Parameters
+    /// - `script`: The scenario's script.
+    ///
+    /// ### Returns
+    /// A new [`ScriptPolicy`].
+    pub(crate) fn new(script: SharedScript) -> Self {
+        Self { script }
+    }
+}
+
+impl MovementPolicy for ScriptPolicy {
+    fn name(&self) -> &'static str {
+        "script"
+    }
+
+    fn decide(&self, world: &World, agent: &Agent) -> Option<usize> {
+        let script = self.script.read().unwrap_or_else(|e| e.into_inner());
+        if !script.defines(CHOOSE_MOVE_FN, 2) {
+            return GreedyPolicy.decide(world, agent);
+        }
+        let neighbors: Array = world
+            .neighbor_cells_info(agent.cid())
+            .into_iter()
+            .map(|(cid, _)| cell_map(world.cell(cid)).into())
+            .collect();
+        match script.call_int(CHOOSE_MOVE_FN, (agent_map(agent), neighbors)) {
+            Ok(choice) => choice.and_then(to_usize),
+            Err(_) => GreedyPolicy.decide(world, agent),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Scenario;
+    use rcs_core::{Agent, Cell, Updatable, World};
+
+    fn world() -> World {
+        let cells = vec![
+            Cell::new(0, 0, 100, 0, 0),
+            Cell::new(1, 1, 100, 0, 0),
+            Cell::new(2, 9, 100, 0, 0),
+        ];
+        let agents = vec![Agent::new(0, 1, 5, 0, 10, true)];
+        World::new((3, 1), cells, agents)
+    }
+
+    #[test]
+    fn agents_follow_the_script() {
+        // Always walk left, towards the empty cell.
+        let scenario = Scenario::from_source(
+            "fn choose_move(agent, neighbors) {
+                for n in neighbors { if n.id < agent.cid { return n.id; } }
+            }",
+        )
+        .unwrap();
+        let mut world = world();
+        scenario.install(&mut world);
+        world.update().unwrap();
+        assert_eq!(scenario.take_error(), None);
+        assert_eq!(world.agents()[0].cid(), 0);
+    }
+
+    #[test]
+    fn failing_scripts_fall_back_to_greedy() {
+        let scenario =
+            Scenario::from_source("fn choose_move(agent, neighbors) { \"left\" }").unwrap();
+        let mut world = world();
+        scenario.install(&mut world);
+        world.update().unwrap();
+        assert_eq!(world.agents()[0].cid(), 2);
+        assert!(scenario.take_error().is_some());
+    }
+}
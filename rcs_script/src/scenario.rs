@@ -0,0 +1,505 @@
+use crate::api::{self, to_usize};
+use crate::errors::ScriptError;
+use crate::hook::ScriptHook;
+use crate::policy::ScriptPolicy;
+use rcs_core::{World, WorldConfig};
+use rhai::{AST, CallFnOptions, Dynamic, Engine, FuncArgs, INT, Map, Scope};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::SystemTime;
+
+/// Name of the optional function returning configuration overrides.
+pub const CONFIG_FN: &str = "config";
+
+/// Name of the optional function returning extra regeneration per cell.
+pub const REGEN_FN: &str = "regen";
+
+/// Name of the optional movement function.
+pub const CHOOSE_MOVE_FN: &str = "choose_move";
+
+/// Name of the optional per-tick function.
+pub const ON_TICK_FN: &str = "on_tick";
+
+/// A compiled script and the engine that runs it.
+pub(crate) struct Script {
+    engine: Engine,
+    ast: AST,
+    last_error: Mutex<Option<ScriptError>>,
+    output: api::Output,
+}
+
+/// A script shared between a [`Scenario`] and the policy and hook it
+/// installed, so that reloading the scenario updates them in place.
+pub(crate) type SharedScript = Arc<RwLock<Script>>;
+
+impl Script {
+    /// Returns whether the script defines a function.
+    ///
+    /// ### Parameters
+    /// - `name`: Function name.
+    /// - `arity`: Number of parameters.
+    ///
+    /// ### Returns
+    /// `true` if a function `name` with `arity` parameters exists.
+    pub(crate) fn defines(&self, name: &str, arity: usize) -> bool {
+        self.ast
+            .iter_functions()
+            .any(|f| f.name == name && f.params.len() == arity)
+    }
+
+    /// Calls a script function.
+    ///
+    /// Failures are also remembered for [`Scenario::take_error`].
+    ///
+    /// ### Parameters
+    /// - `name`: Function name.
+    /// - `args`: Arguments, e.g. a tuple of maps and integers.
+    ///
+    /// ### Returns
+    /// - `Ok(value)` returned by the function.
+    /// - `Err(ScriptError::Runtime { .. })` if the call failed.
+    pub(crate) fn call(&self, name: &str, args: impl FuncArgs) -> Result<Dynamic, ScriptError> {
+        let options = CallFnOptions::new().eval_ast(false);
+        self.engine
+            .call_fn_with_options(options, &mut Scope::new(), &self.ast, name, args)
+            .map_err(|err| self.fail(name, err.to_string()))
+    }
+
+    /// Calls a script function that returns an integer or `()`.
+    ///
+    /// ### Parameters
+    /// - `name`: Function name.
+    /// - `args`: Arguments.
+    ///
+    /// ### Returns
+    /// - `Ok(Some(value))` for an integer, `Ok(None)` for `()`.
+    /// - `Err(ScriptError::Runtime { .. })` if the call failed or returned
+    ///   anything else.
+    pub(crate) fn call_int(
+        &self,
+        name: &str,
+        args: impl FuncArgs,
+    ) -> Result<Option<INT>, ScriptError> {
+        let value = self.call(name, args)?;
+        if value.is_unit() {
+            return Ok(None);
+        }
+        value
+            .as_int()
+            .map(Some)
+            .map_err(|found| self.fail(name, format!("expected an integer or (), got {found}")))
+    }
+
+    /// Records a runtime failure.
+    ///
+    /// ### Parameters
+    /// - `name`: Function that failed.
+    /// - `reason`: What went wrong.
+    ///
+    /// ### Returns
+    /// The recorded [`ScriptError::Runtime`].
+    fn fail(&self, name: &str, reason: String) -> ScriptError {
+        let err = ScriptError::Runtime {
+            function: name.to_string(),
+            reason,
+        };
+        *self.last_error.lock().unwrap_or_else(|e| e.into_inner()) = Some(err.clone());
+        err
+    }
+}
+
+/// A scenario script with custom rules.
+///
+/// A scenario is a [Rhai](https://rhai.rs) script that may define any of
+/// these functions:
+///
+/// - `config()`: returns a map of [`WorldConfig`] overrides (`width`,
+///   `height`, `min_agents`, `max_agents`, `min_resource`, `max_resource`,
+///   `min_regen_rate`, `max_regen_rate`, `min_consumption_rate`,
+///   `max_consumption_rate`, `agent_hp`, `seed`)
+/// - `regen(cell, tick)`: returns resource to add to (or, if negative,
+///   take from) a cell after the built-in regeneration
+/// - `choose_move(agent, neighbors)`: returns the id of the neighbor to
+///   move to, or `()` to stay
+/// - `on_tick(world)`: runs at the end of every tick and may queue changes
+///   (see [`crate::WorldView`])
+///
+/// Cells and agents are passed as maps (see [`crate::cell_map`] and
+/// [`crate::agent_map`]). Scripts run in a sandbox with an operation
+/// budget, so a broken script cannot hang or crash the simulation; a
+/// failing call is skipped and reported through [`Scenario::take_error`].
+pub struct Scenario {
+    path: Option<PathBuf>,
+    stamp: Option<(SystemTime, u64)>,
+    script: SharedScript,
+}
+
+impl Scenario {
+    /// Compiles a scenario from source text.
+    ///
+    /// ### Parameters
+    /// - `source`: Rhai source code.
+    ///
+    /// ### Returns
+    /// - `Ok(Scenario)` without a file to reload from.
+    /// - `Err(ScriptError::Parse(..))` if the script does not compile.
+    pub fn from_source(source: &str) -> Result<Self, ScriptError> {
+        let output = api::Output::default();
+        let engine = api::build_engine(Arc::clone(&output));
+        let ast = compile(&engine, source)?;
+        Ok(Self {
+            path: None,
+            stamp: None,
+            script: Arc::new(RwLock::new(Script {
+                engine,
+                ast,
+                last_error: Mutex::new(None),
+                output,
+            })),
+        })
+    }
+
+    /// Loads a scenario from a file.
+    ///
+    /// ### Parameters
+    /// - `path`: Path of the `.rhai` file.
+    ///
+    /// ### Returns
+    /// - `Ok(Scenario)` that can be reloaded from `path`.
+    /// - `Err(ScriptError::Io(..))` if the file cannot be read.
+    /// - `Err(ScriptError::Parse(..))` if the script does not compile.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ScriptError> {
+        let path = path.as_ref();
+        let mut scenario = Self::from_source(&read(path)?)?;
+        scenario.path = Some(path.to_path_buf());
+        scenario.stamp = stamp(path);
+        Ok(scenario)
+    }
+
+    /// Returns the file the scenario was loaded from.
+    ///
+    /// ### Returns
+    /// - `Some(path)` for scenarios created with [`Scenario::load`].
+    /// - `None` for [`Scenario::from_source`].
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Replaces the script with new source text.
+    ///
+    /// Policies and hooks installed earlier use the new script from their
+    /// next call on. If the source does not compile, the old script stays.
+    ///
+    /// ### Parameters
+    /// - `source`: Rhai source code.
+    ///
+    /// ### Returns
+    /// - `Ok(())` if the script was replaced.
+    /// - `Err(ScriptError::Parse(..))` otherwise.
+    pub fn set_source(&mut self, source: &str) -> Result<(), ScriptError> {
+        let mut script = self.script.write().unwrap_or_else(|e| e.into_inner());
+        script.ast = compile(&script.engine, source)?;
+        *script.last_error.lock().unwrap_or_else(|e| e.into_inner()) = None;
+        Ok(())
+    }
+
+    /// Reloads the script from its file.
+    ///
+    /// ### Returns
+    /// - `Ok(())` if the script was reloaded, or if there is no file.
+    /// - `Err(ScriptError)` if the file cannot be read or compiled; the
+    ///   old script stays.
+    pub fn reload(&mut self) -> Result<(), ScriptError> {
+        let Some(path) = self.path.clone() else {
+            return Ok(());
+        };
+        self.stamp = stamp(&path);
+        self.set_source(&read(&path)?)
+    }
+
+    /// Reloads the script if its file changed since the last (re)load.
+    ///
+    /// Changes are detected from the modification time and size of the
+    /// file, which is cheap enough to check every frame.
+    ///
+    /// ### Returns
+    /// - `Ok(true)` if the script was reloaded.
+    /// - `Ok(false)` if the file is unchanged or there is no file.
+    /// - `Err(ScriptError)` if the changed file cannot be read or compiled.
+    pub fn reload_if_changed(&mut self) -> Result<bool, ScriptError> {
+        match &self.path {
+            Some(path) if stamp(path) != self.stamp => self.reload().map(|()| true),
+            _ => Ok(false),
+        }
+    }
+
+    /// Returns whether the script defines `choose_move(agent, neighbors)`.
+    ///
+    /// ### Returns
+    /// `true` if [`Scenario::install`] sets a [`ScriptPolicy`].
+    pub fn defines_policy(&self) -> bool {
+        self.script
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .defines(CHOOSE_MOVE_FN, 2)
+    }
+
+    /// Returns whether the script defines `regen` or `on_tick`.
+    ///
+    /// ### Returns
+    /// `true` if [`Scenario::install`] adds a [`ScriptHook`].
+    pub fn defines_hook(&self) -> bool {
+        let script = self.script.read().unwrap_or_else(|e| e.into_inner());
+        script.defines(REGEN_FN, 2) || script.defines(ON_TICK_FN, 1)
+    }
+
+    /// Applies the script's `config()` overrides.
+    ///
+    /// ### Parameters
+    /// - `base`: Configuration used for everything the script leaves out.
+    ///
+    /// ### Returns
+    /// - `Ok(config)`, equal to `base` if there is no `config()`.
+    /// - `Err(ScriptError::Runtime { .. })` if `config()` fails.
+    /// - `Err(ScriptError::InvalidConfig(..))` for unknown keys, values of
+    ///   the wrong type, or a configuration that does not validate.
+    pub fn world_config(&self, base: WorldConfig) -> Result<WorldConfig, ScriptError> {
+        let script = self.script.read().unwrap_or_else(|e| e.into_inner());
+        if !script.defines(CONFIG_FN, 0) {
+            return Ok(base);
+        }
+        let overrides = script.call(CONFIG_FN, ())?;
+        let overrides = overrides.try_cast::<Map>().ok_or_else(|| {
+            ScriptError::InvalidConfig(format!("`{CONFIG_FN}()` must return a map"))
+        })?;
+        let mut cfg = base;
+        for (key, value) in overrides {
+            apply_override(&mut cfg, &key, value)?;
+        }
+        cfg.validate()
+            .map_err(|err| ScriptError::InvalidConfig(err.to_string()))?;
+        Ok(cfg)
+    }
+
+    /// Installs the script's rules into a world.
+    ///
+    /// A [`ScriptPolicy`] replaces the world's policy if the script defines
+    /// `choose_move`, and a [`ScriptHook`] is added if it defines `regen`
+    /// or `on_tick`. Both keep following the scenario across reloads.
+    ///
+    /// ### Parameters
+    /// - `world`: The world to extend.
+    pub fn install(&self, world: &mut World) {
+        if self.defines_policy() {
+            world.set_policy(Box::new(ScriptPolicy::new(self.script.clone())));
+        }
+        if self.defines_hook() {
+            world.add_hook(Box::new(ScriptHook::new(self.script.clone())));
+        }
+    }
+
+    /// Returns and clears the most recent runtime error.
+    ///
+    /// ### Returns
+    /// - `Some(error)` if a script call failed since the last call.
+    /// - `None` otherwise.
+    pub fn take_error(&self) -> Option<ScriptError> {
+        self.script
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .last_error
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take()
+    }
+
+    /// Returns and clears the lines the script printed.
+    ///
+    /// `print(..)` and `debug(..)` in a script do not write to the host's
+    /// standard output; their lines are collected here instead.
+    ///
+    /// ### Returns
+    /// The printed lines, oldest first; only the most recent ones are kept
+    /// if nobody reads them.
+    pub fn take_output(&self) -> Vec<String> {
+        let script = self.script.read().unwrap_or_else(|e| e.into_inner());
+        let mut output = script.output.lock().unwrap_or_else(|e| e.into_inner());
+        output.drain(..).collect()
+    }
+}
+
+/// Compiles source text.
+///
+/// ### Parameters
+/// - `engine`: The scenario engine.
+/// - `source`: Rhai source code.
+///
+/// ### Returns
+/// The compiled [`AST`], or [`ScriptError::Parse`].
+fn compile(engine: &Engine, source: &str) -> Result<AST, ScriptError> {
+    engine
+        .compile(source)
+        .map_err(|err| ScriptError::Parse(err.to_string()))
+}
+
+/// Reads a scenario file.
+///
+/// ### Parameters
+/// - `path`: Path of the file.
+///
+/// ### Returns
+/// Its contents, or [`ScriptError::Io`].
+fn read(path: &Path) -> Result<String, ScriptError> {
+    std::fs::read_to_string(path)
+        .map_err(|err| ScriptError::Io(format!("{}: {err}", path.display())))
+}
+
+/// Returns the modification time and size of a file.
+///
+/// ### Parameters
+/// - `path`: Path of the file.
+///
+/// ### Returns
+/// `Some((modified, len))`, or `None` if the metadata is unavailable.
+fn stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    let meta = std::fs::metadata(path).ok()?;
+    Some((meta.modified().ok()?, meta.len()))
+}
+
+/// Applies a single `config()` entry.
+///
+/// ### Parameters
+/// - `cfg`: Configuration to change.
+/// - `key`: Name of the field.
+/// - `value`: New value; must be a non-negative integer that fits.
+///
+/// ### Returns
+/// - `Ok(())` if the field was set.
+/// - `Err(ScriptError::InvalidConfig(..))` otherwise.
+fn apply_override(cfg: &mut WorldConfig, key: &str, value: Dynamic) -> Result<(), ScriptError> {
+    let invalid = || ScriptError::InvalidConfig(format!("`{key}` must be a non-negative integer"));
+    let value = value.as_int().ok().and_then(to_usize).ok_or_else(invalid)?;
+    let small = || u32::try_from(value).map_err(|_| invalid());
+    match key {
+        "width" => cfg.width = value,
+        "height" => cfg.height = value,
+        "min_agents" => cfg.min_agents = value,
+        "max_agents" => cfg.max_agents = value,
+        "min_resource" => cfg.min_resource = small()?,
+        "max_resource" => cfg.max_resource = small()?,
+        "min_regen_rate" => cfg.min_regen_rate = small()?,
+        "max_regen_rate" => cfg.max_regen_rate = small()?,
+        "min_consumption_rate" => cfg.min_consumption_rate = small()?,
+        "max_consumption_rate" => cfg.max_consumption_rate = small()?,
+        "agent_hp" => cfg.agent_hp = small()?,
+        "seed" => cfg.seed = Some(value as u64),
+        _ => return Err(ScriptError::InvalidConfig(format!("unknown key `{key}`"))),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rcs_core::Updatable;
+
+    /// The example scenario shipped with the crate.
+    const OASIS: &str = include_str!("../scenarios/oasis.rhai");
+
+    #[test]
+    fn config_overrides_are_applied_and_checked() {
+        let scenario = Scenario::from_source(OASIS).unwrap();
+        let cfg = scenario.world_config(WorldConfig::default()).unwrap();
+        assert_eq!((cfg.width, cfg.height, cfg.max_agents), (24, 24, 40));
+
+        let unknown = Scenario::from_source("fn config() { #{ colour: 3 } }").unwrap();
+        assert!(matches!(
+            unknown.world_config(WorldConfig::default()),
+            Err(ScriptError::InvalidConfig(_))
+        ));
+        let invalid = Scenario::from_source("fn config() { #{ width: 0 } }").unwrap();
+        assert!(invalid.world_config(WorldConfig::default()).is_err());
+        let negative = Scenario::from_source("fn config() { #{ seed: -1 } }").unwrap();
+        assert!(negative.world_config(WorldConfig::default()).is_err());
+    }
+
+    #[test]
+    fn the_example_scenario_runs() {
+        let scenario = Scenario::from_source(OASIS).unwrap();
+        assert!(scenario.defines_policy());
+        assert!(scenario.defines_hook());
+        let cfg = WorldConfig {
+            seed: Some(3),
+            ..scenario.world_config(WorldConfig::default()).unwrap()
+        };
+        let mut world = World::from_config(cfg);
+        scenario.install(&mut world);
+        assert_eq!(world.policy().name(), "script");
+
+        for _ in 0..60 {
+            world.update().unwrap();
+        }
+        assert_eq!(scenario.take_error(), None);
+        // Only the oasis regenerates, so the agents gather around it.
+        let near_oasis = world
+            .agents()
+            .iter()
+            .filter(|a| a.is_alive())
+            .filter(|a| (a.cid() % 24).abs_diff(12) + (a.cid() / 24).abs_diff(12) <= 4)
+            .count();
+        assert!(near_oasis > 0);
+    }
+
+    #[test]
+    fn the_example_scenario_keeps_the_ledger_balanced() {
+        let scenario = Scenario::from_source(OASIS).unwrap();
+        let cfg = WorldConfig {
+            seed: Some(3),
+            ..scenario.world_config(WorldConfig::default()).unwrap()
+        };
+        let mut world = World::from_config(cfg);
+        world.set_debug_checks(true);
+        scenario.install(&mut world);
+
+        for _ in 0..60 {
+            world.update().unwrap();
+        }
+        let ledger = world.last_ledger().unwrap();
+        assert!(ledger.hook_added + ledger.hook_removed > 0);
+        assert!(ledger.is_balanced());
+    }
+
+    #[test]
+    fn parse_errors_keep_the_old_script() {
+        assert!(matches!(
+            Scenario::from_source("fn broken( {"),
+            Err(ScriptError::Parse(_))
+        ));
+        let mut scenario = Scenario::from_source("fn on_tick(world) {}").unwrap();
+        assert!(scenario.set_source("fn on_tick(world {").is_err());
+        assert!(scenario.defines_hook());
+    }
+
+    #[test]
+    fn changed_files_are_reloaded_into_installed_rules() {
+        let path = std::env::temp_dir().join(format!("rcs_script_{}.rhai", std::process::id()));
+        std::fs::write(&path, "fn regen(cell, tick) { 0 }").unwrap();
+        let mut scenario = Scenario::load(&path).unwrap();
+        assert_eq!(scenario.path(), Some(path.as_path()));
+        assert!(!scenario.reload_if_changed().unwrap());
+
+        let cells = vec![rcs_core::Cell::new(0, 0, 100, 0, 0)];
+        let mut world = World::new((1, 1), cells, Vec::new());
+        scenario.install(&mut world);
+        world.update().unwrap();
+        assert_eq!(world.cell(0).cur_resource(), 0);
+
+        std::fs::write(&path, "fn regen(cell, tick) { tick + 10 }").unwrap();
+        assert!(scenario.reload_if_changed().unwrap());
+        world.update().unwrap();
+        assert_eq!(world.cell(0).cur_resource(), 11);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(matches!(Scenario::load(&path), Err(ScriptError::Io(_))));
+    }
+}
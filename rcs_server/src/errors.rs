@@ -0,0 +1,28 @@
+use rcs_core::SimulationError;
+use std::{fmt, io};
+
+/// Errors raised while starting or running the server.
+#[derive(Debug)]
+pub enum ServerError {
+    /// The socket could not be bound or accepted from.
+    Io(io::Error),
+    /// The initial world configuration is invalid.
+    InvalidConfig(SimulationError),
+}
+
+impl fmt::Display for ServerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServerError::Io(err) => write!(f, "socket error: {err}"),
+            ServerError::InvalidConfig(err) => write!(f, "cannot start the server: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ServerError {}
+
+impl From<io::Error> for ServerError {
+    fn from(err: io::Error) -> Self {
+        ServerError::Io(err)
+    }
+}
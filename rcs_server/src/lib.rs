@@ -0,0 +1,23 @@
+//! A local control and streaming server for the 2-D resource-competition
+//! simulation.
+//!
+//! The server owns one [`rcs_core::World`] and speaks newline-delimited
+//! JSON over TCP, so dashboards and test harnesses written in any language
+//! can drive it:
+//!
+//! - [`protocol`]: the requests clients send and the responses, full
+//!   states and per-tick diffs the server sends back.
+//! - [`Server`]: binds the socket, serves clients and steps the world on a
+//!   timer while it is running; [`ServerHandle`] stops a spawned server.
+//! - [`ServerError`]: errors raised while starting the server.
+//!
+//! Start it with `cargo run -p rcs_server -- 127.0.0.1:7878` and try it
+//! with e.g. `nc 127.0.0.1 7878`.
+
+pub mod errors;
+pub mod protocol;
+pub mod server;
+
+pub use errors::ServerError;
+pub use protocol::{ConfigPatch, Request, Response, State, TickDiff};
+pub use server::{Server, ServerHandle};
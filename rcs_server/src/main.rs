@@ -0,0 +1,28 @@
+use rcs_core::WorldConfig;
+use rcs_server::Server;
+use std::process::ExitCode;
+
+/// Address used when none is given on the command line.
+const DEFAULT_ADDR: &str = "127.0.0.1:7878";
+
+/// Runs the server on the address given as the first argument.
+fn main() -> ExitCode {
+    let addr = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| DEFAULT_ADDR.to_string());
+    let server = match Server::bind(&addr, WorldConfig::default()) {
+        Ok(server) => server,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    eprintln!("rcs_server listening on {addr}");
+    match server.run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,372 @@
+//! The JSON-lines protocol spoken by the server.
+//!
+//! Every message is one JSON object on its own line. Clients send
+//! [`Request`]s, tagged by `"cmd"`:
+//!
+//! | Request | Effect |
+//! |---|---|
+//! | `{"cmd":"state"}` | Replies with the full [`State`]. |
+//! | `{"cmd":"step","ticks":5}` | Runs `ticks` ticks (default 1, at most [`MAX_STEP_TICKS`]), then replies `ok`. |
+//! | `{"cmd":"run","interval_ms":50}` | Starts stepping every `interval_ms` (default: keep the current interval). |
+//! | `{"cmd":"pause"}` | Stops stepping. |
+//! | `{"cmd":"reset","config":{"width":30,"seed":7}}` | Rebuilds the world; `config` patches the current configuration (see [`ConfigPatch`]), up to [`MAX_CELLS`] cells and [`MAX_AGENTS`] agents. |
+//!
+//! The server sends [`Response`]s, tagged by `"type"`:
+//!
+//! - `ok`: a command succeeded; carries the current `tick` and `running`.
+//! - `error`: a command failed or could not be parsed; carries `message`.
+//! - `state`: a full [`State`], sent in reply to `state` and to every
+//!   client after a reset.
+//! - `tick`: a [`TickDiff`], sent to every client after each tick, whoever
+//!   asked for it.
+//!
+//! Every client has a queue of [`CLIENT_QUEUE`] outgoing lines. A client
+//! that falls so far behind that its queue is full is disconnected.
+//!
+//! A client that keeps the last `state` and applies every `tick` diff to
+//! it (see [`State::apply`]) always holds the server's current state.
+
+use rcs_core::{PolicyKind, World, WorldConfig};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Most ticks a single `step` request may run; the world is locked while
+/// they run, so longer runs should use `run` and `pause`.
+pub const MAX_STEP_TICKS: u64 = 1_000;
+
+/// Most cells a `reset` may ask for, so that one request cannot exhaust
+/// the server's memory.
+pub const MAX_CELLS: usize = 1_000_000;
+
+/// Most initial agents a `reset` may ask for.
+pub const MAX_AGENTS: usize = 100_000;
+
+/// Most outgoing lines queued for one client before it is disconnected.
+pub const CLIENT_QUEUE: usize = 1_024;
+
+/// A command sent by a client.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+pub enum Request {
+    /// Ask for the full state.
+    State,
+    /// Run a number of ticks right away.
+    Step {
+        #[serde(default = "one")]
+        ticks: u64,
+    },
+    /// Start stepping on a timer.
+    Run { interval_ms: Option<u64> },
+    /// Stop stepping.
+    Pause,
+    /// Rebuild the world from a patched configuration.
+    Reset {
+        #[serde(default)]
+        config: ConfigPatch,
+    },
+}
+
+/// Default number of ticks of a `step` request.
+fn one() -> u64 {
+    1
+}
+
+/// A message sent by the server.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Response {
+    /// A command succeeded.
+    Ok { tick: u64, running: bool },
+    /// A command failed.
+    Error { message: String },
+    /// The full state of the world.
+    State(State),
+    /// What changed during one tick.
+    Tick(TickDiff),
+}
+
+/// Changes to the world configuration requested by `reset`.
+///
+/// Every field is optional and named after the [`WorldConfig`] field it
+/// replaces; `policy` takes a label such as `"greedy"` or `"vision"`.
+/// Unknown fields are rejected.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConfigPatch {
+    pub width: Option<usize>,
+    pub height: Option<usize>,
+    pub min_resource: Option<u32>,
+    pub max_resource: Option<u32>,
+    pub min_regen_rate: Option<u32>,
+    pub max_regen_rate: Option<u32>,
+    pub min_terrain_cost: Option<u32>,
+    pub max_terrain_cost: Option<u32>,
+    pub min_agents: Option<usize>,
+    pub max_agents: Option<usize>,
+    pub min_consumption_rate: Option<u32>,
+    pub max_consumption_rate: Option<u32>,
+    pub agent_hp: Option<u32>,
+    pub seed: Option<u64>,
+    pub policy: Option<String>,
+}
+
+impl ConfigPatch {
+    /// Applies the patch to a configuration.
+    ///
+    /// ### Parameters
+    /// - `base`: The configuration to change.
+    ///
+    /// ### Returns
+    /// - `Ok(config)` with the patched fields replaced.
+    /// - `Err(message)` if `policy` names no built-in policy, or the grid
+    ///   has more than [`MAX_CELLS`] cells or `max_agents` exceeds
+    ///   [`MAX_AGENTS`].
+    pub fn apply(&self, base: WorldConfig) -> Result<WorldConfig, String> {
+        let mut cfg = base;
+        cfg.width = self.width.unwrap_or(cfg.width);
+        cfg.height = self.height.unwrap_or(cfg.height);
+        cfg.min_resource = self.min_resource.unwrap_or(cfg.min_resource);
+        cfg.max_resource = self.max_resource.unwrap_or(cfg.max_resource);
+        cfg.min_regen_rate = self.min_regen_rate.unwrap_or(cfg.min_regen_rate);
+        cfg.max_regen_rate = self.max_regen_rate.unwrap_or(cfg.max_regen_rate);
+        cfg.min_terrain_cost = self.min_terrain_cost.unwrap_or(cfg.min_terrain_cost);
+        cfg.max_terrain_cost = self.max_terrain_cost.unwrap_or(cfg.max_terrain_cost);
+        cfg.min_agents = self.min_agents.unwrap_or(cfg.min_agents);
+        cfg.max_agents = self.max_agents.unwrap_or(cfg.max_agents);
+        cfg.min_consumption_rate = self
+            .min_consumption_rate
+            .unwrap_or(cfg.min_consumption_rate);
+        cfg.max_consumption_rate = self
+            .max_consumption_rate
+            .unwrap_or(cfg.max_consumption_rate);
+        cfg.agent_hp = self.agent_hp.unwrap_or(cfg.agent_hp);
+        if self.seed.is_some() {
+            cfg.seed = self.seed;
+        }
+        if let Some(name) = &self.policy {
+            cfg.policy = PolicyKind::ALL
+                .into_iter()
+                .find(|kind| kind.label() == name)
+                .ok_or_else(|| format!("unknown policy {name:?}"))?;
+        }
+        if cfg
+            .width
+            .checked_mul(cfg.height)
+            .is_none_or(|n| n > MAX_CELLS)
+        {
+            return Err(format!(
+                "a {}x{} grid has more than {MAX_CELLS} cells",
+                cfg.width, cfg.height
+            ));
+        }
+        if cfg.max_agents > MAX_AGENTS {
+            return Err(format!(
+                "{} agents are more than the limit of {MAX_AGENTS}",
+                cfg.max_agents
+            ));
+        }
+        Ok(cfg)
+    }
+}
+
+/// A living agent as seen by clients.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentState {
+    /// Stable agent id.
+    pub id: u64,
+    pub x: usize,
+    pub y: usize,
+    pub hp: u32,
+    pub energy: u32,
+}
+
+/// The full state of the world.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct State {
+    pub tick: u64,
+    pub width: usize,
+    pub height: usize,
+    pub running: bool,
+    /// Current resource of every cell in row-major order.
+    pub cells: Vec<u32>,
+    /// The living agents in agent order.
+    pub agents: Vec<AgentState>,
+}
+
+impl State {
+    /// Describes a world.
+    ///
+    /// ### Parameters
+    /// - `world`: The world.
+    /// - `running`: Whether the server is stepping it on a timer.
+    ///
+    /// ### Returns
+    /// The [`State`] of `world`.
+    pub fn of(world: &World, running: bool) -> Self {
+        let (width, height) = world.size();
+        Self {
+            tick: world.tick(),
+            width,
+            height,
+            running,
+            cells: world.cells().iter().map(|c| c.cur_resource()).collect(),
+            agents: world
+                .agents()
+                .iter()
+                .filter(|a| a.is_alive())
+                .map(|a| AgentState {
+                    id: a.id() as u64,
+                    x: a.cid() % width,
+                    y: a.cid() / width,
+                    hp: a.health_point(),
+                    energy: a.energy(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Computes what changed between two states of the same world.
+    ///
+    /// ### Parameters
+    /// - `next`: The later state.
+    ///
+    /// ### Returns
+    /// The [`TickDiff`] that turns `self` into `next`.
+    pub fn diff(&self, next: &State) -> TickDiff {
+        let before: BTreeMap<u64, AgentState> = self.agents.iter().map(|a| (a.id, *a)).collect();
+        let after: BTreeMap<u64, AgentState> = next.agents.iter().map(|a| (a.id, *a)).collect();
+        TickDiff {
+            tick: next.tick,
+            cells: (0..next.cells.len())
+                .filter(|&cid| self.cells.get(cid) != next.cells.get(cid))
+                .map(|cid| (cid, next.cells[cid]))
+                .collect(),
+            agents: next
+                .agents
+                .iter()
+                .filter(|a| before.get(&a.id) != Some(a))
+                .copied()
+                .collect(),
+            removed: self
+                .agents
+                .iter()
+                .map(|a| a.id)
+                .filter(|id| !after.contains_key(id))
+                .collect(),
+        }
+    }
+
+    /// Applies a tick diff, as a client would.
+    ///
+    /// New agents are appended, so the agent order can differ from the
+    /// server's; compare agents by id.
+    ///
+    /// ### Parameters
+    /// - `diff`: The diff of the next tick.
+    pub fn apply(&mut self, diff: &TickDiff) {
+        self.tick = diff.tick;
+        for &(cid, resource) in &diff.cells {
+            if let Some(cell) = self.cells.get_mut(cid) {
+                *cell = resource;
+            }
+        }
+        self.agents.retain(|a| !diff.removed.contains(&a.id));
+        for agent in &diff.agents {
+            match self.agents.iter_mut().find(|a| a.id == agent.id) {
+                Some(known) => *known = *agent,
+                None => self.agents.push(*agent),
+            }
+        }
+    }
+}
+
+/// What changed during one tick.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TickDiff {
+    /// The tick counter after the tick.
+    pub tick: u64,
+    /// `[cid, resource]` of every cell whose resource changed.
+    pub cells: Vec<(usize, u32)>,
+    /// Agents that were born, moved or changed hp or energy.
+    pub agents: Vec<AgentState>,
+    /// Ids of agents that died.
+    pub removed: Vec<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requests_parse_with_defaults() {
+        let parse = |line| serde_json::from_str::<Request>(line).unwrap();
+        assert_eq!(parse(r#"{"cmd":"step"}"#), Request::Step { ticks: 1 });
+        assert_eq!(
+            parse(r#"{"cmd":"run"}"#),
+            Request::Run { interval_ms: None }
+        );
+        assert_eq!(
+            parse(r#"{"cmd":"reset"}"#),
+            Request::Reset {
+                config: ConfigPatch::default()
+            }
+        );
+        assert!(
+            serde_json::from_str::<Request>(r#"{"cmd":"reset","config":{"widht":3}}"#).is_err()
+        );
+        assert!(serde_json::from_str::<Request>(r#"{"cmd":"jump"}"#).is_err());
+    }
+
+    #[test]
+    fn patches_change_only_the_given_fields() {
+        let patch = ConfigPatch {
+            width: Some(7),
+            seed: Some(3),
+            policy: Some("vision".into()),
+            ..ConfigPatch::default()
+        };
+        let cfg = patch.apply(WorldConfig::default()).unwrap();
+        assert_eq!((cfg.width, cfg.height), (7, WorldConfig::default().height));
+        assert_eq!((cfg.seed, cfg.policy), (Some(3), PolicyKind::Vision));
+
+        let bad = ConfigPatch {
+            policy: Some("teleport".into()),
+            ..ConfigPatch::default()
+        };
+        assert!(bad.apply(WorldConfig::default()).is_err());
+    }
+
+    #[test]
+    fn applying_diffs_reproduces_the_next_state() {
+        let agent = |id, x, hp| AgentState {
+            id,
+            x,
+            y: 0,
+            hp,
+            energy: 0,
+        };
+        let before = State {
+            tick: 4,
+            width: 3,
+            height: 1,
+            running: false,
+            cells: vec![1, 2, 3],
+            agents: vec![agent(0, 0, 3), agent(1, 1, 3), agent(2, 2, 3)],
+        };
+        let after = State {
+            tick: 5,
+            cells: vec![1, 0, 3],
+            agents: vec![agent(0, 0, 3), agent(2, 1, 2), agent(3, 2, 3)],
+            ..before.clone()
+        };
+
+        let diff = before.diff(&after);
+        assert_eq!(diff.cells, [(1, 0)]);
+        assert_eq!(diff.agents, [agent(2, 1, 2), agent(3, 2, 3)]);
+        assert_eq!(diff.removed, [1]);
+
+        let mut client = before;
+        client.apply(&diff);
+        assert_eq!(client, after);
+    }
+}
@@ -0,0 +1,650 @@
+use crate::ServerError;
+use crate::protocol::{CLIENT_QUEUE, MAX_STEP_TICKS, Request, Response, State};
+use rcs_core::{Updatable, World, WorldConfig};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, SyncSender};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Time between ticks while running, until a client picks another.
+const DEFAULT_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Everything the clients and the timer share.
+struct Shared {
+    session: Mutex<Session>,
+    /// Wakes the timer thread when a command changes the session.
+    wake: Condvar,
+}
+
+impl Shared {
+    /// Locks the session.
+    ///
+    /// ### Returns
+    /// The session guard; a poisoned lock is recovered, since every
+    /// command leaves the session consistent before it can panic.
+    fn lock(&self) -> MutexGuard<'_, Session> {
+        self.session
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// The world and the connected clients.
+struct Session {
+    world: World,
+    config: WorldConfig,
+    running: bool,
+    interval: Duration,
+    /// The state last sent to clients, to diff the next tick against.
+    last: State,
+    /// The connected clients.
+    clients: Vec<Client>,
+    next_client: usize,
+    shutdown: bool,
+}
+
+/// A connected client, as seen by the session.
+#[derive(Clone)]
+struct Client {
+    /// Client number.
+    id: usize,
+    /// Outgoing lines, written to the socket by the client's writer thread.
+    queue: SyncSender<String>,
+    /// The connection, to hang up on a client that falls behind.
+    stream: Arc<TcpStream>,
+}
+
+impl Client {
+    /// Queues a line without waiting.
+    ///
+    /// A client whose queue is full is too slow to keep up; its connection
+    /// is shut down, which also ends its reader and writer threads.
+    ///
+    /// ### Parameters
+    /// - `line`: The serialized message.
+    ///
+    /// ### Returns
+    /// `true` if the line was queued, `false` if the client is gone.
+    fn push(&self, line: String) -> bool {
+        match self.queue.try_send(line) {
+            Ok(()) => true,
+            Err(mpsc::TrySendError::Full(_)) => {
+                let _ = self.stream.shutdown(Shutdown::Both);
+                false
+            }
+            Err(mpsc::TrySendError::Disconnected(_)) => false,
+        }
+    }
+}
+
+/// Queues a response for one client.
+///
+/// ### Parameters
+/// - `client`: The client.
+/// - `response`: The message to send.
+fn send(client: &Client, response: &Response) {
+    // A client that has gone away is removed when its reader stops.
+    client.push(serde_json::to_string(response).expect("responses serialize"));
+}
+
+impl Session {
+    /// Creates a session around a new world.
+    ///
+    /// ### Parameters
+    /// - `config`: The world configuration.
+    ///
+    /// ### Returns
+    /// - `Ok(session)`, paused, without clients.
+    /// - `Err(ServerError::InvalidConfig)` if `config` is invalid.
+    fn new(config: WorldConfig) -> Result<Self, ServerError> {
+        let world = World::try_from_config(config).map_err(ServerError::InvalidConfig)?;
+        Ok(Self {
+            last: State::of(&world, false),
+            world,
+            config,
+            running: false,
+            interval: DEFAULT_INTERVAL,
+            clients: Vec::new(),
+            next_client: 0,
+            shutdown: false,
+        })
+    }
+
+    /// Sends a response to every client, dropping those that are gone or
+    /// have fallen behind.
+    ///
+    /// ### Parameters
+    /// - `response`: The message to send.
+    fn broadcast(&mut self, response: &Response) {
+        let line = serde_json::to_string(response).expect("responses serialize");
+        self.clients.retain(|client| client.push(line.clone()));
+    }
+
+    /// Runs ticks and streams their diffs to every client.
+    ///
+    /// ### Parameters
+    /// - `ticks`: Number of ticks to run.
+    ///
+    /// ### Returns
+    /// - `Ok(())` on success.
+    /// - `Err(message)` from the first failing tick; the ticks before it
+    ///   are kept and were streamed.
+    fn advance(&mut self, ticks: u64) -> Result<(), String> {
+        for _ in 0..ticks {
+            self.world.update().map_err(|err| err.to_string())?;
+            let next = State::of(&self.world, self.running);
+            let diff = self.last.diff(&next);
+            self.last = next;
+            self.broadcast(&Response::Tick(diff));
+        }
+        Ok(())
+    }
+
+    /// Carries out a client's request.
+    ///
+    /// ### Parameters
+    /// - `request`: The request.
+    /// - `client`: The requesting client, for the reply.
+    fn handle(&mut self, request: Request, client: &Client) {
+        let result = match request {
+            Request::State => {
+                let state = State::of(&self.world, self.running);
+                send(client, &Response::State(state));
+                return;
+            }
+            Request::Step { ticks } if ticks > MAX_STEP_TICKS => Err(format!(
+                "cannot step {ticks} ticks at once, the limit is {MAX_STEP_TICKS}"
+            )),
+            Request::Step { ticks } => self.advance(ticks),
+            Request::Run { interval_ms } => {
+                if let Some(ms) = interval_ms {
+                    self.interval = Duration::from_millis(ms.max(1));
+                }
+                self.running = true;
+                Ok(())
+            }
+            Request::Pause => {
+                self.running = false;
+                Ok(())
+            }
+            Request::Reset { config } => self.reset(config.apply(self.config)),
+        };
+        let response = match result {
+            Ok(()) => Response::Ok {
+                tick: self.world.tick(),
+                running: self.running,
+            },
+            Err(message) => Response::Error { message },
+        };
+        send(client, &response);
+    }
+
+    /// Replaces the world and sends its state to every client.
+    ///
+    /// ### Parameters
+    /// - `config`: The new configuration, or why it could not be built.
+    ///
+    /// ### Returns
+    /// - `Ok(())` on success.
+    /// - `Err(message)` if the configuration is invalid; the old world is
+    ///   kept.
+    fn reset(&mut self, config: Result<WorldConfig, String>) -> Result<(), String> {
+        let config = config?;
+        self.world = World::try_from_config(config).map_err(|err| err.to_string())?;
+        self.config = config;
+        self.last = State::of(&self.world, self.running);
+        let state = Response::State(self.last.clone());
+        self.broadcast(&state);
+        Ok(())
+    }
+}
+
+/// A bound server that has not started serving yet.
+///
+/// Every connected client can send commands and receives the diff of every
+/// tick; see [`crate::protocol`] for the messages.
+pub struct Server {
+    listener: TcpListener,
+    shared: Arc<Shared>,
+}
+
+impl Server {
+    /// Binds the socket and builds the initial world.
+    ///
+    /// ### Parameters
+    /// - `addr`: Address to listen on, e.g. `"127.0.0.1:7878"` or port 0
+    ///   for any free port.
+    /// - `config`: Configuration of the initial world.
+    ///
+    /// ### Returns
+    /// - `Ok(server)` ready to [`Server::run`] or [`Server::spawn`].
+    /// - `Err(ServerError)` if binding fails or `config` is invalid.
+    pub fn bind(addr: impl ToSocketAddrs, config: WorldConfig) -> Result<Self, ServerError> {
+        let session = Session::new(config)?;
+        Ok(Self {
+            listener: TcpListener::bind(addr)?,
+            shared: Arc::new(Shared {
+                session: Mutex::new(session),
+                wake: Condvar::new(),
+            }),
+        })
+    }
+
+    /// Returns the address the server listens on.
+    ///
+    /// ### Returns
+    /// The bound address, with the actual port if port 0 was requested.
+    pub fn local_addr(&self) -> Result<SocketAddr, ServerError> {
+        Ok(self.listener.local_addr()?)
+    }
+
+    /// Serves clients on the calling thread until the server is shut down.
+    ///
+    /// ### Returns
+    /// `Ok(())` after a [`ServerHandle::shutdown`].
+    pub fn run(self) -> Result<(), ServerError> {
+        let shared = self.shared.clone();
+        let timer = thread::spawn(move || run_timer(&shared));
+        for stream in self.listener.incoming() {
+            if self.shared.lock().shutdown {
+                break;
+            }
+            // A failed accept only affects that client.
+            if let Ok(stream) = stream {
+                let shared = self.shared.clone();
+                thread::spawn(move || serve_client(stream, &shared));
+            }
+        }
+        let _ = timer.join();
+        Ok(())
+    }
+
+    /// Serves clients on a background thread.
+    ///
+    /// ### Returns
+    /// - `Ok(handle)` to find and stop the server.
+    /// - `Err(ServerError)` if the bound address cannot be read.
+    pub fn spawn(self) -> Result<ServerHandle, ServerError> {
+        let addr = self.local_addr()?;
+        let shared = self.shared.clone();
+        let thread = thread::spawn(move || self.run());
+        Ok(ServerHandle {
+            addr,
+            shared,
+            thread,
+        })
+    }
+}
+
+/// A server running on a background thread.
+pub struct ServerHandle {
+    addr: SocketAddr,
+    shared: Arc<Shared>,
+    thread: JoinHandle<Result<(), ServerError>>,
+}
+
+impl ServerHandle {
+    /// Returns the address the server listens on.
+    ///
+    /// ### Returns
+    /// The bound address.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Stops accepting clients and stepping the world, and waits for the
+    /// server thread.
+    ///
+    /// The connections of all clients are shut down, so they read end of
+    /// file and their threads finish.
+    ///
+    /// ### Returns
+    /// The result of [`Server::run`].
+    pub fn shutdown(self) -> Result<(), ServerError> {
+        {
+            let mut session = self.shared.lock();
+            session.shutdown = true;
+            for client in session.clients.drain(..) {
+                let _ = client.stream.shutdown(Shutdown::Both);
+            }
+        }
+        self.shared.wake.notify_all();
+        // Wake the accept loop so that it sees the flag.
+        let _ = TcpStream::connect(self.addr);
+        self.thread
+            .join()
+            .expect("the server thread does not panic")
+    }
+}
+
+/// Steps the world every interval while the session is running.
+///
+/// ### Parameters
+/// - `shared`: The shared session.
+fn run_timer(shared: &Shared) {
+    let mut session = shared.lock();
+    let mut due = Instant::now();
+    loop {
+        if session.shutdown {
+            return;
+        }
+        if !session.running {
+            session = shared.wake.wait(session).unwrap_or_else(|p| p.into_inner());
+            due = Instant::now() + session.interval;
+            continue;
+        }
+        let now = Instant::now();
+        if now < due {
+            session = shared
+                .wake
+                .wait_timeout(session, due - now)
+                .unwrap_or_else(|p| p.into_inner())
+                .0;
+            continue;
+        }
+        if let Err(message) = session.advance(1) {
+            session.running = false;
+            session.broadcast(&Response::Error { message });
+        }
+        due = now + session.interval;
+    }
+}
+
+/// Reads a client's requests until it hangs up.
+///
+/// Replies and broadcasts go through a bounded queue per client, written by
+/// a separate thread, so a slow client never holds up the session; one that
+/// lets its queue fill up is disconnected.
+///
+/// ### Parameters
+/// - `stream`: The client's connection.
+/// - `shared`: The shared session.
+fn serve_client(stream: TcpStream, shared: &Shared) {
+    let (Ok(mut writer), Ok(hangup)) = (stream.try_clone(), stream.try_clone()) else {
+        return;
+    };
+    let (queue, lines) = mpsc::sync_channel::<String>(CLIENT_QUEUE);
+    thread::spawn(move || {
+        for mut line in lines {
+            line.push('\n');
+            if writer.write_all(line.as_bytes()).is_err() {
+                break;
+            }
+        }
+    });
+
+    let client = {
+        let mut session = shared.lock();
+        if session.shutdown {
+            return;
+        }
+        let client = Client {
+            id: session.next_client,
+            queue,
+            stream: Arc::new(hangup),
+        };
+        session.next_client += 1;
+        session.clients.push(client.clone());
+        client
+    };
+
+    for line in BufReader::new(&stream).lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<Request>(&line) {
+            Ok(request) => {
+                shared.lock().handle(request, &client);
+                shared.wake.notify_all();
+            }
+            Err(err) => send(
+                &client,
+                &Response::Error {
+                    message: format!("invalid request: {err}"),
+                },
+            ),
+        }
+    }
+
+    shared.lock().clients.retain(|c| c.id != client.id);
+    let _ = stream.shutdown(Shutdown::Both);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::MAX_AGENTS;
+    use std::io::BufReader;
+
+    /// A test client speaking the protocol.
+    struct Client {
+        reader: BufReader<TcpStream>,
+        writer: TcpStream,
+    }
+
+    impl Client {
+        fn connect(addr: SocketAddr) -> Self {
+            let writer = TcpStream::connect(addr).unwrap();
+            writer
+                .set_read_timeout(Some(Duration::from_secs(10)))
+                .unwrap();
+            let reader = BufReader::new(writer.try_clone().unwrap());
+            Self { reader, writer }
+        }
+
+        fn send(&mut self, line: &str) {
+            writeln!(self.writer, "{line}").unwrap();
+        }
+
+        fn recv(&mut self) -> Response {
+            let mut line = String::new();
+            self.reader.read_line(&mut line).unwrap();
+            serde_json::from_str(&line).unwrap()
+        }
+
+        fn state(&mut self) -> State {
+            self.send(r#"{"cmd":"state"}"#);
+            match self.recv() {
+                Response::State(state) => state,
+                other => panic!("expected a state, got {other:?}"),
+            }
+        }
+    }
+
+    fn spawn_server() -> ServerHandle {
+        let config = WorldConfig {
+            width: 8,
+            height: 6,
+            seed: Some(5),
+            ..WorldConfig::default()
+        };
+        Server::bind("127.0.0.1:0", config)
+            .unwrap()
+            .spawn()
+            .unwrap()
+    }
+
+    fn sorted(mut state: State) -> State {
+        state.agents.sort_by_key(|a| a.id);
+        state
+    }
+
+    #[test]
+    fn every_client_can_follow_the_world_through_diffs() {
+        let server = spawn_server();
+        let mut a = Client::connect(server.addr());
+        let mut b = Client::connect(server.addr());
+        let mut seen_by_a = a.state();
+        let mut seen_by_b = b.state();
+        assert_eq!(seen_by_a, seen_by_b);
+
+        a.send(r#"{"cmd":"step","ticks":3}"#);
+        for tick in 1..=3 {
+            for (client, seen) in [(&mut a, &mut seen_by_a), (&mut b, &mut seen_by_b)] {
+                match client.recv() {
+                    Response::Tick(diff) => {
+                        assert_eq!(diff.tick, tick);
+                        seen.apply(&diff);
+                    }
+                    other => panic!("expected a tick, got {other:?}"),
+                }
+            }
+        }
+        assert_eq!(
+            a.recv(),
+            Response::Ok {
+                tick: 3,
+                running: false
+            }
+        );
+
+        let current = sorted(b.state());
+        assert_eq!(sorted(seen_by_a), current);
+        assert_eq!(sorted(seen_by_b), current);
+        server.shutdown().unwrap();
+    }
+
+    #[test]
+    fn bad_requests_are_answered_and_resets_are_broadcast() {
+        let server = spawn_server();
+        let mut client = Client::connect(server.addr());
+
+        client.send("not json");
+        assert!(matches!(client.recv(), Response::Error { .. }));
+        client.send(r#"{"cmd":"reset","config":{"width":0}}"#);
+        assert!(matches!(client.recv(), Response::Error { .. }));
+        assert_eq!(client.state().width, 8);
+
+        client.send(r#"{"cmd":"step","ticks":2}"#);
+        for _ in 0..2 {
+            assert!(matches!(client.recv(), Response::Tick(_)));
+        }
+        assert!(matches!(client.recv(), Response::Ok { tick: 2, .. }));
+
+        client.send(r#"{"cmd":"reset","config":{"width":5,"height":4,"seed":2}}"#);
+        match client.recv() {
+            Response::State(state) => {
+                assert_eq!((state.tick, state.width, state.height), (0, 5, 4));
+                assert_eq!(state.cells.len(), 20);
+            }
+            other => panic!("expected a state, got {other:?}"),
+        }
+        assert!(matches!(client.recv(), Response::Ok { tick: 0, .. }));
+        server.shutdown().unwrap();
+    }
+
+    #[test]
+    fn running_streams_ticks_until_paused() {
+        let server = spawn_server();
+        let mut client = Client::connect(server.addr());
+
+        client.send(r#"{"cmd":"run","interval_ms":1}"#);
+        assert_eq!(
+            client.recv(),
+            Response::Ok {
+                tick: 0,
+                running: true
+            }
+        );
+        for tick in 1..=3 {
+            match client.recv() {
+                Response::Tick(diff) => assert_eq!(diff.tick, tick),
+                other => panic!("expected a tick, got {other:?}"),
+            }
+        }
+
+        client.send(r#"{"cmd":"pause"}"#);
+        let mut last = 3;
+        let paused_at = loop {
+            match client.recv() {
+                Response::Tick(diff) => last = diff.tick,
+                Response::Ok {
+                    tick,
+                    running: false,
+                } => break tick,
+                other => panic!("unexpected {other:?}"),
+            }
+        };
+        assert_eq!(paused_at, last);
+        let state = client.state();
+        assert_eq!((state.tick, state.running), (paused_at, false));
+        server.shutdown().unwrap();
+    }
+
+    #[test]
+    fn long_steps_are_refused() {
+        let server = spawn_server();
+        let mut client = Client::connect(server.addr());
+
+        client.send(&format!(
+            r#"{{"cmd":"step","ticks":{}}}"#,
+            MAX_STEP_TICKS + 1
+        ));
+        assert!(matches!(client.recv(), Response::Error { .. }));
+        assert_eq!(client.state().tick, 0);
+        server.shutdown().unwrap();
+    }
+
+    #[test]
+    fn oversized_worlds_are_refused() {
+        let server = spawn_server();
+        let mut client = Client::connect(server.addr());
+
+        client.send(r#"{"cmd":"reset","config":{"width":100000,"height":100000}}"#);
+        assert!(matches!(client.recv(), Response::Error { .. }));
+        client.send(&format!(
+            r#"{{"cmd":"reset","config":{{"max_agents":{}}}}}"#,
+            MAX_AGENTS + 1
+        ));
+        assert!(matches!(client.recv(), Response::Error { .. }));
+        assert_eq!(client.state().width, 8);
+        server.shutdown().unwrap();
+    }
+
+    #[test]
+    fn shutdown_hangs_up_on_clients() {
+        let server = spawn_server();
+        let mut client = Client::connect(server.addr());
+        client.state();
+
+        server.shutdown().unwrap();
+        let mut line = String::new();
+        assert_eq!(client.reader.read_line(&mut line).unwrap(), 0);
+    }
+
+    #[test]
+    fn clients_that_fall_behind_are_disconnected() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut peer = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        peer.set_read_timeout(Some(Duration::from_secs(10)))
+            .unwrap();
+        let (stream, _) = listener.accept().unwrap();
+
+        let mut session = Session::new(WorldConfig {
+            width: 4,
+            height: 4,
+            seed: Some(1),
+            ..WorldConfig::default()
+        })
+        .unwrap();
+        let (queue, lines) = mpsc::sync_channel(2);
+        session.clients.push(super::Client {
+            id: 0,
+            queue,
+            stream: Arc::new(stream),
+        });
+
+        session.advance(2).unwrap();
+        assert_eq!(session.clients.len(), 1);
+        session.advance(1).unwrap();
+        assert!(session.clients.is_empty());
+        assert_eq!(lines.try_iter().count(), 2);
+        let mut rest = Vec::new();
+        assert_eq!(std::io::Read::read_to_end(&mut peer, &mut rest).unwrap(), 0);
+    }
+}